@@ -9,6 +9,7 @@
 
 #[macro_use]
 extern crate bencher;
+extern crate aom_sys;
 extern crate libc;
 extern crate rand;
 extern crate rav1e;
@@ -379,10 +380,6 @@ pub fn write_b() -> Vec<TestDescAndFn> {
 }
 
 fn write_b_bench(b: &mut Bencher, tx_size: TxSize, qindex: usize) {
-  unsafe {
-    av1_rtcd();
-    aom_dsp_rtcd();
-  }
   let mut fi = FrameInvariants::new(1024, 1024, qindex, 10);
   let w = ec::Writer::new();
   let fc = CDFContext::new(fi.qindex as u8);
@@ -1,96 +1,50 @@
 // build.rs
 
-extern crate pkg_config;
-extern crate bindgen;
-extern crate cmake;
-extern crate cc;
+#[cfg(feature = "capi")]
+extern crate cbindgen;
 
-use std::env;
-use std::path::Path;
+/// Generate the C header and the pkg-config file for the C API.
+#[cfg(feature = "capi")]
+fn generate_capi() {
+    use std::env;
+    use std::fs::{self, File};
+    use std::io::Write;
+    use std::path::{Path, PathBuf};
 
-use std::fs::{self, File};
-use std::io::Write;
-
-fn format_write(builder: bindgen::Builder, output: &str) {
-    let s = builder
-        .generate()
+    let cargo_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let root = Path::new(&cargo_dir);
+
+    // The header and the pkg-config file go in the include and pkgconfig
+    // directories of OUT_DIR, exported to dependents as DEP_RAV1E_INCLUDE
+    // and DEP_RAV1E_PKGCONFIG, from where install_capi.sh installs them.
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    let include_dir = out_dir.join("include");
+    fs::create_dir_all(&include_dir).unwrap();
+    let config = cbindgen::Config::from_root_or_default(root);
+    cbindgen::generate_with_config(root, config)
         .unwrap()
-        .to_string()
-        .replace("/**", "/*")
-        .replace("/*!", "/*");
+        .write_to_file(include_dir.join("rav1e.h"));
 
-    let mut file = File::create(output).unwrap();
-
-    let _ = file.write(s.as_bytes());
+    let pkgconfig_dir = out_dir.join("pkgconfig");
+    fs::create_dir_all(&pkgconfig_dir).unwrap();
+    let prefix = env::var("RAV1E_PREFIX").unwrap_or("/usr/local".to_string());
+    let pc = fs::read_to_string(root.join("rav1e.pc.in"))
+        .unwrap()
+        .replace("@PREFIX@", &prefix)
+        .replace("@VERSION@", &env::var("CARGO_PKG_VERSION").unwrap());
+    let mut file = File::create(pkgconfig_dir.join("rav1e.pc")).unwrap();
+    file.write_all(pc.as_bytes()).unwrap();
+
+    println!("cargo:include={}", include_dir.display());
+    println!("cargo:pkgconfig={}", pkgconfig_dir.display());
+
+    println!("cargo:rerun-if-changed=src/capi.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+    println!("cargo:rerun-if-changed=rav1e.pc.in");
+    println!("cargo:rerun-if-env-changed=RAV1E_PREFIX");
 }
 
 fn main() {
-    let cargo_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
-    let build_path = Path::new(&cargo_dir).join("aom_build/aom");
-    let debug = if let Some(v) = env::var("PROFILE").ok() {
-        match v.as_str() {
-            "bench" | "release" => "0",
-            _ => "1",
-        }
-    } else {
-        "0"
-    };
-
-    let dst = cmake::Config::new(build_path)
-        .define("CONFIG_DEBUG", debug)
-        .define("CONFIG_EXPERIMENTAL", "1")
-        .define("CONFIG_UNIT_TESTS", "0")
-        .define("CONFIG_EXT_PARTITION", "0")
-        .define("CONFIG_EXT_PARTITION_TYPES", "0")
-        .define("CONFIG_OBU", "0")
-        .define("CONFIG_FILTER_INTRA", "0")
-        .define("CONFIG_EXT_SKIP", "0")
-        .define("CONFIG_LV_MAP", "1")
-        .define("CONFIG_ANALYZER", "0")
-        .define("CONFIG_Q_ADAPT_PROBS", "0")
-        .define("CONFIG_INTRA_EDGE", "0")
-        .define("ENABLE_DOCS", "0")
-        .build();
-
-    // Dirty hack to force a rebuild whenever the defaults are changed upstream
-    let _ = fs::remove_file(dst.join("build/CMakeCache.txt"));
-
-    env::set_var("PKG_CONFIG_PATH", dst.join("lib/pkgconfig"));
-
-
-    let libs = pkg_config::Config::new().statik(true).probe("aom").unwrap();
-    let headers = libs.include_paths.clone();
-
-    let mut builder = bindgen::builder()
-        .raw_line("#![allow(dead_code)]")
-        .raw_line("#![allow(non_camel_case_types)]")
-        .raw_line("#![allow(non_snake_case)]")
-        .raw_line("#![allow(non_upper_case_globals)]")
-        .blacklist_type("max_align_t")
-        .rustfmt_bindings(false)
-        .header("data/aom.h");
-
-    for header in headers {
-        builder = builder.clang_arg("-I").clang_arg(header.to_str().unwrap());
-    }
-
-    // Manually fix the comment so rustdoc won't try to pick them
-    format_write(builder, "tests/aom.rs");
-
-    {
-        use std::fs;
-        fn rerun_dir<P: AsRef<Path>>(dir: P) {
-            for entry in fs::read_dir(dir).unwrap() {
-                let entry = entry.unwrap();
-                let path = entry.path();
-                println!("cargo:rerun-if-changed={}", path.to_string_lossy());
-
-                if path.is_dir() {
-                    rerun_dir(path);
-                }
-            }
-        }
-
-        rerun_dir("aom_build");
-    }
+    #[cfg(feature = "capi")]
+    generate_capi();
 }
@@ -0,0 +1,33 @@
+#!/bin/bash
+
+# Build the C API and install rav1e.h, rav1e.pc and the libraries under
+# PREFIX (/usr/local by default), staged in DESTDIR if set.
+
+# Stop on first error
+set -e
+
+PREFIX="${PREFIX:-/usr/local}"
+TARGET_DIR="${CARGO_TARGET_DIR:-target}/release"
+
+# build.rs writes the header and the pkg-config file to its OUT_DIR, which
+# cargo reports when the build script runs
+OUT_DIR=$(RAV1E_PREFIX="$PREFIX" cargo build --release --lib --features capi \
+  --message-format=json |
+  grep '"reason":"build-script-executed"' |
+  grep -o '"out_dir":"[^"]*/build/rav1e-[^"]*"' |
+  cut -d '"' -f 4)
+
+if [ -z "$OUT_DIR" ]; then
+  (>&2 echo "ERROR: Failed to find the build script output of rav1e")
+  exit 1
+fi
+
+install -d "$DESTDIR$PREFIX/include" "$DESTDIR$PREFIX/lib/pkgconfig"
+install -m 644 "$OUT_DIR/include/rav1e.h" "$DESTDIR$PREFIX/include"
+install -m 644 "$OUT_DIR/pkgconfig/rav1e.pc" "$DESTDIR$PREFIX/lib/pkgconfig"
+install -m 644 "$TARGET_DIR/librav1e.a" "$DESTDIR$PREFIX/lib"
+for LIB in librav1e.so librav1e.dylib; do
+  if [ -f "$TARGET_DIR/$LIB" ]; then
+    install -m 755 "$TARGET_DIR/$LIB" "$DESTDIR$PREFIX/lib"
+  fi
+done
@@ -0,0 +1,165 @@
+// Copyright (c) 2018, The rav1e contributors. All rights reserved
+//
+// This source code is subject to the terms of the BSD 2 Clause License and
+// the Alliance for Open Media Patent License 1.0. If the BSD 2 Clause License
+// was not distributed with this source code in the LICENSE file, you can
+// obtain it at www.aomedia.org/license/software. If the Alliance for Open
+// Media Patent License 1.0 was not distributed with this source code in the
+// PATENTS file, you can obtain it at www.aomedia.org/license/patent.
+
+//! Length delimited bitstream format of Annex B.
+//!
+//! The packets produced by `encode_frame` use the low overhead format, where
+//! each OBU carries its own size; they are reframed here with
+//! temporal_unit_size, frame_unit_size and obu_length fields instead.
+
+use super::*;
+
+const OBU_HAS_SIZE_FIELD: u8 = 0x2;
+const OBU_EXTENSION_FLAG: u8 = 0x4;
+
+fn invalid_packet() -> EncoderError {
+  EncoderError::UnsupportedInput("malformed OBU in packet".to_string())
+}
+
+fn read_uleb128(data: &[u8]) -> Result<(u64, usize), EncoderError> {
+  let mut value = 0;
+  for (i, &byte) in data.iter().enumerate().take(8) {
+    value |= ((byte & 0x7F) as u64) << (7 * i);
+    if byte & 0x80 == 0 {
+      return Ok((value, i + 1));
+    }
+  }
+  Err(invalid_packet())
+}
+
+fn write_uleb128(buf: &mut Vec<u8>, value: u64) {
+  let mut bw = BitWriter::<BE>::new(buf);
+  bw.write_uleb128(value).unwrap();
+}
+
+/// Split a low overhead format packet into its OBUs, returned as the OBU
+/// header without size field followed by the payload.
+fn split_obus(
+  mut data: &[u8]
+) -> Result<Vec<(OBU_Type, Vec<u8>)>, EncoderError> {
+  let mut obus = Vec::new();
+  while !data.is_empty() {
+    let header = data[0];
+    let header_len = if header & OBU_EXTENSION_FLAG != 0 { 2 } else { 1 };
+    if header & OBU_HAS_SIZE_FIELD == 0 || data.len() < header_len {
+      return Err(invalid_packet());
+    }
+    let (size, size_len) = read_uleb128(&data[header_len..])?;
+    let payload_start = header_len + size_len;
+    if size > (data.len() - payload_start) as u64 {
+      return Err(invalid_packet());
+    }
+    let obu_end = payload_start + size as usize;
+
+    let mut obu = Vec::with_capacity(header_len + size as usize);
+    obu.push(header & !OBU_HAS_SIZE_FIELD);
+    obu.extend_from_slice(&data[1..header_len]);
+    obu.extend_from_slice(&data[payload_start..obu_end]);
+
+    let obu_type = match (header >> 3) & 0xF {
+      1 => OBU_Type::OBU_SEQUENCE_HEADER,
+      2 => OBU_Type::OBU_TEMPORAL_DELIMITER,
+      3 => OBU_Type::OBU_FRAME_HEADER,
+      4 => OBU_Type::OBU_TILE_GROUP,
+      5 => OBU_Type::OBU_METADATA,
+      6 => OBU_Type::OBU_FRAME,
+      7 => OBU_Type::OBU_REDUNDANT_FRAME_HEADER,
+      8 => OBU_Type::OBU_TILE_LIST,
+      _ => OBU_Type::OBU_PADDING
+    };
+    obus.push((obu_type, obu));
+    data = &data[obu_end..];
+  }
+  Ok(obus)
+}
+
+/// Reframe a low overhead format packet as an Annex B temporal_unit().
+pub fn temporal_unit(packet: &[u8]) -> Result<Vec<u8>, EncoderError> {
+  // A frame unit starts with each frame header, and the OBUs preceding the
+  // first one belong to the first frame unit.
+  let mut frame_units: Vec<Vec<u8>> = Vec::new();
+  let mut frame_unit = Vec::new();
+  let mut has_frame_header = false;
+  for (obu_type, obu) in split_obus(packet)? {
+    let starts_frame = obu_type == OBU_Type::OBU_FRAME_HEADER
+      || obu_type == OBU_Type::OBU_FRAME;
+    if starts_frame && has_frame_header {
+      frame_units.push(frame_unit);
+      frame_unit = Vec::new();
+    }
+    has_frame_header |= starts_frame;
+    write_uleb128(&mut frame_unit, obu.len() as u64); // obu_length
+    frame_unit.extend_from_slice(&obu);
+  }
+  frame_units.push(frame_unit);
+
+  let mut frame_units_data = Vec::new();
+  for frame_unit in frame_units {
+    write_uleb128(&mut frame_units_data, frame_unit.len() as u64); // frame_unit_size
+    frame_units_data.extend_from_slice(&frame_unit);
+  }
+
+  let mut temporal_unit = Vec::new();
+  write_uleb128(&mut temporal_unit, frame_units_data.len() as u64); // temporal_unit_size
+  temporal_unit.extend_from_slice(&frame_units_data);
+  Ok(temporal_unit)
+}
+
+pub struct AnnexBMuxer {
+  output: Output
+}
+
+impl AnnexBMuxer {
+  pub fn new(output: Output) -> AnnexBMuxer {
+    AnnexBMuxer { output }
+  }
+}
+
+impl Muxer for AnnexBMuxer {
+  fn write_frame(
+    &mut self, _pts: u64, data: &[u8], _frame_type: FrameType
+  ) -> Result<(), EncoderError> {
+    self.output.write_all(&temporal_unit(data)?)?;
+    Ok(())
+  }
+
+  fn finish(&mut self) -> Result<(), EncoderError> {
+    self.output.flush()?;
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn reframe_packet() {
+    // temporal delimiter, frame header and a tile group in one packet
+    let packet = [0x12, 0x00, 0x1A, 0x02, 0xAA, 0xBB, 0x22, 0x01, 0xCC];
+    let expected = [
+      0x0A, // temporal_unit_size
+      0x09, // frame_unit_size
+      0x01, 0x10, // temporal delimiter
+      0x03, 0x18, 0xAA, 0xBB, // frame header
+      0x02, 0x20, 0xCC // tile group
+    ];
+    assert_eq!(&temporal_unit(&packet).unwrap()[..], &expected[..]);
+  }
+
+  #[test]
+  fn reject_truncated_packet() {
+    // OBU size past the end of the packet
+    assert!(temporal_unit(&[0x12, 0x00, 0x1A, 0x05, 0xAA]).is_err());
+    // leb128 size without a last byte
+    assert!(temporal_unit(&[0x1A, 0x80, 0x80]).is_err());
+    // OBU without a size field
+    assert!(temporal_unit(&[0x18, 0xAA]).is_err());
+  }
+}
@@ -0,0 +1,210 @@
+// Copyright (c) 2018, The rav1e contributors. All rights reserved
+//
+// This source code is subject to the terms of the BSD 2 Clause License and
+// the Alliance for Open Media Patent License 1.0. If the BSD 2 Clause License
+// was not distributed with this source code in the LICENSE file, you can
+// obtain it at www.aomedia.org/license/software. If the Alliance for Open
+// Media Patent License 1.0 was not distributed with this source code in the
+// PATENTS file, you can obtain it at www.aomedia.org/license/patent.
+
+//! AVIF (HEIF) container for a single still picture.
+
+use super::*;
+use mp4::{write_box, write_full_box};
+
+const ITEM_ID: u16 = 1;
+
+pub struct AvifMuxer {
+  output: Output,
+  width: usize,
+  height: usize,
+  num_channels: u8,
+  bit_depth: u8,
+  color_primaries: ColorPrimaries,
+  transfer_characteristics: TransferCharacteristics,
+  matrix_coefficients: MatrixCoefficients,
+  pixel_range: PixelRange,
+  av1c: Vec<u8>,
+  item: Option<Vec<u8>>
+}
+
+impl AvifMuxer {
+  pub fn new(
+    output: Output, sequence: &Sequence
+  ) -> Result<AvifMuxer, EncoderError> {
+    if !sequence.still_picture {
+      return Err(EncoderError::UnsupportedInput(
+        "AVIF output requires a still picture sequence".to_string()
+      ));
+    }
+    Ok(AvifMuxer {
+      output,
+      width: sequence.max_frame_width as usize,
+      height: sequence.max_frame_height as usize,
+      num_channels: if sequence.chroma_sampling == ChromaSampling::CS400 {
+        1
+      } else {
+        3
+      },
+      bit_depth: sequence.bit_depth as u8,
+      color_primaries: sequence.color_primaries,
+      transfer_characteristics: sequence.transfer_characteristics,
+      matrix_coefficients: sequence.matrix_coefficients,
+      pixel_range: sequence.pixel_range,
+      av1c: av1c_record(sequence)?,
+      item: None
+    })
+  }
+
+  fn ftyp(&self, buf: &mut Vec<u8>) {
+    let mut ftyp = Vec::new();
+    ftyp.extend_from_slice(b"avif"); // major_brand
+    ftyp.extend_from_slice(&[0, 0, 0, 0]); // minor_version
+    for brand in &[b"avif", b"mif1", b"miaf"] {
+      ftyp.extend_from_slice(*brand);
+    }
+    write_box(buf, b"ftyp", &ftyp);
+  }
+
+  fn ipco(&self, buf: &mut Vec<u8>) {
+    let mut ipco = Vec::new();
+    write_box(&mut ipco, b"av1C", &self.av1c);
+
+    let mut ispe = Vec::new();
+    {
+      let mut bw = BitWriter::<BE>::new(&mut ispe);
+      bw.write(32, self.width as u32).unwrap();
+      bw.write(32, self.height as u32).unwrap();
+    }
+    write_full_box(&mut ipco, b"ispe", 0, 0, &ispe);
+
+    let mut pixi = vec![self.num_channels];
+    for _ in 0..self.num_channels {
+      pixi.push(self.bit_depth);
+    }
+    write_full_box(&mut ipco, b"pixi", 0, 0, &pixi);
+
+    let mut colr = Vec::new();
+    {
+      let mut bw = BitWriter::<BE>::new(&mut colr);
+      bw.write_bytes(b"nclx").unwrap(); // colour_type
+      bw.write(16, self.color_primaries as u16).unwrap();
+      bw.write(16, self.transfer_characteristics as u16).unwrap();
+      bw.write(16, self.matrix_coefficients as u16).unwrap();
+      bw.write_bit(self.pixel_range == PixelRange::FULL).unwrap();
+      bw.write(7, 0).unwrap(); // reserved
+    }
+    write_box(&mut ipco, b"colr", &colr);
+
+    write_box(buf, b"ipco", &ipco);
+  }
+
+  fn meta(&self, item_offset: u32, item_length: u32) -> Vec<u8> {
+    let mut hdlr = Vec::new();
+    {
+      let mut bw = BitWriter::<BE>::new(&mut hdlr);
+      bw.write(32, 0).unwrap(); // pre_defined
+      bw.write_bytes(b"pict").unwrap(); // handler_type
+      for _ in 0..3 {
+        bw.write(32, 0).unwrap(); // reserved
+      }
+      bw.write(8, 0).unwrap(); // empty name
+    }
+
+    let mut pitm = Vec::new();
+    {
+      let mut bw = BitWriter::<BE>::new(&mut pitm);
+      bw.write(16, ITEM_ID).unwrap(); // primary item
+    }
+
+    let mut iloc = Vec::new();
+    {
+      let mut bw = BitWriter::<BE>::new(&mut iloc);
+      bw.write(4, 4).unwrap(); // offset_size
+      bw.write(4, 4).unwrap(); // length_size
+      bw.write(4, 0).unwrap(); // base_offset_size
+      bw.write(4, 0).unwrap(); // reserved
+      bw.write(16, 1).unwrap(); // item_count
+      bw.write(16, ITEM_ID).unwrap();
+      bw.write(16, 0).unwrap(); // data_reference_index, this file
+      bw.write(16, 1).unwrap(); // extent_count
+      bw.write(32, item_offset).unwrap();
+      bw.write(32, item_length).unwrap();
+    }
+
+    let mut infe = Vec::new();
+    {
+      let mut bw = BitWriter::<BE>::new(&mut infe);
+      bw.write(16, ITEM_ID).unwrap();
+      bw.write(16, 0).unwrap(); // item_protection_index
+      bw.write_bytes(b"av01").unwrap(); // item_type
+      bw.write(8, 0).unwrap(); // empty item_name
+    }
+    let mut iinf = vec![0, 1]; // entry_count
+    write_full_box(&mut iinf, b"infe", 2, 0, &infe);
+
+    // Associate the item with every property in ipco
+    let mut ipma = Vec::new();
+    {
+      let mut bw = BitWriter::<BE>::new(&mut ipma);
+      bw.write(32, 1).unwrap(); // entry_count
+      bw.write(16, ITEM_ID).unwrap();
+      bw.write(8, 4).unwrap(); // association_count
+      bw.write(8, 0x81).unwrap(); // av1C, essential
+      bw.write(8, 0x02).unwrap(); // ispe
+      bw.write(8, 0x03).unwrap(); // pixi
+      bw.write(8, 0x04).unwrap(); // colr
+    }
+    let mut iprp = Vec::new();
+    self.ipco(&mut iprp);
+    write_full_box(&mut iprp, b"ipma", 0, 0, &ipma);
+
+    let mut meta = Vec::new();
+    write_full_box(&mut meta, b"hdlr", 0, 0, &hdlr);
+    write_full_box(&mut meta, b"pitm", 0, 0, &pitm);
+    write_full_box(&mut meta, b"iloc", 0, 0, &iloc);
+    write_full_box(&mut meta, b"iinf", 0, 0, &iinf);
+    write_box(&mut meta, b"iprp", &iprp);
+    let mut buf = Vec::new();
+    write_full_box(&mut buf, b"meta", 0, 0, &meta);
+    buf
+  }
+}
+
+impl Muxer for AvifMuxer {
+  fn write_frame(
+    &mut self, _pts: u64, data: &[u8], frame_type: FrameType
+  ) -> Result<(), EncoderError> {
+    if self.item.is_some() || frame_type != FrameType::KEY {
+      return Err(EncoderError::Internal("AVIF holds a single key frame"));
+    }
+    self.item = Some(strip_temporal_delimiter(data).to_vec());
+    Ok(())
+  }
+
+  fn finish(&mut self) -> Result<(), EncoderError> {
+    let item = match self.item.take() {
+      Some(item) => item,
+      None => return Err(EncoderError::Internal("no frame was encoded"))
+    };
+
+    let mut header = Vec::new();
+    self.ftyp(&mut header);
+
+    // The size of meta does not depend on the item offset it contains
+    let meta_size = self.meta(0, 0).len();
+    let item_offset = header.len() + meta_size + 8;
+    header.extend_from_slice(&self.meta(item_offset as u32, item.len() as u32));
+
+    {
+      let mut bw = BitWriter::<BE>::new(&mut header);
+      bw.write(32, (item.len() + 8) as u32).unwrap();
+      bw.write_bytes(b"mdat").unwrap();
+    }
+
+    self.output.write_all(&header)?;
+    self.output.write_all(&item)?;
+    self.output.flush()?;
+    Ok(())
+  }
+}
@@ -0,0 +1,312 @@
+// Copyright (c) 2017-2018, The rav1e contributors. All rights reserved
+//
+// This source code is subject to the terms of the BSD 2 Clause License and
+// the Alliance for Open Media Patent License 1.0. If the BSD 2 Clause License
+// was not distributed with this source code in the LICENSE file, you can
+// obtain it at www.aomedia.org/license/software. If the Alliance for Open
+// Media Patent License 1.0 was not distributed with this source code in the
+// PATENTS file, you can obtain it at www.aomedia.org/license/patent.
+
+use clap::{App, Arg};
+use rav1e::plane::PlaneConfig;
+use rav1e::*;
+use source::*;
+use std;
+use std::fs::File;
+use std::io::prelude::*;
+use std::process;
+use y4m;
+
+pub struct CliOptions {
+  pub input: Box<Read>,
+  pub output: Output,
+  pub output_format: OutputFormat,
+  pub rec: Option<Box<Write>>,
+  pub limit: u64,
+  /// Print a line for each encoded frame
+  pub verbose: bool,
+  pub config: EncoderConfig,
+  /// Format of the input when it is raw YUV rather than YUV4MPEG2
+  pub raw: Option<VideoDetails>
+}
+
+/// Parse the command line and open the files it names.
+pub fn parse_cli() -> CliOptions {
+  let matches = App::new("rav1e")
+    .version("0.1.0")
+    .about("AV1 video encoder")
+    .arg(
+      Arg::with_name("INPUT")
+        .help("Uncompressed YUV4MPEG2 video input, or raw planar YUV when --width and --height are given")
+        .required(true)
+        .index(1)
+    ).arg(
+      Arg::with_name("OUTPUT")
+        .help("Compressed AV1 video output, in WebM for .webm or .mkv paths, MP4 for .mp4 paths and IVF otherwise. A .avif path encodes the first frame as a still image")
+        .short("o")
+        .long("output")
+        .required(true)
+        .takes_value(true)
+    ).arg(
+      Arg::with_name("ANNEXB")
+        .help("Write a length delimited Annex B bitstream instead of a container")
+        .long("annexb")
+    ).arg(Arg::with_name("RECONSTRUCTION").short("r").takes_value(true))
+    .arg(
+      Arg::with_name("LIMIT")
+        .help("Maximum number of frames to encode")
+        .short("l")
+        .long("limit")
+        .takes_value(true)
+        .default_value("0")
+    ).arg(
+      Arg::with_name("VERBOSE")
+        .help("Print the type and size of each encoded frame")
+        .short("v")
+        .long("verbose")
+    ).arg(
+      Arg::with_name("QP")
+        .help("Quantizer (1-255)")
+        .long("quantizer")
+        .takes_value(true)
+        .default_value("100")
+    ).arg(
+      Arg::with_name("SPEED")
+        .help("Speed level (0(slow)-10(fast))")
+        .short("s")
+        .long("speed")
+        .takes_value(true)
+        .default_value("3")
+    ).arg(
+      Arg::with_name("KEYINT")
+        .help("Maximum interval between key frames")
+        .long("keyint")
+        .takes_value(true)
+        .default_value("30")
+    ).arg(
+      Arg::with_name("MINI_GOP_LEN")
+        .help("Maximum number of frames coded out of order (1-16)")
+        .long("mini-gop-len")
+        .takes_value(true)
+        .default_value("4")
+    ).arg(
+      Arg::with_name("TILE_COLS_LOG2")
+        .help("Log2 of the number of tile columns (0-6)")
+        .long("tile-cols-log2")
+        .takes_value(true)
+        .default_value("0")
+    ).arg(
+      Arg::with_name("TILE_ROWS_LOG2")
+        .help("Log2 of the number of tile rows (0-6)")
+        .long("tile-rows-log2")
+        .takes_value(true)
+        .default_value("0")
+    ).arg(
+      Arg::with_name("WIDTH")
+        .help("Width of raw YUV input")
+        .long("width")
+        .takes_value(true)
+        .requires("HEIGHT")
+    ).arg(
+      Arg::with_name("HEIGHT")
+        .help("Height of raw YUV input")
+        .long("height")
+        .takes_value(true)
+        .requires("WIDTH")
+    ).arg(
+      Arg::with_name("FPS")
+        .help("Frame rate of raw YUV input, as an integer or a num/den ratio")
+        .long("fps")
+        .takes_value(true)
+        .default_value("30")
+    ).arg(
+      Arg::with_name("PIX_FMT")
+        .help("Pixel format of raw YUV input, with 16-bit little-endian samples for the p10 and p12 formats")
+        .long("pix-fmt")
+        .takes_value(true)
+        .possible_values(&[
+          "i420", "i422", "i444", "i420p10", "i422p10", "i444p10", "i420p12",
+          "i422p12", "i444p12"
+        ]).default_value("i420")
+    ).get_matches();
+
+  let output_format = if matches.is_present("ANNEXB") {
+    OutputFormat::AnnexB
+  } else {
+    OutputFormat::from_path(matches.value_of("OUTPUT").unwrap())
+  };
+  // AVIF images hold a single intra frame
+  let still_picture = output_format == OutputFormat::AVIF;
+
+  let config = EncoderConfig {
+    quantizer: value_t!(matches, "QP", usize).unwrap_or_else(|e| e.exit()),
+    speed: value_t!(matches, "SPEED", usize).unwrap_or_else(|e| e.exit()),
+    key_frame_interval: value_t!(matches, "KEYINT", u64)
+      .unwrap_or_else(|e| e.exit()),
+    mini_gop_len: value_t!(matches, "MINI_GOP_LEN", usize)
+      .unwrap_or_else(|e| e.exit()),
+    tile_cols_log2: value_t!(matches, "TILE_COLS_LOG2", usize)
+      .unwrap_or_else(|e| e.exit()),
+    tile_rows_log2: value_t!(matches, "TILE_ROWS_LOG2", usize)
+      .unwrap_or_else(|e| e.exit()),
+    still_picture,
+    ..Default::default()
+  };
+  if let Err(e) = config.validate() {
+    eprintln!("error: {}", e);
+    process::exit(1);
+  }
+
+  let raw = if matches.is_present("WIDTH") {
+    let framerate = match parse_framerate(matches.value_of("FPS").unwrap()) {
+      Some(framerate) => framerate,
+      None => {
+        eprintln!("error: invalid frame rate");
+        process::exit(1);
+      }
+    };
+    let (chroma_sampling, bit_depth) =
+      parse_pix_fmt(matches.value_of("PIX_FMT").unwrap());
+    Some(VideoDetails {
+      width: value_t!(matches, "WIDTH", usize).unwrap_or_else(|e| e.exit()),
+      height: value_t!(matches, "HEIGHT", usize).unwrap_or_else(|e| e.exit()),
+      bit_depth,
+      chroma_sampling,
+      chroma_sample_position: ChromaSamplePosition::UNKNOWN,
+      framerate
+    })
+  } else {
+    None
+  };
+
+  CliOptions {
+    input: match matches.value_of("INPUT").unwrap() {
+      "-" => Box::new(std::io::stdin()) as Box<Read>,
+      f => Box::new(file_or_exit(f, File::open(f))) as Box<Read>
+    },
+    output: match matches.value_of("OUTPUT").unwrap() {
+      "-" => Output::Stdout(std::io::stdout()),
+      f => Output::File(file_or_exit(f, File::create(f)))
+    },
+    output_format,
+    rec: matches
+      .value_of("RECONSTRUCTION")
+      .map(|f| Box::new(file_or_exit(f, File::create(f))) as Box<Write>),
+    limit: if still_picture {
+      1
+    } else {
+      value_t!(matches, "LIMIT", u64).unwrap_or_else(|e| e.exit())
+    },
+    verbose: matches.is_present("VERBOSE"),
+    config,
+    raw
+  }
+}
+
+/// Unwrap the file opened at `path`, or report the error and exit.
+fn file_or_exit(path: &str, file: std::io::Result<File>) -> File {
+  file.unwrap_or_else(|e| {
+    eprintln!("error: {}: {}", path, e);
+    process::exit(1);
+  })
+}
+
+/// Parse a frame rate given as `num` or `num/den`.
+fn parse_framerate(s: &str) -> Option<(usize, usize)> {
+  let mut parts = s.splitn(2, '/');
+  let num = parts.next()?.parse().ok()?;
+  let den = match parts.next() {
+    Some(den) => den.parse().ok()?,
+    None => 1
+  };
+  if num == 0 || den == 0 {
+    return None;
+  }
+  Some((num, den))
+}
+
+fn parse_pix_fmt(pix_fmt: &str) -> (ChromaSampling, usize) {
+  let chroma_sampling = match &pix_fmt[..4] {
+    "i422" => ChromaSampling::CS422,
+    "i444" => ChromaSampling::CS444,
+    _ => ChromaSampling::CS420
+  };
+  let bit_depth = match &pix_fmt[4..] {
+    "p10" => 10,
+    "p12" => 12,
+    _ => 8
+  };
+  (chroma_sampling, bit_depth)
+}
+
+/// Map a y4m error to the error reported by the encoder.
+pub fn y4m_error(e: y4m::Error) -> EncoderError {
+  match e {
+    y4m::Error::IoError(e) => EncoderError::Io(e),
+    e => EncoderError::UnsupportedInput(format!("invalid y4m input: {:?}", e))
+  }
+}
+
+fn write_rec_frame(
+  y4m_enc: &mut y4m::Encoder<Box<Write>>, rec: &Frame, width: usize,
+  height: usize, bytes: usize
+) -> Result<(), EncoderError> {
+  // Chroma stays empty for monochrome
+  let mut rec_planes = [Vec::new(), Vec::new(), Vec::new()];
+  for (plane, rec_plane) in rec.planes.iter().zip(rec_planes.iter_mut()) {
+    let PlaneConfig { xdec, ydec, .. } = plane.cfg;
+    let plane_width = (width + xdec) >> xdec;
+    let plane_height = (height + ydec) >> ydec;
+    *rec_plane = vec![128 as u8; plane_width * plane_height * bytes];
+    plane.copy_to_raw_u8(rec_plane, plane_width * bytes, bytes);
+  }
+  let rec_frame = y4m::Frame::new(
+    [&rec_planes[0], &rec_planes[1], &rec_planes[2]],
+    None
+  );
+  y4m_enc.write_frame(&rec_frame).map_err(y4m_error)
+}
+
+/// Write out the packets `enc` has ready, along with their reconstruction,
+/// and describe them on stderr when `verbose`.
+pub fn write_packets(
+  enc: &mut Encoder, muxer: &mut Muxer,
+  mut y4m_enc: Option<&mut y4m::Encoder<Box<Write>>>, verbose: bool
+) -> Result<(), EncoderError> {
+  let width = enc.sequence().max_frame_width as usize;
+  let height = enc.sequence().max_frame_height as usize;
+  let bytes = if enc.sequence().bit_depth > 8 { 2 } else { 1 };
+  while let Some(packet) = enc.receive_packet()? {
+    if verbose {
+      eprintln!("{}", packet);
+    }
+    muxer.write_frame(packet.pts, &packet.data, packet.frame_type)?;
+    if let (Some(y4m_enc), Some(rec)) =
+      (y4m_enc.as_mut(), packet.rec.as_ref())
+    {
+      write_rec_frame(y4m_enc, rec, width, height, bytes)?;
+    }
+  }
+  Ok(())
+}
+
+/// Encode the next frame of the input as frame `number` and write out the
+/// resulting packets. Returns false once the input is exhausted.
+pub fn process_frame(
+  enc: &mut Encoder, number: u64, muxer: &mut Muxer,
+  source: &mut FrameSource, y4m_enc: Option<&mut y4m::Encoder<Box<Write>>>,
+  verbose: bool
+) -> Result<bool, EncoderError> {
+  let more_input = match source.read_frame(enc)? {
+    Some(frame) => {
+      enc.send_frame(frame, number)?;
+      true
+    }
+    None => {
+      enc.flush();
+      false
+    }
+  };
+  write_packets(enc, muxer, y4m_enc, verbose)?;
+  Ok(more_input)
+}
@@ -7,63 +7,73 @@
 // Media Patent License 1.0 was not distributed with this source code in the
 // PATENTS file, you can obtain it at www.aomedia.org/license/patent.
 
+#[macro_use]
+extern crate clap;
 extern crate rav1e;
 extern crate y4m;
 
+mod common;
+mod source;
+
+use common::*;
 use rav1e::*;
+use source::*;
+use std::process;
 
 fn main() {
-  let mut files = EncoderConfig::from_cli();
-  let mut y4m_dec = y4m::decode(&mut files.input_file).unwrap();
-  let width = y4m_dec.get_width();
-  let height = y4m_dec.get_height();
-  let framerate = y4m_dec.get_framerate();
-  let mut y4m_enc = match files.rec_file.as_mut() {
+  if let Err(e) = run() {
+    eprintln!("error: {}", e);
+    process::exit(1);
+  }
+}
+
+fn run() -> Result<(), EncoderError> {
+  let mut cli = parse_cli();
+  let mut source = open_source(&mut cli.input, cli.raw)?;
+  let details = source.video_details();
+  let (fps_num, fps_den) = details.framerate;
+  let mut y4m_enc = match cli.rec.as_mut() {
     Some(rec_file) => Some(
-      y4m::encode(width, height, framerate).write_header(rec_file).unwrap()
+      y4m::encode(
+        details.width,
+        details.height,
+        y4m::Ratio::new(fps_num, fps_den)
+      ).with_colorspace(details.y4m_colorspace())
+        .write_header(rec_file)
+        .map_err(y4m_error)?
     ),
     None => None
   };
 
-  let mut fi =
-    FrameInvariants::new(width, height, files.quantizer, files.speed);
-  let sequence = Sequence::new();
-  write_ivf_header(
-    &mut files.output_file,
-    width,
-    height,
-    framerate.num,
-    framerate.den
-  );
+  cli.config.bit_depth = details.bit_depth;
+  let mut enc = Encoder::new(&cli.config, details.sequence())?;
+  let mut muxer = new_muxer(
+    cli.output,
+    cli.output_format,
+    enc.sequence(),
+    fps_num,
+    fps_den
+  )?;
+  enc.set_output_reconstruction(y4m_enc.is_some());
 
-  let mut last_rec: Option<Frame> = None;
+  let mut number = 0;
   loop {
-    //fi.frame_type = FrameType::KEY;
-    fi.frame_type = if fi.number % 30 == 0 {
-      FrameType::KEY
-    } else {
-      FrameType::INTER
-    };
-
-    fi.intra_only = fi.frame_type == FrameType::KEY
-      || fi.frame_type == FrameType::INTRA_ONLY;
-    fi.use_prev_frame_mvs = !(fi.intra_only || fi.error_resilient);
-
     if !process_frame(
-      &sequence,
-      &fi,
-      &mut files.output_file,
-      &mut y4m_dec,
+      &mut enc,
+      number,
+      &mut *muxer,
+      &mut *source,
       y4m_enc.as_mut(),
-      &mut last_rec
-    ) {
+      cli.verbose
+    )? {
       break;
     }
-    fi.number += 1;
-    //fi.show_existing_frame = fi.number % 2 == 1;
-    if fi.number == files.limit {
+    number += 1;
+    if number == cli.limit {
+      enc.flush();
+      write_packets(&mut enc, &mut *muxer, y4m_enc.as_mut(), cli.verbose)?;
       break;
     }
-    files.output_file.flush().unwrap();
   }
+  muxer.finish()
 }
@@ -0,0 +1,317 @@
+// Copyright (c) 2018, The rav1e contributors. All rights reserved
+//
+// This source code is subject to the terms of the BSD 2 Clause License and
+// the Alliance for Open Media Patent License 1.0. If the BSD 2 Clause License
+// was not distributed with this source code in the LICENSE file, you can
+// obtain it at www.aomedia.org/license/software. If the Alliance for Open
+// Media Patent License 1.0 was not distributed with this source code in the
+// PATENTS file, you can obtain it at www.aomedia.org/license/patent.
+
+use common::y4m_error;
+use rav1e::plane::PlaneConfig;
+use rav1e::*;
+use std::io;
+use std::io::prelude::*;
+use y4m;
+
+/// Format of the frames produced by a `FrameSource`
+#[derive(Clone, Copy, Debug)]
+pub struct VideoDetails {
+  pub width: usize,
+  pub height: usize,
+  pub bit_depth: usize,
+  pub chroma_sampling: ChromaSampling,
+  pub chroma_sample_position: ChromaSamplePosition,
+  /// Frames per second, as numerator and denominator
+  pub framerate: (usize, usize)
+}
+
+impl VideoDetails {
+  /// Sequence parameters describing the video.
+  pub fn sequence(&self) -> Sequence {
+    let mut sequence = Sequence::new(self.width, self.height);
+    sequence.chroma_sampling = self.chroma_sampling;
+    sequence.chroma_sample_position = self.chroma_sample_position;
+    sequence.timing_info =
+      Some(TimingInfo::new(self.framerate.0, self.framerate.1));
+    sequence
+  }
+
+  /// y4m colorspace for writing out the reconstruction.
+  pub fn y4m_colorspace(&self) -> y4m::Colorspace {
+    use y4m::Colorspace::*;
+    match (self.chroma_sampling, self.bit_depth) {
+      (ChromaSampling::CS420, 8) => match self.chroma_sample_position {
+        ChromaSamplePosition::VERTICAL => C420mpeg2,
+        ChromaSamplePosition::COLOCATED => C420paldv,
+        _ => C420
+      },
+      (ChromaSampling::CS420, 10) => C420p10,
+      (ChromaSampling::CS420, _) => C420p12,
+      (ChromaSampling::CS422, 8) => C422,
+      (ChromaSampling::CS422, 10) => C422p10,
+      (ChromaSampling::CS422, _) => C422p12,
+      (ChromaSampling::CS444, 8) => C444,
+      (ChromaSampling::CS444, 10) => C444p10,
+      (ChromaSampling::CS444, _) => C444p12,
+      (ChromaSampling::CS400, _) => Cmono
+    }
+  }
+}
+
+/// Uncompressed input of the command line encoder
+pub trait FrameSource {
+  fn video_details(&self) -> VideoDetails;
+
+  /// Read the next frame into a frame allocated by `enc`, or return None at
+  /// the end of the input.
+  fn read_frame(&mut self, enc: &Encoder)
+    -> Result<Option<Frame>, EncoderError>;
+}
+
+/// Open the input as raw planar YUV in the format `raw`, or as YUV4MPEG2
+/// when it is None.
+pub fn open_source<'d>(
+  input: &'d mut Box<Read>, raw: Option<VideoDetails>
+) -> Result<Box<FrameSource + 'd>, EncoderError> {
+  Ok(match raw {
+    Some(details) => Box::new(RawSource::new(input, details)),
+    None => Box::new(Y4mSource::new(input)?)
+  })
+}
+
+/// YUV4MPEG2 input, parsed by the `y4m` crate
+pub struct Y4mSource<'d> {
+  dec: y4m::Decoder<'d, Box<Read>>,
+  details: VideoDetails
+}
+
+impl<'d> Y4mSource<'d> {
+  pub fn new(input: &'d mut Box<Read>) -> Result<Y4mSource<'d>, EncoderError> {
+    let dec = y4m::decode(input).map_err(y4m_error)?;
+    let csp = dec.get_colorspace();
+    let chroma_sampling = ChromaSampling::from_y4m(csp).ok_or_else(|| {
+      EncoderError::UnsupportedInput(format!(
+        "colorspace {:?} is not supported",
+        csp
+      ))
+    })?;
+    let framerate = dec.get_framerate();
+    let details = VideoDetails {
+      width: dec.get_width(),
+      height: dec.get_height(),
+      bit_depth: dec.get_bit_depth(),
+      chroma_sampling,
+      chroma_sample_position: ChromaSamplePosition::from_y4m(csp),
+      framerate: (framerate.num, framerate.den)
+    };
+    Ok(Y4mSource { dec, details })
+  }
+}
+
+impl<'d> FrameSource for Y4mSource<'d> {
+  fn video_details(&self) -> VideoDetails {
+    self.details
+  }
+
+  fn read_frame(
+    &mut self, enc: &Encoder
+  ) -> Result<Option<Frame>, EncoderError> {
+    let width = self.dec.get_width();
+    let y4m_bits = self.dec.get_bit_depth();
+    let y4m_bytes = self.dec.get_bytes_per_sample();
+    let csp = self.dec.get_colorspace();
+    if y4m_bits != enc.sequence().bit_depth {
+      return Err(EncoderError::UnsupportedInput(format!(
+        "input bit depth {} does not match the encoder",
+        y4m_bits
+      )));
+    }
+    if ChromaSampling::from_y4m(csp) != Some(enc.sequence().chroma_sampling) {
+      return Err(EncoderError::UnsupportedInput(format!(
+        "colorspace {:?} does not match the encoder",
+        csp
+      )));
+    }
+    match self.dec.read_frame() {
+      Ok(y4m_frame) => {
+        let y4m_planes = [
+          y4m_frame.get_y_plane(),
+          y4m_frame.get_u_plane(),
+          y4m_frame.get_v_plane()
+        ];
+        let mut frame = enc.new_frame();
+        // Monochrome frames only have a luma plane
+        for (plane, y4m_plane) in
+          frame.planes.iter_mut().zip(y4m_planes.iter())
+        {
+          let xdec = plane.cfg.xdec;
+          let stride = ((width + xdec) >> xdec) * y4m_bytes;
+          plane.copy_from_raw_u8(y4m_plane, stride, y4m_bytes);
+        }
+
+        Ok(Some(frame))
+      }
+      Err(y4m::Error::EOF) => Ok(None),
+      Err(e) => Err(y4m_error(e))
+    }
+  }
+}
+
+/// Headerless planar YUV input, with 8-bit samples or 16-bit little-endian
+/// samples above 8 bits
+pub struct RawSource<'d> {
+  input: &'d mut Box<Read>,
+  details: VideoDetails
+}
+
+impl<'d> RawSource<'d> {
+  pub fn new(input: &'d mut Box<Read>, details: VideoDetails) -> RawSource<'d> {
+    RawSource { input, details }
+  }
+}
+
+impl<'d> FrameSource for RawSource<'d> {
+  fn video_details(&self) -> VideoDetails {
+    self.details
+  }
+
+  fn read_frame(
+    &mut self, enc: &Encoder
+  ) -> Result<Option<Frame>, EncoderError> {
+    let bytes = if self.details.bit_depth > 8 { 2 } else { 1 };
+    let mut frame = enc.new_frame();
+    for (p, plane) in frame.planes.iter_mut().enumerate() {
+      let PlaneConfig { xdec, ydec, .. } = plane.cfg;
+      let stride = ((self.details.width + xdec) >> xdec) * bytes;
+      let mut buf = vec![0; stride * ((self.details.height + ydec) >> ydec)];
+      let len = read_fully(&mut self.input, &mut buf)?;
+      if len == 0 && p == 0 {
+        return Ok(None);
+      }
+      if len < buf.len() {
+        return Err(EncoderError::UnsupportedInput(
+          "raw input ends with a partial frame".to_string()
+        ));
+      }
+      plane.copy_from_raw_u8(&buf, stride, bytes);
+    }
+    Ok(Some(frame))
+  }
+}
+
+/// Fill `buf` from `input`, returning less than its length only at the end
+/// of the input.
+fn read_fully(input: &mut Read, buf: &mut [u8]) -> io::Result<usize> {
+  let mut len = 0;
+  while len < buf.len() {
+    match input.read(&mut buf[len..]) {
+      Ok(0) => break,
+      Ok(n) => len += n,
+      Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+      Err(e) => return Err(e)
+    }
+  }
+  Ok(len)
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  fn details(
+    width: usize, height: usize, bit_depth: usize,
+    chroma_sampling: ChromaSampling
+  ) -> VideoDetails {
+    VideoDetails {
+      width,
+      height,
+      bit_depth,
+      chroma_sampling,
+      chroma_sample_position: ChromaSamplePosition::UNKNOWN,
+      framerate: (30, 1)
+    }
+  }
+
+  fn encoder(details: &VideoDetails) -> Encoder {
+    let config =
+      EncoderConfig { bit_depth: details.bit_depth, ..Default::default() };
+    Encoder::new(&config, details.sequence()).unwrap()
+  }
+
+  /// Frames read from `data` until the end of the input or an error.
+  fn read_frames(
+    details: VideoDetails, data: Vec<u8>
+  ) -> (Vec<Frame>, Option<EncoderError>) {
+    let enc = encoder(&details);
+    let mut input: Box<Read> = Box::new(io::Cursor::new(data));
+    let mut source = RawSource::new(&mut input, details);
+    let mut frames = Vec::new();
+    loop {
+      match source.read_frame(&enc) {
+        Ok(Some(frame)) => frames.push(frame),
+        Ok(None) => return (frames, None),
+        Err(e) => return (frames, Some(e))
+      }
+    }
+  }
+
+  #[test]
+  fn raw_input_ends_at_frame_boundary() {
+    // Two frames of 4x2 luma and 2x1 chroma samples
+    let data: Vec<u8> = (0..24).collect();
+    let (frames, err) =
+      read_frames(details(4, 2, 8, ChromaSampling::CS420), data);
+    assert!(err.is_none());
+    assert_eq!(frames.len(), 2);
+    let second = &frames[1].planes;
+    assert_eq!(second[0].p(0, 0), 12);
+    assert_eq!(second[0].p(3, 1), 19);
+    assert_eq!(second[1].p(1, 0), 21);
+    assert_eq!(second[2].p(0, 0), 22);
+  }
+
+  #[test]
+  fn raw_input_ends_with_partial_frame() {
+    // Frames of 12 bytes, cut in the luma or the chroma planes
+    for &len in &[12 + 1, 12 + 9] {
+      let data = vec![0; len];
+      let (frames, err) =
+        read_frames(details(4, 2, 8, ChromaSampling::CS420), data);
+      assert_eq!(frames.len(), 1);
+      match err {
+        Some(EncoderError::UnsupportedInput(_)) => {}
+        _ => panic!("{} bytes read without error", len)
+      }
+    }
+  }
+
+  #[test]
+  fn raw_input_16_bit_little_endian() {
+    let details = details(2, 1, 10, ChromaSampling::CS444);
+    let data =
+      vec![0xff, 0x03, 0x01, 0x02, 0, 0, 0x34, 0x01, 0x00, 0x02, 7, 0];
+    let (frames, err) = read_frames(details, data);
+    assert!(err.is_none());
+    let planes = &frames[0].planes;
+    assert_eq!((planes[0].p(0, 0), planes[0].p(1, 0)), (0x3ff, 0x201));
+    assert_eq!((planes[1].p(0, 0), planes[1].p(1, 0)), (0, 0x134));
+    assert_eq!((planes[2].p(0, 0), planes[2].p(1, 0)), (0x200, 7));
+  }
+
+  #[test]
+  fn raw_input_odd_size_chroma() {
+    // 5x3 luma samples, with 3x2 chroma samples rounded up
+    let details = details(5, 3, 8, ChromaSampling::CS420);
+    let data: Vec<u8> = (0..15 + 2 * 6).collect();
+    let (frames, err) = read_frames(details, data);
+    assert!(err.is_none());
+    assert_eq!(frames.len(), 1);
+    let planes = &frames[0].planes;
+    assert_eq!(planes[0].p(4, 2), 14);
+    assert_eq!(planes[1].p(0, 0), 15);
+    assert_eq!(planes[1].p(2, 0), 17);
+    assert_eq!(planes[1].p(0, 1), 18);
+    assert_eq!(planes[2].p(2, 1), 26);
+  }
+}
@@ -0,0 +1,381 @@
+// Copyright (c) 2018, The rav1e contributors. All rights reserved
+//
+// This source code is subject to the terms of the BSD 2 Clause License and
+// the Alliance for Open Media Patent License 1.0. If the BSD 2 Clause License
+// was not distributed with this source code in the LICENSE file, you can
+// obtain it at www.aomedia.org/license/software. If the Alliance for Open
+// Media Patent License 1.0 was not distributed with this source code in the
+// PATENTS file, you can obtain it at www.aomedia.org/license/patent.
+
+//! C interface to the encoder, built with the `capi` feature.
+//!
+//! Configs, contexts, frames and packets are handed out as pointers to boxed
+//! library types, and released with the matching `*_unref` function. The
+//! `rav1e.h` header is generated from this module by cbindgen, into the
+//! `include` directory of the build script output.
+
+#![allow(non_camel_case_types)]
+
+use super::*;
+use libc::{c_int, ptrdiff_t, size_t};
+use std::ptr;
+use std::slice;
+
+/// Encoder settings along with the format of the input
+pub struct RaConfig {
+  config: EncoderConfig,
+  width: usize,
+  height: usize,
+  chroma_sampling: ChromaSampling,
+  time_base: Option<(usize, usize)>
+}
+
+/// Encoder instance
+pub struct RaContext {
+  enc: Encoder
+}
+
+/// Uncompressed frame to be sent to the encoder
+pub struct RaFrame {
+  frame: Frame
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RaStatus {
+  RA_STATUS_SUCCESS = 0,
+  /// No packet is ready, more frames or a flush are needed
+  RA_STATUS_NEED_MORE_DATA = 1,
+  RA_STATUS_INVALID_CONFIG = -1,
+  RA_STATUS_UNSUPPORTED_INPUT = -2,
+  RA_STATUS_IO_ERROR = -3,
+  RA_STATUS_INTERNAL_ERROR = -4,
+  /// An argument of the call is out of range
+  RA_STATUS_INVALID_ARGUMENT = -5
+}
+
+impl From<EncoderError> for RaStatus {
+  fn from(e: EncoderError) -> RaStatus {
+    match e {
+      EncoderError::InvalidConfig(_) => RaStatus::RA_STATUS_INVALID_CONFIG,
+      EncoderError::UnsupportedInput(_) =>
+        RaStatus::RA_STATUS_UNSUPPORTED_INPUT,
+      EncoderError::Io(_) => RaStatus::RA_STATUS_IO_ERROR,
+      EncoderError::Internal(_) => RaStatus::RA_STATUS_INTERNAL_ERROR
+    }
+  }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RaFrameType {
+  RA_FRAME_TYPE_KEY,
+  RA_FRAME_TYPE_INTER,
+  RA_FRAME_TYPE_INTRA_ONLY,
+  RA_FRAME_TYPE_SWITCH
+}
+
+impl From<FrameType> for RaFrameType {
+  fn from(frame_type: FrameType) -> RaFrameType {
+    match frame_type {
+      FrameType::KEY => RaFrameType::RA_FRAME_TYPE_KEY,
+      FrameType::INTER => RaFrameType::RA_FRAME_TYPE_INTER,
+      FrameType::INTRA_ONLY => RaFrameType::RA_FRAME_TYPE_INTRA_ONLY,
+      FrameType::S => RaFrameType::RA_FRAME_TYPE_SWITCH
+    }
+  }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RaChromaSampling {
+  RA_CHROMA_SAMPLING_CS420,
+  RA_CHROMA_SAMPLING_CS422,
+  RA_CHROMA_SAMPLING_CS444,
+  RA_CHROMA_SAMPLING_CS400
+}
+
+impl From<RaChromaSampling> for ChromaSampling {
+  fn from(chroma_sampling: RaChromaSampling) -> ChromaSampling {
+    match chroma_sampling {
+      RaChromaSampling::RA_CHROMA_SAMPLING_CS420 => ChromaSampling::CS420,
+      RaChromaSampling::RA_CHROMA_SAMPLING_CS422 => ChromaSampling::CS422,
+      RaChromaSampling::RA_CHROMA_SAMPLING_CS444 => ChromaSampling::CS444,
+      RaChromaSampling::RA_CHROMA_SAMPLING_CS400 => ChromaSampling::CS400
+    }
+  }
+}
+
+/// Encoded frame, holding a temporal unit of OBUs
+#[repr(C)]
+pub struct RaPacket {
+  pub data: *const u8,
+  pub len: size_t,
+  /// Presentation timestamp given to `rav1e_send_frame`
+  pub pts: u64,
+  pub frame_type: RaFrameType
+}
+
+/// Create a config for `width`x`height` input with the default settings.
+#[no_mangle]
+pub unsafe extern "C" fn rav1e_config_default(
+  width: size_t, height: size_t
+) -> *mut RaConfig {
+  let cfg = RaConfig {
+    config: EncoderConfig::default(),
+    width,
+    height,
+    chroma_sampling: ChromaSampling::CS420,
+    time_base: None
+  };
+  Box::into_raw(Box::new(cfg))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rav1e_config_unref(cfg: *mut RaConfig) {
+  if !cfg.is_null() {
+    let _ = Box::from_raw(cfg);
+  }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rav1e_config_set_quantizer(
+  cfg: *mut RaConfig, quantizer: size_t
+) {
+  (*cfg).config.quantizer = quantizer;
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rav1e_config_set_speed(
+  cfg: *mut RaConfig, speed: size_t
+) {
+  (*cfg).config.speed = speed;
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rav1e_config_set_key_frame_interval(
+  cfg: *mut RaConfig, key_frame_interval: u64
+) {
+  (*cfg).config.key_frame_interval = key_frame_interval;
+}
+
+/// Largest number of frames coded out of order, from 1 to 16. Packets lag
+/// behind the input by up to `mini_gop_len` - 1 frames.
+#[no_mangle]
+pub unsafe extern "C" fn rav1e_config_set_mini_gop_len(
+  cfg: *mut RaConfig, mini_gop_len: size_t
+) {
+  (*cfg).config.mini_gop_len = mini_gop_len;
+}
+
+/// Log2 of the number of tile columns and rows, up to 6 each. Frames too
+/// large for the tiles are split into more.
+#[no_mangle]
+pub unsafe extern "C" fn rav1e_config_set_tiles(
+  cfg: *mut RaConfig, tile_cols_log2: size_t, tile_rows_log2: size_t
+) {
+  (*cfg).config.tile_cols_log2 = tile_cols_log2;
+  (*cfg).config.tile_rows_log2 = tile_rows_log2;
+}
+
+/// Bit depth of the input and of the encoded stream: 8, 10 or 12.
+#[no_mangle]
+pub unsafe extern "C" fn rav1e_config_set_bit_depth(
+  cfg: *mut RaConfig, bit_depth: size_t
+) {
+  (*cfg).config.bit_depth = bit_depth;
+}
+
+/// Chroma subsampling of the input and of the encoded stream. Monochrome
+/// frames only have plane 0.
+#[no_mangle]
+pub unsafe extern "C" fn rav1e_config_set_chroma_sampling(
+  cfg: *mut RaConfig, chroma_sampling: RaChromaSampling
+) {
+  (*cfg).chroma_sampling = chroma_sampling.into();
+}
+
+/// Signal a constant frame rate of `num`/`den` frames per second. Both
+/// must be non-zero.
+#[no_mangle]
+pub unsafe extern "C" fn rav1e_config_set_frame_rate(
+  cfg: *mut RaConfig, num: size_t, den: size_t
+) -> RaStatus {
+  if num == 0 || den == 0 {
+    return RaStatus::RA_STATUS_INVALID_ARGUMENT;
+  }
+  (*cfg).time_base = Some((num, den));
+  RaStatus::RA_STATUS_SUCCESS
+}
+
+/// Encode a single frame with a reduced still picture header.
+#[no_mangle]
+pub unsafe extern "C" fn rav1e_config_set_still_picture(
+  cfg: *mut RaConfig, still_picture: c_int
+) {
+  (*cfg).config.still_picture = still_picture != 0;
+}
+
+/// Create an encoder from `cfg` into `ctx`, which is left untouched if the
+/// config is rejected.
+#[no_mangle]
+pub unsafe extern "C" fn rav1e_context_new(
+  cfg: *const RaConfig, ctx: *mut *mut RaContext
+) -> RaStatus {
+  let cfg = &*cfg;
+  let mut sequence = Sequence::new(cfg.width, cfg.height);
+  sequence.chroma_sampling = cfg.chroma_sampling;
+  if let Some((num, den)) = cfg.time_base {
+    sequence.timing_info = Some(TimingInfo::new(num, den));
+  }
+  match Encoder::new(&cfg.config, sequence) {
+    Ok(enc) => {
+      *ctx = Box::into_raw(Box::new(RaContext { enc }));
+      RaStatus::RA_STATUS_SUCCESS
+    }
+    Err(e) => e.into()
+  }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rav1e_context_unref(ctx: *mut RaContext) {
+  if !ctx.is_null() {
+    let _ = Box::from_raw(ctx);
+  }
+}
+
+/// AV1CodecConfigurationRecord for the stream, as stored by the MP4 and
+/// Matroska containers. Release it with `rav1e_data_unref`. Returns NULL
+/// if the sequence header cannot be written.
+#[no_mangle]
+pub unsafe extern "C" fn rav1e_container_sequence_header(
+  ctx: *const RaContext, len: *mut size_t
+) -> *mut u8 {
+  match av1c_record((*ctx).enc.sequence()) {
+    Ok(record) => {
+      let record = record.into_boxed_slice();
+      *len = record.len();
+      Box::into_raw(record) as *mut u8
+    }
+    Err(_) => {
+      *len = 0;
+      ptr::null_mut()
+    }
+  }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rav1e_data_unref(data: *mut u8, len: size_t) {
+  if !data.is_null() {
+    let _ = Box::from_raw(slice::from_raw_parts_mut(data, len));
+  }
+}
+
+/// Allocate a frame matching the format of `ctx`.
+#[no_mangle]
+pub unsafe extern "C" fn rav1e_frame_new(
+  ctx: *const RaContext
+) -> *mut RaFrame {
+  let frame = (*ctx).enc.new_frame();
+  Box::into_raw(Box::new(RaFrame { frame }))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rav1e_frame_unref(frame: *mut RaFrame) {
+  if !frame.is_null() {
+    let _ = Box::from_raw(frame);
+  }
+}
+
+/// Samples of plane `plane` of `frame`, with rows `*stride` samples apart.
+/// Returns NULL for an invalid plane index.
+#[no_mangle]
+pub unsafe extern "C" fn rav1e_frame_plane(
+  frame: *mut RaFrame, plane: c_int, stride: *mut ptrdiff_t
+) -> *mut u16 {
+  let frame = &mut (*frame).frame;
+  match frame.planes.get_mut(plane as usize) {
+    Some(p) => {
+      *stride = p.cfg.stride as ptrdiff_t;
+      p.data.as_mut_ptr()
+    }
+    _ => ptr::null_mut()
+  }
+}
+
+/// Copy `len` bytes of samples `bytewidth` bytes wide, with rows `stride`
+/// bytes apart, into plane `plane` of `frame`. `bytewidth` is 1, or 2 for
+/// little-endian 16-bit samples.
+#[no_mangle]
+pub unsafe extern "C" fn rav1e_frame_fill_plane(
+  frame: *mut RaFrame, plane: c_int, data: *const u8, len: size_t,
+  stride: ptrdiff_t, bytewidth: c_int
+) -> RaStatus {
+  if (data.is_null() && len > 0)
+    || stride <= 0
+    || (bytewidth != 1 && bytewidth != 2)
+  {
+    return RaStatus::RA_STATUS_INVALID_ARGUMENT;
+  }
+  let frame = &mut (*frame).frame;
+  match frame.planes.get_mut(plane as usize) {
+    Some(p) => {
+      let data = if len > 0 {
+        slice::from_raw_parts(data, len)
+      } else {
+        &[]
+      };
+      p.copy_from_raw_u8(data, stride as usize, bytewidth as usize);
+      RaStatus::RA_STATUS_SUCCESS
+    }
+    _ => RaStatus::RA_STATUS_INVALID_ARGUMENT
+  }
+}
+
+/// Queue `frame` for encoding with timestamp `pts`; the frame is copied and
+/// may be reused. A NULL frame flushes the encoder.
+#[no_mangle]
+pub unsafe extern "C" fn rav1e_send_frame(
+  ctx: *mut RaContext, frame: *const RaFrame, pts: u64
+) -> RaStatus {
+  let enc = &mut (*ctx).enc;
+  if frame.is_null() {
+    enc.flush();
+    return RaStatus::RA_STATUS_SUCCESS;
+  }
+  match enc.send_frame(&(*frame).frame, pts) {
+    Ok(()) => RaStatus::RA_STATUS_SUCCESS,
+    Err(e) => e.into()
+  }
+}
+
+/// Encode the next queued frame into `*pkt`, to be released with
+/// `rav1e_packet_unref`.
+#[no_mangle]
+pub unsafe extern "C" fn rav1e_receive_packet(
+  ctx: *mut RaContext, pkt: *mut *mut RaPacket
+) -> RaStatus {
+  match (*ctx).enc.receive_packet() {
+    Ok(Some(packet)) => {
+      let data = packet.data.into_boxed_slice();
+      let len = data.len();
+      *pkt = Box::into_raw(Box::new(RaPacket {
+        data: Box::into_raw(data) as *const u8,
+        len,
+        pts: packet.pts,
+        frame_type: packet.frame_type.into()
+      }));
+      RaStatus::RA_STATUS_SUCCESS
+    }
+    Ok(None) => RaStatus::RA_STATUS_NEED_MORE_DATA,
+    Err(e) => e.into()
+  }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn rav1e_packet_unref(pkt: *mut RaPacket) {
+  if !pkt.is_null() {
+    let pkt = Box::from_raw(pkt);
+    rav1e_data_unref(pkt.data as *mut u8, pkt.len);
+  }
+}
@@ -7,7 +7,6 @@
 // Media Patent License 1.0 was not distributed with this source code in the
 // PATENTS file, you can obtain it at www.aomedia.org/license/patent.
 
-#![allow(safe_extern_statics)]
 #![allow(non_upper_case_globals)]
 #![allow(dead_code)]
 #![allow(non_camel_case_types)]
@@ -17,18 +16,35 @@
 #![cfg_attr(feature = "cargo-clippy", allow(collapsible_if))]
 
 use ec;
+use entropymode::*;
+use gm::*;
 use partition::BlockSize::*;
 use partition::PredictionMode::*;
 use partition::TxType::*;
 use partition::*;
 use plane::*;
+use scan_order::*;
 use std::*;
+use token_cdfs::*;
+use FrameInvariants;
+use FrameType;
+use ALTREF2_FRAME;
+use ALTREF_FRAME;
+use BWDREF_FRAME;
+use GOLDEN_FRAME;
+use INTRA_FRAME;
+use LAST2_FRAME;
+use LAST3_FRAME;
+use LAST_FRAME;
+use NONE_FRAME;
+use relative_dist;
 
 const PLANES: usize = 3;
 
 const PARTITION_PLOFFSET: usize = 4;
-const PARTITION_CONTEXTS: usize = 20;
+pub const PARTITION_CONTEXTS: usize = 20;
 pub const PARTITION_TYPES: usize = 4;
+pub const EXT_PARTITION_TYPES: usize = 10;
 
 pub const MI_SIZE_LOG2: usize = 2;
 const MI_SIZE: usize = (1 << MI_SIZE_LOG2);
@@ -43,17 +59,17 @@ const MAX_SB_SQUARE: usize = (MAX_SB_SIZE * MAX_SB_SIZE);
 pub const MAX_TX_SIZE: usize = 32;
 const MAX_TX_SQUARE: usize = MAX_TX_SIZE * MAX_TX_SIZE;
 
-const INTRA_MODES: usize = 13;
-const UV_INTRA_MODES: usize = 14;
-const BLOCK_SIZE_GROUPS: usize = 4;
-const MAX_ANGLE_DELTA: usize = 3;
-const DIRECTIONAL_MODES: usize = 8;
-const KF_MODE_CONTEXTS: usize = 5;
+pub const INTRA_MODES: usize = 13;
+pub const UV_INTRA_MODES: usize = 14;
+pub const BLOCK_SIZE_GROUPS: usize = 4;
+pub const MAX_ANGLE_DELTA: usize = 3;
+pub const DIRECTIONAL_MODES: usize = 8;
+pub const KF_MODE_CONTEXTS: usize = 5;
 
-const EXT_TX_SIZES: usize = 4;
+pub const EXT_TX_SIZES: usize = 4;
 const EXT_TX_SET_TYPES: usize = 9;
-const EXT_TX_SETS_INTRA: usize = 3;
-const EXT_TX_SETS_INTER: usize = 4;
+pub const EXT_TX_SETS_INTRA: usize = 3;
+pub const EXT_TX_SETS_INTER: usize = 4;
 // Number of transform types in each set type
 static num_ext_tx_set: [usize; EXT_TX_SET_TYPES] =
   [1, 2, 5, 7, 7, 10, 12, 16, 16];
@@ -176,6 +192,20 @@ pub fn get_plane_block_size(bsize: BlockSize, subsampling_x: usize, subsampling_
   ss_size_lookup[bsize as usize][subsampling_x][subsampling_y]
 }
 
+/// Largest transform covering a chroma block of size `plane_bsize`, capped
+/// at 32x32 as for luma.
+pub fn get_uv_tx_size(plane_bsize: BlockSize) -> TxSize {
+  match plane_bsize {
+    BLOCK_4X4 => TxSize::TX_4X4,
+    BLOCK_4X8 => TxSize::TX_4X8,
+    BLOCK_8X8 => TxSize::TX_8X8,
+    BLOCK_8X16 => TxSize::TX_8X16,
+    BLOCK_16X16 => TxSize::TX_16X16,
+    BLOCK_16X32 => TxSize::TX_16X32,
+    _ => TxSize::TX_32X32
+  }
+}
+
 // Generates 4 bit field in which each bit set to 1 represents
 // a blocksize partition  1111 means we split 64x64, 32x32, 16x16
 // and 8x8.  1000 means we just split the 64x64 to 32x32
@@ -308,31 +338,84 @@ enum TailToken {
     Cat6 = 8,
 }
 
-const PLANE_TYPES: usize = 2;
+pub const PLANE_TYPES: usize = 2;
+pub const TOKEN_CDF_Q_CTXS: usize = 4;
 const REF_TYPES: usize = 2;
-const SKIP_CONTEXTS: usize = 3;
-const INTRA_INTER_CONTEXTS: usize = 4;
+pub const SKIP_CONTEXTS: usize = 3;
+pub const INTRA_INTER_CONTEXTS: usize = 4;
+
+pub const NEWMV_MODE_CONTEXTS: usize = 6;
+pub const GLOBALMV_MODE_CONTEXTS: usize = 2;
+pub const REFMV_MODE_CONTEXTS: usize = 6;
+
+// Packing of the inter mode contexts in a single value, as in libaom
+const GLOBALMV_OFFSET: usize = 3;
+const REFMV_OFFSET: usize = 4;
+const NEWMV_CTX_MASK: usize = (1 << GLOBALMV_OFFSET) - 1;
+const GLOBALMV_CTX_MASK: usize = (1 << (REFMV_OFFSET - GLOBALMV_OFFSET)) - 1;
+const REFMV_CTX_MASK: usize = (1 << (8 - REFMV_OFFSET)) - 1;
+
+pub const DRL_MODE_CONTEXTS: usize = 3;
+pub const MAX_REF_MV_STACK_SIZE: usize = 8;
+// Weight added to the candidates of the nearest row and column
+const REF_CAT_LEVEL: u32 = 640;
+// Distance past the frame edges allowed for candidates, in 1/8 samples
+const MV_BORDER: i32 = 128;
+// Bounds of the motion vector components, exclusive
+const MV_LOW: i32 = -(1 << 14);
+const MV_UPP: i32 = 1 << 14;
+// Largest motion vector component saved for the temporal candidates
+const REFMVS_LIMIT: i16 = (1 << 12) - 1;
+// Largest frame distance the projected motion vectors are scaled by
+const MAX_FRAME_DISTANCE: i32 = 31;
+// Bound on the references projected onto the frame after LAST_FRAME
+const MFMV_STACK_SIZE: usize = 3;
+// 2^14 / d, to divide by the frame distances d
+const DIV_MULT: [i32; 32] = [
+  0, 16384, 8192, 5461, 4096, 3276, 2730, 2340, 2048, 1820, 1638, 1489, 1365,
+  1260, 1170, 1092, 1024, 963, 910, 862, 819, 780, 744, 712, 682, 655, 630,
+  606, 585, 564, 546, 528
+];
+
+pub const INTER_MODE_CONTEXTS: usize = 8;
+pub const INTER_COMPOUND_MODES: usize = 8;
+const COMP_NEWMV_CTXS: usize = 5;
+
+pub const REF_CONTEXTS: usize = 3;
+pub const SINGLE_REFS: usize = 7;
+pub const FWD_REFS: usize = 4;
+pub const BWD_REFS: usize = 3;
+pub const COMP_INTER_CONTEXTS: usize = 5;
+pub const COMP_REF_TYPE_CONTEXTS: usize = 5;
+
+pub const MV_JOINTS: usize = 4;
+pub const MV_CLASSES: usize = 11;
+const CLASS0_BITS: usize = 1;
+pub const CLASS0_SIZE: usize = 1 << CLASS0_BITS;
+pub const MV_OFFSET_BITS: usize = MV_CLASSES + CLASS0_BITS - 2;
+pub const MV_FP_SIZE: usize = 4;
 
 // Level Map
-const TXB_SKIP_CONTEXTS: usize =  13;
+pub const TXB_SKIP_CONTEXTS: usize =  13;
 
-const EOB_COEF_CONTEXTS: usize =  22;
+pub const EOB_COEF_CONTEXTS: usize =  9;
 
 const SIG_COEF_CONTEXTS_2D: usize =  26;
 const SIG_COEF_CONTEXTS_1D: usize =  16;
-const SIG_COEF_CONTEXTS_EOB: usize =  4;
-const SIG_COEF_CONTEXTS: usize = SIG_COEF_CONTEXTS_2D + SIG_COEF_CONTEXTS_1D;
+pub const SIG_COEF_CONTEXTS_EOB: usize =  4;
+pub const SIG_COEF_CONTEXTS: usize =
+  SIG_COEF_CONTEXTS_2D + SIG_COEF_CONTEXTS_1D;
 
 const COEFF_BASE_CONTEXTS: usize = SIG_COEF_CONTEXTS;
-const DC_SIGN_CONTEXTS: usize =  3;
+pub const DC_SIGN_CONTEXTS: usize =  3;
 
 const BR_TMP_OFFSET: usize =  12;
 const BR_REF_CAT: usize =  4;
-const LEVEL_CONTEXTS: usize =  21;
+pub const LEVEL_CONTEXTS: usize =  21;
 
 const NUM_BASE_LEVELS: usize =  2;
 
-const BR_CDF_SIZE: usize = 4;
+pub const BR_CDF_SIZE: usize = 4;
 const COEFF_BASE_RANGE: usize = 4 * (BR_CDF_SIZE - 1);
 
 const COEFF_CONTEXT_BITS: usize = 6;
@@ -889,73 +972,51 @@ pub fn uv_intra_mode_to_tx_type_context(pred: PredictionMode) -> TxType {
   intra_mode_to_tx_type_context[uv2y[pred as usize] as usize]
 }
 
-extern {
-  static default_partition_cdf:
-    [[u16; PARTITION_TYPES + 1]; PARTITION_CONTEXTS];
-  static default_kf_y_mode_cdf:
-    [[[u16; INTRA_MODES + 1]; KF_MODE_CONTEXTS]; KF_MODE_CONTEXTS];
-  static default_if_y_mode_cdf: [[u16; INTRA_MODES + 1]; BLOCK_SIZE_GROUPS];
-  static default_uv_mode_cdf: [[[u16; UV_INTRA_MODES + 1]; INTRA_MODES]; 2];
-  static default_intra_ext_tx_cdf:
-    [[[[u16; TX_TYPES + 1]; INTRA_MODES]; EXT_TX_SIZES]; EXT_TX_SETS_INTRA];
-  static default_inter_ext_tx_cdf:
-    [[[u16; TX_TYPES + 1]; EXT_TX_SIZES]; EXT_TX_SETS_INTRA];
-  static default_skip_cdfs: [[u16; 3]; SKIP_CONTEXTS];
-  static default_intra_inter_cdf: [[u16; 3]; INTRA_INTER_CONTEXTS];
-  static default_angle_delta_cdf:
-    [[u16; 2 * MAX_ANGLE_DELTA + 1 + 1]; DIRECTIONAL_MODES];
-
-  static av1_inter_scan_orders: [[SCAN_ORDER; TX_TYPES]; TxSize::TX_SIZES_ALL];
-
-  // lv_map
-  static av1_default_txb_skip_cdf:
-    [[[u16; 3]; TXB_SKIP_CONTEXTS]; TxSize::TX_SIZES];
-  static av1_default_dc_sign_cdf:
-    [[[u16; 3]; DC_SIGN_CONTEXTS]; TxSize::TX_SIZES];
-  static av1_default_eob_extra_cdf:
-    [[[[u16; 3]; EOB_COEF_CONTEXTS]; PLANE_TYPES]; TxSize::TX_SIZES];
-
-  static av1_default_eob_multi16: [[[u16; 5 + 1]; 2]; PLANE_TYPES];
-  static av1_default_eob_multi32: [[[u16; 6 + 1]; 2]; PLANE_TYPES];
-  static av1_default_eob_multi64: [[[u16; 7 + 1]; 2]; PLANE_TYPES];
-  static av1_default_eob_multi128: [[[u16; 8 + 1]; 2]; PLANE_TYPES];
-  static av1_default_eob_multi256: [[[u16; 9 + 1]; 2]; PLANE_TYPES];
-  static av1_default_eob_multi512: [[[u16; 10 + 1]; 2]; PLANE_TYPES];
-  static av1_default_eob_multi1024: [[[u16; 11 + 1]; 2]; PLANE_TYPES];
-
-  static av1_default_coeff_base_eob_multi:
-    [[[[u16; 3 + 1]; SIG_COEF_CONTEXTS_EOB]; PLANE_TYPES]; TxSize::TX_SIZES];
-  static av1_default_coeff_base_multi:
-    [[[[u16; 4 + 1]; SIG_COEF_CONTEXTS]; PLANE_TYPES]; TxSize::TX_SIZES];
-  static av1_default_coeff_lps_multi: [[[[u16; BR_CDF_SIZE + 1];
-    LEVEL_CONTEXTS]; PLANE_TYPES];
-    TxSize::TX_SIZES];
+#[derive(Clone, Copy)]
+pub struct NMVComponent {
+  pub classes_cdf: [u16; MV_CLASSES + 1],
+  pub class0_fp_cdf: [[u16; MV_FP_SIZE + 1]; CLASS0_SIZE],
+  pub fp_cdf: [u16; MV_FP_SIZE + 1],
+  pub sign_cdf: [u16; 3],
+  pub class0_hp_cdf: [u16; 3],
+  pub hp_cdf: [u16; 3],
+  pub class0_cdf: [u16; CLASS0_SIZE + 1],
+  pub bits_cdf: [[u16; 3]; MV_OFFSET_BITS]
 }
 
-#[repr(C)]
-pub struct SCAN_ORDER {
-  // FIXME: don't hardcode sizes
-  pub scan: &'static [u16; 64 * 64],
-  pub iscan: &'static [u16; 64 * 64],
-  pub neighbors: &'static [u16; ((64 * 64) + 1) * 2]
+#[derive(Clone, Copy)]
+pub struct NMVContext {
+  pub joints_cdf: [u16; MV_JOINTS + 1],
+  pub comps: [NMVComponent; 2]
 }
 
 #[derive(Clone)]
 pub struct CDFContext {
-  partition_cdf: [[u16; PARTITION_TYPES + 1]; PARTITION_CONTEXTS],
+  partition_cdf: [[u16; EXT_PARTITION_TYPES + 1]; PARTITION_CONTEXTS],
   kf_y_cdf: [[[u16; INTRA_MODES + 1]; KF_MODE_CONTEXTS]; KF_MODE_CONTEXTS],
   y_mode_cdf: [[u16; INTRA_MODES + 1]; BLOCK_SIZE_GROUPS],
   uv_mode_cdf: [[[u16; UV_INTRA_MODES + 1]; INTRA_MODES]; 2],
   intra_ext_tx_cdf:
     [[[[u16; TX_TYPES + 1]; INTRA_MODES]; EXT_TX_SIZES]; EXT_TX_SETS_INTRA],
-  inter_ext_tx_cdf: [[[u16; TX_TYPES + 1]; EXT_TX_SIZES]; EXT_TX_SETS_INTRA],
+  inter_ext_tx_cdf: [[[u16; TX_TYPES + 1]; EXT_TX_SIZES]; EXT_TX_SETS_INTER],
   skip_cdfs: [[u16; 3]; SKIP_CONTEXTS],
   intra_inter_cdfs: [[u16; 3]; INTRA_INTER_CONTEXTS],
   angle_delta_cdf: [[u16; 2 * MAX_ANGLE_DELTA + 1 + 1]; DIRECTIONAL_MODES],
+  newmv_cdf: [[u16; 3]; NEWMV_MODE_CONTEXTS],
+  zeromv_cdf: [[u16; 3]; GLOBALMV_MODE_CONTEXTS],
+  refmv_cdf: [[u16; 3]; REFMV_MODE_CONTEXTS],
+  single_ref_cdfs: [[[u16; 3]; SINGLE_REFS - 1]; REF_CONTEXTS],
+  comp_inter_cdfs: [[u16; 3]; COMP_INTER_CONTEXTS],
+  comp_ref_type_cdfs: [[u16; 3]; COMP_REF_TYPE_CONTEXTS],
+  comp_ref_cdfs: [[[u16; 3]; FWD_REFS - 1]; REF_CONTEXTS],
+  comp_bwdref_cdfs: [[[u16; 3]; BWD_REFS - 1]; REF_CONTEXTS],
+  compound_mode_cdf: [[u16; INTER_COMPOUND_MODES + 1]; INTER_MODE_CONTEXTS],
+  drl_cdfs: [[u16; 3]; DRL_MODE_CONTEXTS],
+  nmv_context: NMVContext,
 
   // lv_map
   txb_skip_cdf: [[[u16; 3]; TXB_SKIP_CONTEXTS]; TxSize::TX_SIZES],
-  dc_sign_cdf: [[[u16; 3]; DC_SIGN_CONTEXTS]; TxSize::TX_SIZES],
+  dc_sign_cdf: [[[u16; 3]; DC_SIGN_CONTEXTS]; PLANE_TYPES],
   eob_extra_cdf:
     [[[[u16; 3]; EOB_COEF_CONTEXTS]; PLANE_TYPES]; TxSize::TX_SIZES],
 
@@ -976,7 +1037,13 @@ pub struct CDFContext {
 }
 
 impl CDFContext {
-  pub fn new(_qindex: u8) -> CDFContext {
+  pub fn new(qindex: u8) -> CDFContext {
+    let qctx = match qindex {
+      0..=20 => 0,
+      21..=60 => 1,
+      61..=120 => 2,
+      _ => 3
+    };
     CDFContext {
       partition_cdf: default_partition_cdf,
       kf_y_cdf: default_kf_y_mode_cdf,
@@ -987,23 +1054,34 @@ impl CDFContext {
       skip_cdfs: default_skip_cdfs,
       intra_inter_cdfs: default_intra_inter_cdf,
       angle_delta_cdf: default_angle_delta_cdf,
+      newmv_cdf: default_newmv_cdf,
+      zeromv_cdf: default_zeromv_cdf,
+      refmv_cdf: default_refmv_cdf,
+      single_ref_cdfs: default_single_ref_cdf,
+      comp_inter_cdfs: default_comp_inter_cdf,
+      comp_ref_type_cdfs: default_comp_ref_type_cdf,
+      comp_ref_cdfs: default_comp_ref_cdf,
+      comp_bwdref_cdfs: default_comp_bwdref_cdf,
+      compound_mode_cdf: default_compound_mode_cdf,
+      drl_cdfs: default_drl_cdf,
+      nmv_context: default_nmv_context,
 
       // lv_map
-      txb_skip_cdf: av1_default_txb_skip_cdf,
-      dc_sign_cdf: av1_default_dc_sign_cdf,
-      eob_extra_cdf: av1_default_eob_extra_cdf,
-
-      eob_flag_cdf16: av1_default_eob_multi16,
-      eob_flag_cdf32: av1_default_eob_multi32,
-      eob_flag_cdf64: av1_default_eob_multi64,
-      eob_flag_cdf128: av1_default_eob_multi128,
-      eob_flag_cdf256: av1_default_eob_multi256,
-      eob_flag_cdf512: av1_default_eob_multi512,
-      eob_flag_cdf1024: av1_default_eob_multi1024,
-
-      coeff_base_eob_cdf: av1_default_coeff_base_eob_multi,
-      coeff_base_cdf: av1_default_coeff_base_multi,
-      coeff_br_cdf: av1_default_coeff_lps_multi
+      txb_skip_cdf: av1_default_txb_skip_cdfs[qctx],
+      dc_sign_cdf: av1_default_dc_sign_cdfs[qctx],
+      eob_extra_cdf: av1_default_eob_extra_cdfs[qctx],
+
+      eob_flag_cdf16: av1_default_eob_multi16_cdfs[qctx],
+      eob_flag_cdf32: av1_default_eob_multi32_cdfs[qctx],
+      eob_flag_cdf64: av1_default_eob_multi64_cdfs[qctx],
+      eob_flag_cdf128: av1_default_eob_multi128_cdfs[qctx],
+      eob_flag_cdf256: av1_default_eob_multi256_cdfs[qctx],
+      eob_flag_cdf512: av1_default_eob_multi512_cdfs[qctx],
+      eob_flag_cdf1024: av1_default_eob_multi1024_cdfs[qctx],
+
+      coeff_base_eob_cdf: av1_default_coeff_base_eob_multi_cdfs[qctx],
+      coeff_base_cdf: av1_default_coeff_base_multi_cdfs[qctx],
+      coeff_br_cdf: av1_default_coeff_lps_multi_cdfs[qctx]
     }
   }
 
@@ -1039,6 +1117,40 @@ impl CDFContext {
       self.angle_delta_cdf.first().unwrap().as_ptr() as usize;
     let angle_delta_cdf_end =
       angle_delta_cdf_start + size_of_val(&self.angle_delta_cdf);
+    let newmv_cdf_start = self.newmv_cdf.first().unwrap().as_ptr() as usize;
+    let newmv_cdf_end = newmv_cdf_start + size_of_val(&self.newmv_cdf);
+    let zeromv_cdf_start = self.zeromv_cdf.first().unwrap().as_ptr() as usize;
+    let zeromv_cdf_end = zeromv_cdf_start + size_of_val(&self.zeromv_cdf);
+    let refmv_cdf_start = self.refmv_cdf.first().unwrap().as_ptr() as usize;
+    let refmv_cdf_end = refmv_cdf_start + size_of_val(&self.refmv_cdf);
+    let single_ref_cdfs_start =
+      self.single_ref_cdfs.first().unwrap().as_ptr() as usize;
+    let single_ref_cdfs_end =
+      single_ref_cdfs_start + size_of_val(&self.single_ref_cdfs);
+    let comp_inter_cdfs_start =
+      self.comp_inter_cdfs.first().unwrap().as_ptr() as usize;
+    let comp_inter_cdfs_end =
+      comp_inter_cdfs_start + size_of_val(&self.comp_inter_cdfs);
+    let comp_ref_type_cdfs_start =
+      self.comp_ref_type_cdfs.first().unwrap().as_ptr() as usize;
+    let comp_ref_type_cdfs_end =
+      comp_ref_type_cdfs_start + size_of_val(&self.comp_ref_type_cdfs);
+    let comp_ref_cdfs_start =
+      self.comp_ref_cdfs.first().unwrap().as_ptr() as usize;
+    let comp_ref_cdfs_end =
+      comp_ref_cdfs_start + size_of_val(&self.comp_ref_cdfs);
+    let comp_bwdref_cdfs_start =
+      self.comp_bwdref_cdfs.first().unwrap().as_ptr() as usize;
+    let comp_bwdref_cdfs_end =
+      comp_bwdref_cdfs_start + size_of_val(&self.comp_bwdref_cdfs);
+    let compound_mode_cdf_start =
+      self.compound_mode_cdf.first().unwrap().as_ptr() as usize;
+    let compound_mode_cdf_end =
+      compound_mode_cdf_start + size_of_val(&self.compound_mode_cdf);
+    let drl_cdfs_start = self.drl_cdfs.first().unwrap().as_ptr() as usize;
+    let drl_cdfs_end = drl_cdfs_start + size_of_val(&self.drl_cdfs);
+    let nmv_context_start = &self.nmv_context as *const NMVContext as usize;
+    let nmv_context_end = nmv_context_start + size_of_val(&self.nmv_context);
     let txb_skip_cdf_start =
       self.txb_skip_cdf.first().unwrap().as_ptr() as usize;
     let txb_skip_cdf_end =
@@ -1101,6 +1213,17 @@ impl CDFContext {
       ("skip_cdfs", skip_cdfs_start, skip_cdfs_end),
       ("intra_inter_cdfs", intra_inter_cdfs_start, intra_inter_cdfs_end),
       ("angle_delta_cdf", angle_delta_cdf_start, angle_delta_cdf_end),
+      ("newmv_cdf", newmv_cdf_start, newmv_cdf_end),
+      ("zeromv_cdf", zeromv_cdf_start, zeromv_cdf_end),
+      ("refmv_cdf", refmv_cdf_start, refmv_cdf_end),
+      ("single_ref_cdfs", single_ref_cdfs_start, single_ref_cdfs_end),
+      ("comp_inter_cdfs", comp_inter_cdfs_start, comp_inter_cdfs_end),
+      ("comp_ref_type_cdfs", comp_ref_type_cdfs_start, comp_ref_type_cdfs_end),
+      ("comp_ref_cdfs", comp_ref_cdfs_start, comp_ref_cdfs_end),
+      ("comp_bwdref_cdfs", comp_bwdref_cdfs_start, comp_bwdref_cdfs_end),
+      ("compound_mode_cdf", compound_mode_cdf_start, compound_mode_cdf_end),
+      ("drl_cdfs", drl_cdfs_start, drl_cdfs_end),
+      ("nmv_context", nmv_context_start, nmv_context_end),
       ("txb_skip_cdf", txb_skip_cdf_start, txb_skip_cdf_end),
       ("dc_sign_cdf", dc_sign_cdf_start, dc_sign_cdf_end),
       ("eob_extra_cdf", eob_extra_cdf_start, eob_extra_cdf_end),
@@ -1131,6 +1254,202 @@ mod test {
     let f = &cdf.partition_cdf[2];
     cdf_map.lookup(f.as_ptr() as usize);
   }
+
+  #[test]
+  fn coeff_cdfs_by_qindex() {
+    use super::*;
+
+    for &(qindex, qctx) in &[(0, 0), (20, 0), (21, 1), (120, 2), (255, 3)] {
+      let cdf = CDFContext::new(qindex);
+      assert_eq!(cdf.txb_skip_cdf, av1_default_txb_skip_cdfs[qctx]);
+    }
+  }
+
+  #[test]
+  fn mv_stack_from_neighbours() {
+    use super::*;
+
+    let mut bc = BlockContext::new(16, 16);
+    let bsize = BLOCK_16X16;
+    let neighbours = [
+      (BlockOffset { x: 4, y: 0 }, NEWMV, MotionVector { row: 8, col: -4 }),
+      (BlockOffset { x: 0, y: 4 }, NEARESTMV, MotionVector { row: 16, col: 0 })
+    ];
+    for &(ref bo, mode, mv) in &neighbours {
+      bc.set_mode(bo, bsize, mode);
+      bc.set_block_size(bo, bsize);
+      bc.set_ref_frames(bo, bsize, [LAST_FRAME, NONE_FRAME]);
+      bc.set_motion_vectors(bo, bsize, [mv, MotionVector::default()]);
+    }
+
+    let fi = FrameInvariants::new(64, 64, 100, 3);
+    let bo = BlockOffset { x: 4, y: 4 };
+    let stack = bc.find_mv_stack(&fi, &bo, bsize, [LAST_FRAME, NONE_FRAME]);
+    assert_eq!(stack.num_mv_found, 2);
+    // Equal weights keep the scan order, above first
+    assert_eq!(stack.mvs[0][0], neighbours[0].2);
+    assert_eq!(stack.mvs[1][0], neighbours[1].2);
+    // Matches above and left, one of them NEWMV
+    assert_eq!(stack.mode_context, 4 | 5 << REFMV_OFFSET);
+
+    // Other references do not match, and the left neighbour fills the
+    // stack with the above one
+    let stack = bc.find_mv_stack(&fi, &bo, bsize, [GOLDEN_FRAME, NONE_FRAME]);
+    assert_eq!(stack.num_mv_found, 2);
+    assert_eq!(stack.mode_context, 0);
+  }
+
+  #[test]
+  fn mv_projection() {
+    use super::*;
+
+    let mv = MotionVector { row: 64, col: -30 };
+    assert_eq!(mv.projected(1, 2), MotionVector { row: 32, col: -15 });
+    assert_eq!(mv.projected(-2, 1), MotionVector { row: -128, col: 60 });
+    // Distances are capped, and the projections clamped
+    let mv = MotionVector { row: REFMVS_LIMIT, col: 0 };
+    assert_eq!(
+      mv.projected(40, 1),
+      MotionVector { row: (MV_UPP - 1) as i16, col: 0 }
+    );
+  }
+
+  #[test]
+  fn mv_stack_from_motion_field() {
+    use super::*;
+    use std::rc::Rc;
+    use {ChromaSampling, Frame, ReferenceFrame, REFS_PER_FRAME};
+
+    // LAST_FRAME, the previous frame, moved from its own LAST_FRAME. Its
+    // ALTREF_FRAME is not GOLDEN_FRAME, so it is not an overlay.
+    let mv = MotionVector { row: 32, col: -16 };
+    let mut frame_mvs = vec![vec![None; 8]; 8];
+    frame_mvs[2][2] = Some(SavedMotionVector { ref_frame: LAST_FRAME, mv });
+    let mut ref_order_hints = [0; REFS_PER_FRAME];
+    ref_order_hints[ALTREF_FRAME - LAST_FRAME] = 4;
+    let mut fi = FrameInvariants::new(64, 64, 100, 3);
+    fi.frame_type = FrameType::INTER;
+    fi.order_hint = 2;
+    fi.use_prev_frame_mvs = true;
+    fi.ref_frames[0] = Some(Rc::new(ReferenceFrame {
+      frame: Frame::new(64, 64, ChromaSampling::CS420),
+      cdfs: CDFContext::new(100),
+      frame_type: FrameType::INTER,
+      number: 1,
+      order_hint: 1,
+      ref_order_hints,
+      frame_mvs
+    }));
+    fi.motion_field = project_motion_field(&fi);
+    assert_eq!(
+      fi.motion_field[2][2],
+      Some(TemporalMotionVector { mv, ref_offset: 1 })
+    );
+
+    // The projection over the block is the only candidate, and it is far
+    // from the global motion
+    let mut bc = BlockContext::new(16, 16);
+    let bsize = BLOCK_16X16;
+    let bo = BlockOffset { x: 4, y: 4 };
+    let stack = bc.find_mv_stack(&fi, &bo, bsize, [LAST_FRAME, NONE_FRAME]);
+    assert_eq!(stack.num_mv_found, 1);
+    assert_eq!(stack.mvs[0][0], mv);
+    assert_eq!(stack.weights[0], 2);
+    assert_eq!(stack.mode_context, 1 << GLOBALMV_OFFSET);
+
+    fi.motion_field[2][2] = Some(TemporalMotionVector {
+      mv: MotionVector { row: 8, col: 0 },
+      ref_offset: 1
+    });
+    let stack = bc.find_mv_stack(&fi, &bo, bsize, [LAST_FRAME, NONE_FRAME]);
+    assert_eq!(stack.mode_context, 0);
+
+    // The motion vectors into past references are saved with the frame
+    bc.set_ref_frames(&bo, bsize, [LAST_FRAME, NONE_FRAME]);
+    bc.set_motion_vectors(&bo, bsize, [mv, MotionVector::default()]);
+    let saved = bc.saved_motion_vectors(&fi);
+    assert_eq!(
+      saved[3][3],
+      Some(SavedMotionVector { ref_frame: LAST_FRAME, mv })
+    );
+    assert_eq!(saved[1][1], None);
+  }
+
+  #[test]
+  fn newmv_coded_against_mv_stack() {
+    use super::*;
+
+    let mut bc = BlockContext::new(16, 16);
+    let bsize = BLOCK_16X16;
+    for &(ref bo, mode, mv) in &[
+      (BlockOffset { x: 4, y: 0 }, NEWMV, MotionVector { row: 8, col: -4 }),
+      (BlockOffset { x: 0, y: 4 }, NEARESTMV, MotionVector { row: 16, col: 0 })
+    ] {
+      bc.set_mode(bo, bsize, mode);
+      bc.set_block_size(bo, bsize);
+      bc.set_ref_frames(bo, bsize, [LAST_FRAME, NONE_FRAME]);
+      bc.set_motion_vectors(bo, bsize, [mv, MotionVector::default()]);
+    }
+
+    let fi = FrameInvariants::new(64, 64, 100, 3);
+    let hp = fi.allow_high_precision_mv;
+    let bo = BlockOffset { x: 4, y: 4 };
+    let stack = bc.find_mv_stack(&fi, &bo, bsize, [LAST_FRAME, NONE_FRAME]);
+    let mv = MotionVector { row: 26, col: -12 };
+
+    let fc = CDFContext::new(fi.qindex as u8);
+    let mut cw = ContextWriter::new(ec::Writer::new(), fc, bc);
+    cw.write_inter_mode(NEWMV, stack.mode_context);
+    cw.write_drl_mode(NEWMV, 0, &stack);
+    cw.write_mv(&mv, &stack.mvs[0][0], hp);
+    let buf = cw.w.done();
+
+    // Decode as the specification does, predicting from the stack
+    let mut fc = CDFContext::new(fi.qindex as u8);
+    let mut r = ec::Reader::new(&buf);
+    let newmv_ctx = stack.mode_context & NEWMV_CTX_MASK;
+    assert_eq!(r.symbol(&mut fc.newmv_cdf[newmv_ctx], 2), 0);
+    let mut ref_mv_idx = 0;
+    for idx in 0..2 {
+      if stack.num_mv_found > idx + 1 {
+        if r.symbol(&mut fc.drl_cdfs[stack.drl_ctx(idx)], 2) == 0 {
+          break;
+        }
+        ref_mv_idx = idx + 1;
+      }
+    }
+    assert_eq!(ref_mv_idx, 0);
+    let joint = r.symbol(&mut fc.nmv_context.joints_cdf, MV_JOINTS);
+    let mut diff = [0i16; 2];
+    for (comp, c) in fc.nmv_context.comps.iter_mut().enumerate() {
+      if joint >> (1 - comp) & 1 == 0 {
+        continue;
+      }
+      let sign = r.symbol(&mut c.sign_cdf, 2);
+      let mv_class = r.symbol(&mut c.classes_cdf, MV_CLASSES);
+      let (base, d, fr, hp_bit) = if mv_class == 0 {
+        let d = r.symbol(&mut c.class0_cdf, CLASS0_SIZE);
+        let fr = r.symbol(&mut c.class0_fp_cdf[d as usize], MV_FP_SIZE);
+        let hp_bit = if hp { r.symbol(&mut c.class0_hp_cdf, 2) } else { 1 };
+        (0, d, fr, hp_bit)
+      } else {
+        let mut d = 0;
+        for i in 0..mv_class {
+          d |= r.symbol(&mut c.bits_cdf[i as usize], 2) << i;
+        }
+        let fr = r.symbol(&mut c.fp_cdf, MV_FP_SIZE);
+        let hp_bit = if hp { r.symbol(&mut c.hp_cdf, 2) } else { 1 };
+        ((CLASS0_SIZE as u32) << (mv_class + 2), d, fr, hp_bit)
+      };
+      let mag = (base + (d << 3 | fr << 1 | hp_bit) + 1) as i16;
+      diff[comp] = if sign == 1 { -mag } else { mag };
+    }
+    let ref_mv = stack.mvs[ref_mv_idx][0];
+    assert_eq!(
+      MotionVector { row: ref_mv.row + diff[0], col: ref_mv.col + diff[1] },
+      mv
+    );
+  }
 }
 
 const SUPERBLOCK_TO_PLANE_SHIFT: usize = MAX_SB_SIZE_LOG2;
@@ -1197,12 +1516,177 @@ impl BlockOffset {
   }
 }
 
+/// Motion vector in 1/8 sample units
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct MotionVector {
+  pub row: i16,
+  pub col: i16
+}
+
+impl MotionVector {
+  /// The vector rounded towards zero to 1/4 sample units, unless
+  /// allow_high_precision_mv.
+  pub fn lowered_precision(self, allow_high_precision_mv: bool) -> Self {
+    if allow_high_precision_mv {
+      self
+    } else {
+      MotionVector {
+        row: self.row - self.row.signum() * (self.row & 1),
+        col: self.col - self.col.signum() * (self.col & 1)
+      }
+    }
+  }
+
+  /// The vector scaled from a frame distance of den to one of num, as in
+  /// the projection of the motion field.
+  pub fn projected(self, num: i32, den: i32) -> Self {
+    let den = cmp::min(den, MAX_FRAME_DISTANCE);
+    let num = clamp(num, -MAX_FRAME_DISTANCE, MAX_FRAME_DISTANCE);
+    let scale = |v: i16| {
+      let v = v as i32 * num * DIV_MULT[den as usize];
+      let v = if v < 0 {
+        -((-v + (1 << 13)) >> 14)
+      } else {
+        (v + (1 << 13)) >> 14
+      };
+      clamp(v, MV_LOW + 1, MV_UPP - 1) as i16
+    };
+    MotionVector { row: scale(self.row), col: scale(self.col) }
+  }
+}
+
+/// Motion vector of an 8x8 block of a reference frame into one of its
+/// past references, kept for the temporal candidates
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SavedMotionVector {
+  pub ref_frame: usize,
+  pub mv: MotionVector
+}
+
+/// Saved motion vector of a reference whose trajectory crosses an 8x8 block
+/// of the frame, with the distance it spans in order hints
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TemporalMotionVector {
+  pub mv: MotionVector,
+  pub ref_offset: i32
+}
+
+/// Project the saved motion vectors of the references onto the frame, as
+/// the decoder does when use_ref_frame_mvs is on. An 8x8 block takes the
+/// last motion vector whose trajectory crosses it.
+pub fn project_motion_field(
+  fi: &FrameInvariants
+) -> Vec<Vec<Option<TemporalMotionVector>>> {
+  let mut field = vec![vec![None; fi.w_in_b >> 1]; fi.h_in_b >> 1];
+  let is_future =
+    |r: usize| relative_dist(fi.ref_frame_order_hint(r), fi.order_hint) > 0;
+
+  let mut ref_stamp = MFMV_STACK_SIZE as isize - 1;
+  if let Some(ref last) = fi.ref_frames[0] {
+    // LAST_FRAME is skipped when it is an overlay, with GOLDEN_FRAME as its
+    // ALTREF_FRAME
+    let golden_order_hint = fi.ref_frame_order_hint(GOLDEN_FRAME);
+    if last.ref_order_hints[ALTREF_FRAME - LAST_FRAME] != golden_order_hint {
+      project_reference(fi, &mut field, LAST_FRAME, true);
+    }
+    ref_stamp -= 1;
+  }
+  for &r in &[BWDREF_FRAME, ALTREF2_FRAME, ALTREF_FRAME] {
+    if is_future(r)
+      && (r != ALTREF_FRAME || ref_stamp >= 0)
+      && project_reference(fi, &mut field, r, false)
+    {
+      ref_stamp -= 1;
+    }
+  }
+  if ref_stamp >= 0 {
+    project_reference(fi, &mut field, LAST2_FRAME, true);
+  }
+  field
+}
+
+// Project the saved motion vectors of the reference src onto the frame,
+// backwards for the past references. Returns false if src has none, as an
+// intra frame.
+fn project_reference(
+  fi: &FrameInvariants, field: &mut [Vec<Option<TemporalMotionVector>>],
+  src: usize, past: bool
+) -> bool {
+  let r = match fi.ref_frames[src - LAST_FRAME] {
+    Some(ref r) => r,
+    None => return false
+  };
+  if r.frame_type == FrameType::KEY || r.frame_type == FrameType::INTRA_ONLY {
+    return false;
+  }
+
+  let mut to_frame = relative_dist(r.order_hint, fi.order_hint);
+  if past {
+    to_frame = -to_frame;
+  }
+  let rows8 = (fi.h_in_b >> 1) as isize;
+  let cols8 = (fi.w_in_b >> 1) as isize;
+  // Offset along the trajectory in 8x8 blocks, rounded towards zero
+  let offset8 = |v: i16| {
+    if v >= 0 {
+      (v >> 6) as isize
+    } else {
+      -((-v >> 6) as isize)
+    }
+  };
+
+  for (row8, saved_row) in r.frame_mvs.iter().enumerate() {
+    for (col8, saved) in saved_row.iter().enumerate() {
+      let saved = match *saved {
+        Some(saved) => saved,
+        None => continue
+      };
+      let ref_offset = relative_dist(
+        r.order_hint,
+        r.ref_order_hints[saved.ref_frame - LAST_FRAME]
+      );
+      if ref_offset <= 0
+        || ref_offset > MAX_FRAME_DISTANCE
+        || to_frame.abs() > MAX_FRAME_DISTANCE
+      {
+        continue;
+      }
+      let mv = saved.mv.projected(to_frame, ref_offset);
+      let (row, col) = if past {
+        (row8 as isize - offset8(mv.row), col8 as isize - offset8(mv.col))
+      } else {
+        (row8 as isize + offset8(mv.row), col8 as isize + offset8(mv.col))
+      };
+      // The block stays in the frame, in the same 64 rows and within 64
+      // columns of the same 64 columns
+      let base_row = (row8 & !7) as isize;
+      let base_col = (col8 & !7) as isize;
+      if row < 0
+        || row >= rows8
+        || col < 0
+        || col >= cols8
+        || row < base_row
+        || row >= base_row + 8
+        || col < base_col - 8
+        || col >= base_col + 16
+      {
+        continue;
+      }
+      field[row as usize][col as usize] =
+        Some(TemporalMotionVector { mv: saved.mv, ref_offset });
+    }
+  }
+  true
+}
+
 #[derive(Copy, Clone)]
 pub struct Block {
   pub mode: PredictionMode,
   pub bsize: BlockSize,
   pub partition: PartitionType,
-  pub skip: bool
+  pub skip: bool,
+  pub ref_frames: [usize; 2],
+  pub mvs: [MotionVector; 2]
 }
 
 impl Block {
@@ -1211,12 +1695,153 @@ impl Block {
       mode: PredictionMode::DC_PRED,
       bsize: BlockSize::BLOCK_64X64,
       partition: PartitionType::PARTITION_NONE,
-      skip: false
+      skip: false,
+      ref_frames: [INTRA_FRAME, NONE_FRAME],
+      mvs: [MotionVector::default(); 2]
     }
   }
   pub fn is_inter(&self) -> bool {
     self.mode >= PredictionMode::NEARESTMV
   }
+  pub fn has_second_ref(&self) -> bool {
+    self.ref_frames[1] != NONE_FRAME
+  }
+}
+
+/// Candidate motion vectors of a block for NEARESTMV, NEARMV and as NEWMV
+/// predictors, found among the motion vectors of its neighbours
+#[derive(Clone, Copy)]
+pub struct RefMVStack {
+  /// Candidates for each reference, by decreasing weight
+  pub mvs: [[MotionVector; 2]; MAX_REF_MV_STACK_SIZE],
+  pub weights: [u32; MAX_REF_MV_STACK_SIZE],
+  pub num_mv_found: usize,
+  /// Packed contexts of the inter mode symbols
+  pub mode_context: usize,
+  /// Motion vectors of the block in GLOBALMV modes
+  pub global_mvs: [MotionVector; 2],
+  ref_frames: [usize; 2],
+  allow_high_precision_mv: bool,
+  // Whether the global motion of each reference is more than a translation
+  warped_global: [bool; 2],
+  new_mv_count: usize,
+  found_match: bool
+}
+
+impl RefMVStack {
+  fn new(
+    fi: &FrameInvariants, bo: &BlockOffset, bsize: BlockSize,
+    ref_frames: [usize; 2]
+  ) -> RefMVStack {
+    let mut global_mvs = [MotionVector::default(); 2];
+    let mut warped_global = [false; 2];
+    for i in 0..2 {
+      let ref_frame = ref_frames[i];
+      if ref_frame != NONE_FRAME {
+        let gm_type = fi.globalmv_transformation_type[ref_frame];
+        global_mvs[i] = global_mv(
+          gm_type,
+          &fi.gm_params[ref_frame],
+          bo,
+          bsize,
+          fi.allow_high_precision_mv
+        );
+        warped_global[i] = gm_type > GlobalMVMode::TRANSLATION;
+      }
+    }
+
+    RefMVStack {
+      mvs: [[MotionVector::default(); 2]; MAX_REF_MV_STACK_SIZE],
+      weights: [0; MAX_REF_MV_STACK_SIZE],
+      num_mv_found: 0,
+      mode_context: 0,
+      global_mvs,
+      ref_frames,
+      allow_high_precision_mv: fi.allow_high_precision_mv,
+      warped_global,
+      new_mv_count: 0,
+      found_match: false
+    }
+  }
+
+  fn is_compound(&self) -> bool {
+    self.ref_frames[1] != NONE_FRAME
+  }
+
+  // Add a candidate, or add weight to it if it is already in the stack
+  fn add(
+    &mut self, mode: PredictionMode, mvs: [MotionVector; 2], weight: u32
+  ) {
+    if mode.has_newmv() {
+      self.new_mv_count += 1;
+    }
+    self.found_match = true;
+    self.insert(mvs, weight);
+  }
+
+  // Add a candidate or its weight, with no neighbour to count
+  fn insert(&mut self, mvs: [MotionVector; 2], weight: u32) {
+    let mut mvs = mvs;
+    for mv in mvs.iter_mut() {
+      *mv = mv.lowered_precision(self.allow_high_precision_mv);
+    }
+
+    match self.mvs[..self.num_mv_found].iter().position(|&m| m == mvs) {
+      Some(idx) => self.weights[idx] += weight,
+      None =>
+        if self.num_mv_found < MAX_REF_MV_STACK_SIZE {
+          self.mvs[self.num_mv_found] = mvs;
+          self.weights[self.num_mv_found] = weight;
+          self.num_mv_found += 1;
+        },
+    }
+  }
+
+  // Whether a candidate is 2 samples or more away from the global motion
+  fn is_far_from_global(&self, mvs: [MotionVector; 2]) -> bool {
+    (0..1 + self.is_compound() as usize).any(|list| {
+      let (mv, global_mv) = (mvs[list], self.global_mvs[list]);
+      (mv.row as i32 - global_mv.row as i32).abs() >= 16
+        || (mv.col as i32 - global_mv.col as i32).abs() >= 16
+    })
+  }
+
+  // Whether a candidate was found since the last call
+  fn take_match(&mut self) -> bool {
+    let found_match = self.found_match;
+    self.found_match = false;
+    found_match
+  }
+
+  // Stable sort of the candidates in start..end by decreasing weight
+  fn sort(&mut self, start: usize, end: usize) {
+    let mut end = end;
+    while end > start {
+      let mut new_end = start;
+      for idx in start + 1..end {
+        if self.weights[idx - 1] < self.weights[idx] {
+          self.mvs.swap(idx - 1, idx);
+          self.weights.swap(idx - 1, idx);
+          new_end = idx;
+        }
+      }
+      end = new_end;
+    }
+  }
+
+  /// Context of the drl_mode symbol choosing between candidates idx and
+  /// idx + 1.
+  pub fn drl_ctx(&self, idx: usize) -> usize {
+    match (
+      self.weights[idx] >= REF_CAT_LEVEL,
+      self.weights[idx + 1] >= REF_CAT_LEVEL
+    ) {
+      (true, true) => 0,
+      (true, false) => 1,
+      (false, false) => 2,
+      (false, true) => 0
+    }
+  }
 }
 
 pub struct TXB_CTX {
@@ -1232,6 +1857,9 @@ pub struct BlockContext {
   left_partition_context: [u8; MAX_MIB_SIZE],
   above_coeff_context: [Vec<u8>; PLANES],
   left_coeff_context: [[u8; MAX_MIB_SIZE]; PLANES],
+  // Columns and rows of the tile being coded, in 4x4 blocks
+  tile_cols: ops::Range<usize>,
+  tile_rows: ops::Range<usize>,
   blocks: Vec<Vec<Block>>
 }
 
@@ -1251,6 +1879,8 @@ impl BlockContext {
         vec![0; cols << (MI_SIZE_LOG2 - TxSize::smallest_width_log2())]
       ],
       left_coeff_context: [[0; MAX_MIB_SIZE]; PLANES],
+      tile_cols: 0..cols,
+      tile_rows: 0..rows,
       blocks: vec![vec![Block::default(); cols]; rows]
     }
   }
@@ -1263,6 +1893,8 @@ impl BlockContext {
       left_partition_context: self.left_partition_context,
       above_coeff_context: self.above_coeff_context.clone(),
       left_coeff_context: self.left_coeff_context,
+      tile_cols: self.tile_cols.clone(),
+      tile_rows: self.tile_rows.clone(),
       blocks: vec![vec![Block::default(); 0]; 0]
     }
   }
@@ -1276,12 +1908,69 @@ impl BlockContext {
     self.left_coeff_context = checkpoint.left_coeff_context;
   }
 
+  /// Motion vectors of the frame saved for the temporal candidates of later
+  /// frames, by 8x8 block: those of its bottom right 4x4 block into past
+  /// references, the second one if both are.
+  pub fn saved_motion_vectors(
+    &self, fi: &FrameInvariants
+  ) -> Vec<Vec<Option<SavedMotionVector>>> {
+    let saved = |b: &Block| {
+      let mut saved = None;
+      for list in 0..2 {
+        let (ref_frame, mv) = (b.ref_frames[list], b.mvs[list]);
+        if ref_frame == INTRA_FRAME || ref_frame == NONE_FRAME {
+          continue;
+        }
+        let order_hint = fi.ref_frame_order_hint(ref_frame);
+        if relative_dist(order_hint, fi.order_hint) < 0
+          && mv.row.abs() <= REFMVS_LIMIT
+          && mv.col.abs() <= REFMVS_LIMIT
+        {
+          saved = Some(SavedMotionVector { ref_frame, mv });
+        }
+      }
+      saved
+    };
+    (0..self.rows >> 1)
+      .map(|row8| {
+        (0..self.cols >> 1)
+          .map(|col8| saved(&self.blocks[2 * row8 + 1][2 * col8 + 1]))
+          .collect()
+      }).collect()
+  }
+
   pub fn at(&mut self, bo: &BlockOffset) -> &mut Block {
     &mut self.blocks[bo.y][bo.x]
   }
 
+  /// Code the following blocks in the tile of columns `cols` and rows
+  /// `rows`, in 4x4 blocks, independently of the rest of the frame.
+  pub fn set_tile(
+    &mut self, cols: ops::Range<usize>, rows: ops::Range<usize>
+  ) {
+    for c in &mut self.above_partition_context {
+      *c = 0;
+    }
+    for p in 0..PLANES {
+      for c in &mut self.above_coeff_context[p] {
+        *c = 0;
+      }
+    }
+    self.tile_cols = cols;
+    self.tile_rows = rows;
+  }
+
+  // Whether the blocks above and to the left of bo are in its tile
+  pub fn has_above(&self, bo: &BlockOffset) -> bool {
+    bo.y > self.tile_rows.start
+  }
+
+  pub fn has_left(&self, bo: &BlockOffset) -> bool {
+    bo.x > self.tile_cols.start
+  }
+
   pub fn above_of(&mut self, bo: &BlockOffset) -> Block {
-    if bo.y > 0 {
+    if self.has_above(bo) {
       self.blocks[bo.y - 1][bo.x]
     } else {
       Block::default()
@@ -1289,7 +1978,7 @@ impl BlockContext {
   }
 
   pub fn left_of(&mut self, bo: &BlockOffset) -> Block {
-    if bo.x > 0 {
+    if self.has_left(bo) {
       self.blocks[bo.y][bo.x - 1]
     } else {
       Block::default()
@@ -1395,10 +2084,48 @@ impl BlockContext {
     }
   }
 
+  pub fn set_block_size(&mut self, bo: &BlockOffset, bsize: BlockSize) {
+    let bw = bsize.width_mi();
+    let bh = bsize.height_mi();
+
+    for y in 0..bh {
+      for x in 0..bw {
+        self.blocks[bo.y + y][bo.x + x].bsize = bsize;
+      }
+    }
+  }
+
+  pub fn set_motion_vectors(
+    &mut self, bo: &BlockOffset, bsize: BlockSize, mvs: [MotionVector; 2]
+  ) {
+    let bw = bsize.width_mi();
+    let bh = bsize.height_mi();
+
+    for y in 0..bh {
+      for x in 0..bw {
+        self.blocks[bo.y + y][bo.x + x].mvs = mvs;
+      }
+    }
+  }
+
   pub fn get_mode(&mut self, bo: &BlockOffset) -> PredictionMode {
     self.blocks[bo.y][bo.x].mode
   }
 
+  pub fn set_ref_frames(
+    &mut self, bo: &BlockOffset, bsize: BlockSize, ref_frames: [usize; 2]
+  ) {
+    let bw = bsize.width_mi();
+    let bh = bsize.height_mi();
+
+    for y in 0..bh {
+      for x in 0..bw {
+        self.blocks[bo.y + y as usize][bo.x + x as usize].ref_frames =
+          ref_frames;
+      }
+    }
+  }
+
   fn partition_plane_context(
     &self, bo: &BlockOffset, bsize: BlockSize
   ) -> usize {
@@ -1439,12 +2166,12 @@ impl BlockContext {
   }
 
   fn skip_context(&mut self, bo: &BlockOffset) -> usize {
-    let above_skip = if bo.y > 0 {
+    let above_skip = if self.has_above(bo) {
       self.above_of(bo).skip as usize
     } else {
       0
     };
-    let left_skip = if bo.x > 0 {
+    let left_skip = if self.has_left(bo) {
       self.left_of(bo).skip as usize
     } else {
       0
@@ -1471,8 +2198,8 @@ impl BlockContext {
   // 2 - intra/--, --/intra
   // 3 - intra/intra
   pub fn intra_inter_context(&mut self, bo: &BlockOffset) -> usize {
-    let has_above = bo.y > 0;
-    let has_left = bo.x > 0;
+    let has_above = self.has_above(bo);
+    let has_left = self.has_left(bo);
 
     match (has_above, has_left) {
       (true, true) => {
@@ -1494,6 +2221,591 @@ impl BlockContext {
     }
   }
 
+  // Number of references of the available above and left neighbours equal
+  // to frame_type
+  fn count_refs(&mut self, bo: &BlockOffset, frame_type: usize) -> usize {
+    let mut c = 0;
+    if self.has_above(bo) {
+      let above = self.above_of(bo);
+      c += above.ref_frames.iter().filter(|&&r| r == frame_type).count();
+    }
+    if self.has_left(bo) {
+      let left = self.left_of(bo);
+      c += left.ref_frames.iter().filter(|&&r| r == frame_type).count();
+    }
+    c
+  }
+
+  fn ref_count_ctx(counts0: usize, counts1: usize) -> usize {
+    if counts0 < counts1 {
+      0
+    } else if counts0 == counts1 {
+      1
+    } else {
+      2
+    }
+  }
+
+  // Contexts of single_ref_p1 to single_ref_p6, each comparing the
+  // neighbour counts of the two groups of references the symbol selects
+  // between. The compound reference symbols share them: comp_ref,
+  // comp_ref_p1 and comp_ref_p2 use those of p3, p4 and p5, comp_bwdref and
+  // comp_bwdref_p1 those of p2 and p6.
+  fn single_ref_ctx(&mut self, bo: &BlockOffset, p: usize) -> usize {
+    let groups: (&[usize], &[usize]) = match p {
+      1 => (
+        &[LAST_FRAME, LAST2_FRAME, LAST3_FRAME, GOLDEN_FRAME],
+        &[BWDREF_FRAME, ALTREF2_FRAME, ALTREF_FRAME]
+      ),
+      2 => (&[BWDREF_FRAME, ALTREF2_FRAME], &[ALTREF_FRAME]),
+      3 => (&[LAST_FRAME, LAST2_FRAME], &[LAST3_FRAME, GOLDEN_FRAME]),
+      4 => (&[LAST_FRAME], &[LAST2_FRAME]),
+      5 => (&[LAST3_FRAME], &[GOLDEN_FRAME]),
+      6 => (&[BWDREF_FRAME], &[ALTREF2_FRAME]),
+      _ => unreachable!()
+    };
+    let counts0 = groups.0.iter().map(|&r| self.count_refs(bo, r)).sum();
+    let counts1 = groups.1.iter().map(|&r| self.count_refs(bo, r)).sum();
+    BlockContext::ref_count_ctx(counts0, counts1)
+  }
+
+  // Context of comp_mode, from the use of compound prediction and of
+  // backward references by the neighbours
+  fn comp_inter_ctx(&mut self, bo: &BlockOffset) -> usize {
+    fn check_backward(b: &Block) -> bool {
+      b.ref_frames[0] >= BWDREF_FRAME
+    }
+    let above = if self.has_above(bo) {
+      Some(self.above_of(bo))
+    } else {
+      None
+    };
+    let left = if self.has_left(bo) {
+      Some(self.left_of(bo))
+    } else {
+      None
+    };
+    match (above, left) {
+      (Some(a), Some(l)) => match (a.has_second_ref(), l.has_second_ref()) {
+        (false, false) => (check_backward(&a) ^ check_backward(&l)) as usize,
+        (false, true) => 2 + (check_backward(&a) || !a.is_inter()) as usize,
+        (true, false) => 2 + (check_backward(&l) || !l.is_inter()) as usize,
+        (true, true) => 4
+      },
+      (Some(b), None) | (None, Some(b)) =>
+        if b.has_second_ref() {
+          3
+        } else {
+          check_backward(&b) as usize
+        },
+      (None, None) => 1
+    }
+  }
+
+  // Context of comp_ref_type, from the use of unidirectional and
+  // bidirectional compound prediction by the neighbours
+  fn comp_ref_type_ctx(&mut self, bo: &BlockOffset) -> usize {
+    fn is_backward(r: usize) -> bool {
+      r >= BWDREF_FRAME
+    }
+    fn has_uni_comp_refs(b: &Block) -> bool {
+      b.has_second_ref()
+        && is_backward(b.ref_frames[0]) == is_backward(b.ref_frames[1])
+    }
+    let above = if self.has_above(bo) {
+      Some(self.above_of(bo))
+    } else {
+      None
+    };
+    let left = if self.has_left(bo) {
+      Some(self.left_of(bo))
+    } else {
+      None
+    };
+    match (above, left) {
+      (Some(a), Some(l)) => {
+        if !a.is_inter() && !l.is_inter() {
+          2
+        } else if !a.is_inter() || !l.is_inter() {
+          let b = if a.is_inter() {
+            a
+          } else {
+            l
+          };
+          if b.has_second_ref() {
+            1 + 2 * has_uni_comp_refs(&b) as usize
+          } else {
+            2
+          }
+        } else {
+          let (a0, l0) = (a.ref_frames[0], l.ref_frames[0]);
+          match (a.has_second_ref(), l.has_second_ref()) {
+            (false, false) =>
+              1 + 2 * (is_backward(a0) == is_backward(l0)) as usize,
+            (false, true) | (true, false) => {
+              let b = if a.has_second_ref() {
+                a
+              } else {
+                l
+              };
+              if has_uni_comp_refs(&b) {
+                3 + (is_backward(a0) == is_backward(l0)) as usize
+              } else {
+                1
+              }
+            }
+            (true, true) =>
+              match (has_uni_comp_refs(&a), has_uni_comp_refs(&l)) {
+                (false, false) => 0,
+                (true, true) =>
+                  3 + ((a0 == BWDREF_FRAME) == (l0 == BWDREF_FRAME)) as usize,
+                _ => 2
+              },
+          }
+        }
+      }
+      (Some(b), None) | (None, Some(b)) =>
+        if b.is_inter() && b.has_second_ref() {
+          4 * has_uni_comp_refs(&b) as usize
+        } else {
+          2
+        },
+      (None, None) => 2
+    }
+  }
+
+  // Whether (row, col) lies in the tile. Rows and columns above and to
+  // the left of a block are coded before it.
+  fn is_inside(&self, row: isize, col: isize) -> bool {
+    row >= self.tile_rows.start as isize
+      && col >= self.tile_cols.start as isize
+      && (row as usize) < self.tile_rows.end
+      && (col as usize) < self.tile_cols.end
+  }
+
+  // Whether the block above and to the right of a square block is coded
+  // before it, with PARTITION_SPLIT as the only partition into several
+  // blocks
+  fn has_top_right(&self, bo: &BlockOffset, bsize: BlockSize) -> bool {
+    let mut bs = bsize.width_mi();
+    let mask_row = bo.y & MAX_MIB_MASK;
+    let mask_col = bo.x & MAX_MIB_MASK;
+
+    // The bottom right block of a split has no top right, and neither have
+    // the bottom right blocks of the blocks it is the bottom right of
+    if mask_row & bs != 0 && mask_col & bs != 0 {
+      return false;
+    }
+    while bs < MAX_MIB_SIZE && mask_col & bs != 0 {
+      if mask_col & (2 * bs) != 0 && mask_row & (2 * bs) != 0 {
+        return false;
+      }
+      bs <<= 1;
+    }
+    true
+  }
+
+  fn add_ref_mv_candidate(
+    &self, stack: &mut RefMVStack, row: usize, col: usize, weight: u32
+  ) {
+    let b = &self.blocks[row][col];
+    if !b.is_inter() {
+      return;
+    }
+    // Warped GLOBALMV neighbours count with the global motion of the block
+    let is_global = (b.mode == ZEROMV || b.mode == ZERO_ZEROMV)
+      && cmp::min(b.bsize.width(), b.bsize.height()) >= 8;
+    if !stack.is_compound() {
+      for list in 0..2 {
+        if b.ref_frames[list] == stack.ref_frames[0] {
+          let mv = if is_global && stack.warped_global[0] {
+            stack.global_mvs[0]
+          } else {
+            b.mvs[list]
+          };
+          stack.add(b.mode, [mv, MotionVector::default()], weight);
+        }
+      }
+    } else if b.ref_frames == stack.ref_frames {
+      let mut mvs = b.mvs;
+      for list in 0..2 {
+        if is_global && stack.warped_global[list] {
+          mvs[list] = stack.global_mvs[list];
+        }
+      }
+      stack.add(b.mode, mvs, weight);
+    }
+  }
+
+  // Scan the row delta_row rows above the block
+  fn scan_row(
+    &self, stack: &mut RefMVStack, bo: &BlockOffset, bsize: BlockSize,
+    delta_row: isize
+  ) {
+    let bw4 = bsize.width_mi();
+    let end4 = cmp::min(cmp::min(bw4, self.cols - bo.x), 16);
+    let use_step16 = bw4 >= 16;
+    let (delta_row, delta_col) = if delta_row.abs() > 1 {
+      (delta_row + (bo.y & 1) as isize, 1 - (bo.x & 1) as isize)
+    } else {
+      (delta_row, 0)
+    };
+
+    let mut i = 0;
+    while i < end4 {
+      let row = bo.y as isize + delta_row;
+      let col = bo.x as isize + delta_col + i as isize;
+      if !self.is_inside(row, col) {
+        break;
+      }
+      let (row, col) = (row as usize, col as usize);
+      let mut len = cmp::min(bw4, self.blocks[row][col].bsize.width_mi());
+      if delta_row.abs() > 1 {
+        len = cmp::max(2, len);
+      }
+      if use_step16 {
+        len = cmp::max(4, len);
+      }
+      self.add_ref_mv_candidate(stack, row, col, 2 * len as u32);
+      i += len;
+    }
+  }
+
+  // Scan the column delta_col columns left of the block
+  fn scan_col(
+    &self, stack: &mut RefMVStack, bo: &BlockOffset, bsize: BlockSize,
+    delta_col: isize
+  ) {
+    let bh4 = bsize.height_mi();
+    let end4 = cmp::min(cmp::min(bh4, self.rows - bo.y), 16);
+    let use_step16 = bh4 >= 16;
+    let (delta_row, delta_col) = if delta_col.abs() > 1 {
+      (1 - (bo.y & 1) as isize, delta_col + (bo.x & 1) as isize)
+    } else {
+      (0, delta_col)
+    };
+
+    let mut i = 0;
+    while i < end4 {
+      let row = bo.y as isize + delta_row + i as isize;
+      let col = bo.x as isize + delta_col;
+      if !self.is_inside(row, col) {
+        break;
+      }
+      let (row, col) = (row as usize, col as usize);
+      let mut len = cmp::min(bh4, self.blocks[row][col].bsize.height_mi());
+      if delta_col.abs() > 1 {
+        len = cmp::max(2, len);
+      }
+      if use_step16 {
+        len = cmp::max(4, len);
+      }
+      self.add_ref_mv_candidate(stack, row, col, 2 * len as u32);
+      i += len;
+    }
+  }
+
+  fn scan_point(
+    &self, stack: &mut RefMVStack, bo: &BlockOffset, delta_row: isize,
+    delta_col: isize
+  ) {
+    let row = bo.y as isize + delta_row;
+    let col = bo.x as isize + delta_col;
+    if self.is_inside(row, col) {
+      self.add_ref_mv_candidate(stack, row as usize, col as usize, 4);
+    }
+  }
+
+  // Add the motion field of the frame projected onto the references, over
+  // the block and below and right of it. Returns the zeromv context, 0 if
+  // the projection at the top left of the block is close to the global
+  // motion.
+  fn scan_temporal(
+    &self, fi: &FrameInvariants, stack: &mut RefMVStack, bo: &BlockOffset,
+    bsize: BlockSize
+  ) -> usize {
+    let (bw4, bh4) = (bsize.width_mi(), bsize.height_mi());
+    let step_h = if bh4 >= 16 { 4 } else { 2 };
+    let step_w = if bw4 >= 16 { 4 } else { 2 };
+
+    let mut zeromv_ctx = 1;
+    let mut delta_row = 0;
+    while delta_row < cmp::min(bh4, 16) {
+      let mut delta_col = 0;
+      while delta_col < cmp::min(bw4, 16) {
+        let mvs = self.add_tpl_ref_mv(
+          fi,
+          stack,
+          bo,
+          delta_row as isize,
+          delta_col as isize
+        );
+        if delta_row == 0 && delta_col == 0 {
+          if let Some(mvs) = mvs {
+            zeromv_ctx = stack.is_far_from_global(mvs) as usize;
+          }
+        }
+        delta_col += step_w;
+      }
+      delta_row += step_h;
+    }
+
+    // Below left, below right and right of blocks from 8x8 to 32x32,
+    // within the superblock
+    if cmp::min(bw4, bh4) >= 2 && cmp::max(bw4, bh4) < 16 {
+      let (bw4, bh4) = (bw4 as isize, bh4 as isize);
+      for &(delta_row, delta_col) in
+        &[(bh4, -2), (bh4, bw4), (bh4 - 2, bw4)]
+      {
+        let row = (bo.y & MAX_MIB_MASK) as isize + delta_row;
+        let col = (bo.x & MAX_MIB_MASK) as isize + delta_col;
+        if row >= 0
+          && row < MAX_MIB_SIZE as isize
+          && col >= 0
+          && col < MAX_MIB_SIZE as isize
+        {
+          self.add_tpl_ref_mv(fi, stack, bo, delta_row, delta_col);
+        }
+      }
+    }
+    zeromv_ctx
+  }
+
+  // Add the projection of the motion field at (delta_row, delta_col) from
+  // the block onto its references, and return it. The motion field is
+  // sampled at odd rows and columns.
+  fn add_tpl_ref_mv(
+    &self, fi: &FrameInvariants, stack: &mut RefMVStack, bo: &BlockOffset,
+    delta_row: isize, delta_col: isize
+  ) -> Option<[MotionVector; 2]> {
+    let row = (bo.y | 1) as isize + delta_row;
+    let col = (bo.x | 1) as isize + delta_col;
+    if !self.is_inside(row, col) {
+      return None;
+    }
+    let tpl = fi.motion_field[row as usize >> 1][col as usize >> 1]?;
+
+    let mut mvs = [MotionVector::default(); 2];
+    let num_refs = 1 + stack.is_compound() as usize;
+    let ref_frames = stack.ref_frames.iter().take(num_refs);
+    for (mv, &ref_frame) in mvs.iter_mut().zip(ref_frames) {
+      let to_ref =
+        relative_dist(fi.order_hint, fi.ref_frame_order_hint(ref_frame));
+      *mv = tpl
+        .mv
+        .projected(to_ref, tpl.ref_offset)
+        .lowered_precision(stack.allow_high_precision_mv);
+    }
+    stack.insert(mvs, 2);
+    Some(mvs)
+  }
+
+  // Complete a stack of fewer than two candidates with the motion vectors
+  // of the nearest neighbours, whatever their references
+  fn extra_search(
+    &self, fi: &FrameInvariants, stack: &mut RefMVStack, bo: &BlockOffset,
+    bsize: BlockSize
+  ) {
+    let mut ref_id_mvs = [[MotionVector::default(); 2]; 2];
+    let mut ref_id_count = [0; 2];
+    let mut ref_diff_mvs = [[MotionVector::default(); 2]; 2];
+    let mut ref_diff_count = [0; 2];
+
+    let w4 = cmp::min(cmp::min(16, bsize.width_mi()), self.cols - bo.x);
+    let h4 = cmp::min(cmp::min(16, bsize.height_mi()), self.rows - bo.y);
+    let num4x4 = cmp::min(w4, h4);
+
+    for pass in 0..2 {
+      let mut idx = 0;
+      while idx < num4x4 && stack.num_mv_found < 2 {
+        let (row, col) = if pass == 0 {
+          (bo.y as isize - 1, (bo.x + idx) as isize)
+        } else {
+          ((bo.y + idx) as isize, bo.x as isize - 1)
+        };
+        if !self.is_inside(row, col) {
+          break;
+        }
+        let b = &self.blocks[row as usize][col as usize];
+
+        for cand_list in 0..2 {
+          let cand_ref = b.ref_frames[cand_list];
+          if cand_ref == INTRA_FRAME || cand_ref == NONE_FRAME {
+            continue;
+          }
+          let cand_mv = b.mvs[cand_list];
+          // The motion vector is reversed for a reference on the other side
+          // of the frame in display order
+          let signed_mv = |ref_frame: usize| {
+            if fi.ref_frame_sign_bias(cand_ref)
+              == fi.ref_frame_sign_bias(ref_frame)
+            {
+              cand_mv
+            } else {
+              MotionVector { row: -cand_mv.row, col: -cand_mv.col }
+            }
+          };
+          if stack.is_compound() {
+            for list in 0..2 {
+              if cand_ref == stack.ref_frames[list] && ref_id_count[list] < 2
+              {
+                ref_id_mvs[list][ref_id_count[list]] = cand_mv;
+                ref_id_count[list] += 1;
+              } else if ref_diff_count[list] < 2 {
+                ref_diff_mvs[list][ref_diff_count[list]] =
+                  signed_mv(stack.ref_frames[list]);
+                ref_diff_count[list] += 1;
+              }
+            }
+          } else {
+            let mvs =
+              [signed_mv(stack.ref_frames[0]), MotionVector::default()];
+            let n = stack.num_mv_found;
+            if !stack.mvs[..n].contains(&mvs) {
+              stack.mvs[n] = mvs;
+              stack.weights[n] = 2;
+              stack.num_mv_found += 1;
+            }
+          }
+        }
+
+        idx += if pass == 0 {
+          b.bsize.width_mi()
+        } else {
+          b.bsize.height_mi()
+        };
+      }
+    }
+
+    // Missing candidates are the global motion vectors
+    if stack.is_compound() {
+      let mut combined_mvs = [stack.global_mvs; 2];
+      for list in 0..2 {
+        let cands = ref_id_mvs[list][..ref_id_count[list]]
+          .iter()
+          .chain(ref_diff_mvs[list][..ref_diff_count[list]].iter());
+        for (comp_mvs, &mv) in combined_mvs.iter_mut().zip(cands) {
+          comp_mvs[list] = mv;
+        }
+      }
+      if stack.num_mv_found == 1 {
+        stack.mvs[1] = if combined_mvs[0] == stack.mvs[0] {
+          combined_mvs[1]
+        } else {
+          combined_mvs[0]
+        };
+        stack.weights[1] = 2;
+        stack.num_mv_found = 2;
+      } else {
+        for (idx, &mvs) in combined_mvs.iter().enumerate() {
+          stack.mvs[idx] = mvs;
+          stack.weights[idx] = 2;
+        }
+        stack.num_mv_found = 2;
+      }
+    } else {
+      for idx in stack.num_mv_found..2 {
+        stack.mvs[idx][0] = stack.global_mvs[0];
+      }
+    }
+  }
+
+  // Keep a candidate within MV_BORDER of the frame edges
+  fn clamp_mv(
+    &self, bo: &BlockOffset, bsize: BlockSize, mv: MotionVector
+  ) -> MotionVector {
+    let mi_size = MI_SIZE as i32 * 8;
+    let (bw4, bh4) = (bsize.width_mi() as i32, bsize.height_mi() as i32);
+    let (mi_row, mi_col) = (bo.y as i32, bo.x as i32);
+    let to_top = -mi_row * mi_size;
+    let to_bottom = (self.rows as i32 - bh4 - mi_row) * mi_size;
+    let to_left = -mi_col * mi_size;
+    let to_right = (self.cols as i32 - bw4 - mi_col) * mi_size;
+    let border_row = MV_BORDER + bh4 * mi_size;
+    let border_col = MV_BORDER + bw4 * mi_size;
+    MotionVector {
+      row: clamp(mv.row as i32, to_top - border_row, to_bottom + border_row)
+        as i16,
+      col: clamp(mv.col as i32, to_left - border_col, to_right + border_col)
+        as i16
+    }
+  }
+
+  /// Candidate motion vectors of the block at bo predicted from
+  /// ref_frames, from a scan of the neighbouring blocks and, when
+  /// use_prev_frame_mvs, of the motion field projected from the references.
+  pub fn find_mv_stack(
+    &self, fi: &FrameInvariants, bo: &BlockOffset, bsize: BlockSize,
+    ref_frames: [usize; 2]
+  ) -> RefMVStack {
+    let mut stack = RefMVStack::new(fi, bo, bsize, ref_frames);
+    let (bw4, bh4) = (bsize.width_mi(), bsize.height_mi());
+
+    // Nearest row and column, and the top right block
+    self.scan_row(&mut stack, bo, bsize, -1);
+    let mut found_above_match = stack.take_match();
+    self.scan_col(&mut stack, bo, bsize, -1);
+    let mut found_left_match = stack.take_match();
+    if cmp::max(bw4, bh4) <= 16 && self.has_top_right(bo, bsize) {
+      self.scan_point(&mut stack, bo, -1, bw4 as isize);
+    }
+    found_above_match |= stack.take_match();
+
+    let close_matches = found_above_match as usize + found_left_match as usize;
+    let num_nearest = stack.num_mv_found;
+    let num_new = stack.new_mv_count;
+    for w in stack.weights[..num_nearest].iter_mut() {
+      *w += REF_CAT_LEVEL;
+    }
+
+    let zeromv_ctx = if fi.use_prev_frame_mvs {
+      self.scan_temporal(fi, &mut stack, bo, bsize)
+    } else {
+      0
+    };
+
+    // Top left block, and the outer rows and columns
+    self.scan_point(&mut stack, bo, -1, -1);
+    found_above_match |= stack.take_match();
+    self.scan_row(&mut stack, bo, bsize, -3);
+    found_above_match |= stack.take_match();
+    self.scan_col(&mut stack, bo, bsize, -3);
+    found_left_match |= stack.take_match();
+    if bh4 > 1 {
+      self.scan_row(&mut stack, bo, bsize, -5);
+    }
+    found_above_match |= stack.take_match();
+    if bw4 > 1 {
+      self.scan_col(&mut stack, bo, bsize, -5);
+    }
+    found_left_match |= stack.take_match();
+    let total_matches = found_above_match as usize + found_left_match as usize;
+
+    let num_mv_found = stack.num_mv_found;
+    stack.sort(0, num_nearest);
+    stack.sort(num_nearest, num_mv_found);
+
+    if stack.num_mv_found < 2 {
+      self.extra_search(fi, &mut stack, bo, bsize);
+    }
+
+    let (newmv_ctx, refmv_ctx) = match close_matches {
+      0 => (cmp::min(total_matches, 1), total_matches),
+      1 => (3 - cmp::min(num_new, 1), 2 + total_matches),
+      _ => (5 - cmp::min(num_new, 1), 5)
+    };
+    stack.mode_context = newmv_ctx
+      | zeromv_ctx << GLOBALMV_OFFSET
+      | refmv_ctx << REFMV_OFFSET;
+
+    for idx in 0..stack.num_mv_found {
+      for list in 0..2 {
+        stack.mvs[idx][list] = self.clamp_mv(bo, bsize, stack.mvs[idx][list]);
+      }
+    }
+    stack
+  }
+
   pub fn get_txb_ctx(
     &mut self, plane_bsize: BlockSize, tx_size: TxSize, plane: usize,
     bo: &BlockOffset, xdec: usize, ydec: usize
@@ -1672,14 +2984,16 @@ impl ContextWriter {
     out: &mut [u16; 2], cdf_in: &[u16], _bsize: BlockSize
   ) {
     out[0] = 32768;
-    out[0] -= ContextWriter::cdf_element_prob(
-      cdf_in,
-      PartitionType::PARTITION_HORZ as usize
-    );
-    out[0] -= ContextWriter::cdf_element_prob(
-      cdf_in,
-      PartitionType::PARTITION_SPLIT as usize
-    );
+    for &p in &[
+      PartitionType::PARTITION_HORZ,
+      PartitionType::PARTITION_SPLIT,
+      PartitionType::PARTITION_HORZ_A,
+      PartitionType::PARTITION_HORZ_B,
+      PartitionType::PARTITION_VERT_A,
+      PartitionType::PARTITION_HORZ_4
+    ] {
+      out[0] -= ContextWriter::cdf_element_prob(cdf_in, p as usize);
+    }
     out[0] = 32768 - out[0];
     out[1] = 0;
   }
@@ -1688,14 +3002,16 @@ impl ContextWriter {
     out: &mut [u16; 2], cdf_in: &[u16], _bsize: BlockSize
   ) {
     out[0] = 32768;
-    out[0] -= ContextWriter::cdf_element_prob(
-      cdf_in,
-      PartitionType::PARTITION_VERT as usize
-    );
-    out[0] -= ContextWriter::cdf_element_prob(
-      cdf_in,
-      PartitionType::PARTITION_SPLIT as usize
-    );
+    for &p in &[
+      PartitionType::PARTITION_VERT,
+      PartitionType::PARTITION_SPLIT,
+      PartitionType::PARTITION_HORZ_A,
+      PartitionType::PARTITION_VERT_A,
+      PartitionType::PARTITION_VERT_B,
+      PartitionType::PARTITION_VERT_4
+    ] {
+      out[0] -= ContextWriter::cdf_element_prob(cdf_in, p as usize);
+    }
     out[0] = 32768 - out[0];
     out[1] = 0;
   }
@@ -1715,7 +3031,13 @@ impl ContextWriter {
     }
 
     if has_rows && has_cols {
-      symbol!(self, p as u32, partition_cdf, PARTITION_TYPES);
+      // 8x8 blocks cannot use the extended partition types
+      let nsymbs = if bsize == BlockSize::BLOCK_8X8 {
+        PARTITION_TYPES
+      } else {
+        EXT_PARTITION_TYPES
+      };
+      symbol!(self, p as u32, partition_cdf, nsymbs);
     } else if !has_rows && has_cols {
       let mut cdf = [0u16; 2];
       ContextWriter::partition_gather_vert_alike(
@@ -1818,9 +3140,187 @@ impl ContextWriter {
     let ctx = self.bc.intra_inter_context(bo);
     symbol!(self, is_inter as u32, &mut self.fc.intra_inter_cdfs[ctx], 2);
   }
+  fn write_single_ref(&mut self, bo: &BlockOffset, p: usize, b: bool) {
+    let ctx = self.bc.single_ref_ctx(bo, p);
+    symbol!(self, b as u32, &mut self.fc.single_ref_cdfs[ctx][p - 1], 2);
+  }
+  fn write_comp_ref(&mut self, bo: &BlockOffset, p: usize, b: bool) {
+    let ctx = self.bc.single_ref_ctx(bo, p);
+    let cdf = match p {
+      3 => &mut self.fc.comp_ref_cdfs[ctx][0],
+      4 => &mut self.fc.comp_ref_cdfs[ctx][1],
+      5 => &mut self.fc.comp_ref_cdfs[ctx][2],
+      2 => &mut self.fc.comp_bwdref_cdfs[ctx][0],
+      6 => &mut self.fc.comp_bwdref_cdfs[ctx][1],
+      _ => unreachable!()
+    };
+    symbol!(self, b as u32, cdf, 2);
+  }
+  /// Write the references of an inter block, a past and a future one for
+  /// compound prediction. comp_mode is only coded with reference_select.
+  pub fn write_ref_frames(
+    &mut self, bo: &BlockOffset, bsize: BlockSize, reference_select: bool,
+    ref_frames: [usize; 2]
+  ) {
+    let compound = ref_frames[1] != NONE_FRAME;
+    if reference_select && bsize.width_mi() >= 2 && bsize.height_mi() >= 2 {
+      let ctx = self.bc.comp_inter_ctx(bo);
+      symbol!(self, compound as u32, &mut self.fc.comp_inter_cdfs[ctx], 2);
+    } else {
+      assert!(!compound);
+    }
+
+    if compound {
+      // Only bidirectional pairs are used
+      assert!(ref_frames[0] < BWDREF_FRAME && ref_frames[1] >= BWDREF_FRAME);
+      let ctx = self.bc.comp_ref_type_ctx(bo);
+      symbol!(self, 1, &mut self.fc.comp_ref_type_cdfs[ctx], 2);
+
+      let (fwd, bwd) = (ref_frames[0], ref_frames[1]);
+      let b = fwd == LAST3_FRAME || fwd == GOLDEN_FRAME;
+      self.write_comp_ref(bo, 3, b);
+      if b {
+        self.write_comp_ref(bo, 5, fwd == GOLDEN_FRAME);
+      } else {
+        self.write_comp_ref(bo, 4, fwd == LAST2_FRAME);
+      }
+      self.write_comp_ref(bo, 2, bwd == ALTREF_FRAME);
+      if bwd != ALTREF_FRAME {
+        self.write_comp_ref(bo, 6, bwd == ALTREF2_FRAME);
+      }
+      return;
+    }
+
+    let ref_frame = ref_frames[0];
+    let b1 = ref_frame >= BWDREF_FRAME;
+    self.write_single_ref(bo, 1, b1);
+    if b1 {
+      self.write_single_ref(bo, 2, ref_frame == ALTREF_FRAME);
+      if ref_frame != ALTREF_FRAME {
+        self.write_single_ref(bo, 6, ref_frame == ALTREF2_FRAME);
+      }
+    } else {
+      let b3 = ref_frame == LAST3_FRAME || ref_frame == GOLDEN_FRAME;
+      self.write_single_ref(bo, 3, b3);
+      if b3 {
+        self.write_single_ref(bo, 5, ref_frame == GOLDEN_FRAME);
+      } else {
+        self.write_single_ref(bo, 4, ref_frame == LAST2_FRAME);
+      }
+    }
+  }
+  pub fn write_inter_mode(&mut self, mode: PredictionMode, ctx: usize) {
+    let newmv_ctx = ctx & NEWMV_CTX_MASK;
+    let cdf = &mut self.fc.newmv_cdf[newmv_ctx];
+    symbol!(self, (mode != NEWMV) as u32, cdf, 2);
+    if mode != NEWMV {
+      let zeromv_ctx = (ctx >> GLOBALMV_OFFSET) & GLOBALMV_CTX_MASK;
+      let cdf = &mut self.fc.zeromv_cdf[zeromv_ctx];
+      symbol!(self, (mode != ZEROMV) as u32, cdf, 2);
+      if mode != ZEROMV {
+        let refmv_ctx = (ctx >> REFMV_OFFSET) & REFMV_CTX_MASK;
+        let cdf = &mut self.fc.refmv_cdf[refmv_ctx];
+        symbol!(self, (mode != NEARESTMV) as u32, cdf, 2);
+      }
+    }
+  }
+  pub fn write_compound_mode(&mut self, mode: PredictionMode, ctx: usize) {
+    const compound_mode_ctx_map: [[usize; COMP_NEWMV_CTXS]; 3] =
+      [[0, 1, 1, 1, 1], [1, 2, 3, 4, 4], [4, 4, 5, 6, 7]];
+    let newmv_ctx = ctx & NEWMV_CTX_MASK;
+    let refmv_ctx = (ctx >> REFMV_OFFSET) & REFMV_CTX_MASK;
+    let ctx = compound_mode_ctx_map[refmv_ctx >> 1]
+      [cmp::min(newmv_ctx, COMP_NEWMV_CTXS - 1)];
+    symbol!(
+      self,
+      mode as u32 - NEAREST_NEARESTMV as u32,
+      &mut self.fc.compound_mode_cdf[ctx],
+      INTER_COMPOUND_MODES
+    );
+  }
+  /// Write the index of the candidate of mv_stack used by mode, a NEWMV
+  /// mode or a mode using NEARMV.
+  pub fn write_drl_mode(
+    &mut self, mode: PredictionMode, ref_mv_idx: usize, mv_stack: &RefMVStack
+  ) {
+    // NEARMV candidates start from the second one
+    let start = mode.has_nearmv() as usize;
+    assert!(ref_mv_idx >= start);
+    for idx in start..start + 2 {
+      if mv_stack.num_mv_found > idx + 1 {
+        let drl_mode = ref_mv_idx > idx;
+        let ctx = mv_stack.drl_ctx(idx);
+        symbol!(self, drl_mode as u32, &mut self.fc.drl_cdfs[ctx], 2);
+        if !drl_mode {
+          break;
+        }
+      }
+    }
+  }
+  /// Write the difference between mv and its predictor ref_mv. Without
+  /// allow_high_precision_mv both must be in 1/4 sample units.
+  pub fn write_mv(
+    &mut self, mv: &MotionVector, ref_mv: &MotionVector,
+    allow_high_precision_mv: bool
+  ) {
+    let diff =
+      MotionVector { row: mv.row - ref_mv.row, col: mv.col - ref_mv.col };
+    let joint = ((diff.row != 0) as u32) << 1 | (diff.col != 0) as u32;
+    symbol!(self, joint, &mut self.fc.nmv_context.joints_cdf, MV_JOINTS);
+    if diff.row != 0 {
+      self.write_mv_component(diff.row, 0, allow_high_precision_mv);
+    }
+    if diff.col != 0 {
+      self.write_mv_component(diff.col, 1, allow_high_precision_mv);
+    }
+  }
+  fn write_mv_component(&mut self, v: i16, comp: usize, allow_hp: bool) {
+    assert!(v != 0);
+    if !allow_hp {
+      assert!(v & 1 == 0);
+    }
+    let z = v.abs() as u32 - 1;
+    let mv_class = if z >> 3 < 2 {
+      0
+    } else {
+      cmp::min(31 - (z >> 3).leading_zeros(), MV_CLASSES as u32 - 1)
+    };
+    let offset = if mv_class == 0 {
+      z
+    } else {
+      z - ((CLASS0_SIZE as u32) << (mv_class + 2))
+    };
+    let d = offset >> 3;
+    let fr = (offset >> 1) & 3;
+    let hp = offset & 1;
+
+    let mvcomp = &mut self.fc.nmv_context.comps[comp];
+    symbol!(self, (v < 0) as u32, &mut mvcomp.sign_cdf, 2);
+    symbol!(self, mv_class, &mut mvcomp.classes_cdf, MV_CLASSES);
+    if mv_class == 0 {
+      symbol!(self, d, &mut mvcomp.class0_cdf, CLASS0_SIZE);
+      symbol!(
+        self,
+        fr,
+        &mut mvcomp.class0_fp_cdf[d as usize],
+        MV_FP_SIZE
+      );
+      if allow_hp {
+        symbol!(self, hp, &mut mvcomp.class0_hp_cdf, 2);
+      }
+    } else {
+      for i in 0..mv_class {
+        symbol!(self, (d >> i) & 1, &mut mvcomp.bits_cdf[i as usize], 2);
+      }
+      symbol!(self, fr, &mut mvcomp.fp_cdf, MV_FP_SIZE);
+      if allow_hp {
+        symbol!(self, hp, &mut mvcomp.hp_cdf, 2);
+      }
+    }
+  }
 
   pub fn get_txsize_entropy_ctx(&mut self, tx_size: TxSize) -> usize {
-    (tx_size.sqr() as usize + tx_size.sqr() as usize + 1) >> 1
+    (tx_size.sqr() as usize + tx_size.sqr_up() as usize + 1) >> 1
   }
 
   pub fn txb_init_levels(
@@ -1971,7 +3471,7 @@ impl ContextWriter {
   }
 
   pub fn get_nz_map_contexts(
-    &mut self, levels: &mut [u8], scan: &[u16; 4096], eob: u16,
+    &mut self, levels: &mut [u8], scan: &[u16], eob: u16,
     tx_size: TxSize, tx_class: TxClass, coeff_contexts: &mut [i8]
   ) {
     // TODO: If TX_64X64 is enabled, use av1_get_adjusted_tx_size()
@@ -2064,10 +3564,8 @@ impl ContextWriter {
   ) {
     let pred_mode = self.bc.get_mode(bo);
     let is_inter = pred_mode >= PredictionMode::NEARESTMV;
-    assert!(!is_inter);
-    // TODO: If iner mode, scan_order should use inter version of them
     let scan_order =
-      &av1_inter_scan_orders[tx_size as usize][tx_type as usize];
+      &av1_scan_orders[tx_size as usize][tx_type as usize];
     let scan = scan_order.scan;
     let mut coeffs_storage = [0 as i32; 32 * 32];
     let coeffs = &mut coeffs_storage[..tx_size.area()];
@@ -2210,7 +3708,8 @@ impl ContextWriter {
       symbol!(
         self,
         bit,
-        &mut self.fc.eob_extra_cdf[txs_ctx][plane_type][eob_pt as usize],
+        &mut self.fc.eob_extra_cdf[txs_ctx][plane_type]
+          [(eob_pt - 3) as usize],
         2
       );
       for i in 1..eob_offset_bits {
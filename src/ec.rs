@@ -166,17 +166,11 @@ impl od_ec_enc {
     // We output the minimum number of bits that ensures that the symbols encoded
     // thus far will be decoded correctly regardless of the bits that follow.
     let l = self.low;
-    let r = self.rng as u32;
     let mut c = self.cnt;
-    let mut s = 9;
-    let mut m = 0x7FFF;
-    let mut e = (l + m) & !m;
+    let mut s = 10;
+    let m = 0x3FFF;
+    let mut e = ((l + m) & !m) | (m + 1);
 
-    while (e | m) >= l + r {
-      s += 1;
-      m >>= 1;
-      e = (l + m) & !m;
-    }
     s += c;
 
     if s > 0 {
@@ -488,68 +482,189 @@ pub struct WriterCheckpoint {
   cnt: i16
 }
 
-#[repr(C)]
-#[derive(Debug, Copy, Clone)]
-pub struct od_ec_dec {
-  pub buf: *const ::std::os::raw::c_uchar,
-  pub eptr: *const ::std::os::raw::c_uchar,
-  pub end_window: od_ec_window,
-  pub nend_bits: ::std::os::raw::c_int,
-  pub tell_offs: i32,
-  pub end: *const ::std::os::raw::c_uchar,
-  pub bptr: *const ::std::os::raw::c_uchar,
-  pub dif: od_ec_window,
-  pub rng: u16,
-  pub cnt: i16,
-  pub error: ::std::os::raw::c_int
+pub struct Reader<'a> {
+  dec: od_ec_dec<'a>
 }
 
-#[cfg(test)]
-mod test {
-  use super::*;
-  use std::mem;
+/// The number of bits counted as read once the input has run out, so that
+/// `cnt` stays positive however many symbols are decoded past the end.
+const OD_EC_LOTS_OF_BITS: i16 = 0x4000;
+const OD_EC_WINDOW_SIZE: i32 = 32;
 
-  struct Reader<'a> {
-    dec: od_ec_dec,
-    _dummy: &'a [u8]
-  }
+#[derive(Debug)]
+pub struct od_ec_dec<'a> {
+  /// The input bytes.
+  buf: &'a [u8],
+  /// The position of the next byte to read into `dif`.
+  bptr: usize,
+  /// The difference between the high end of the current range and the coded
+  /// value, minus 1, with the unread bits set to 1.
+  dif: od_ec_window,
+  /// The number of values in the current range.
+  rng: u16,
+  /// The number of bits of data in `dif` beyond the top 16.
+  cnt: i16,
+  /// The offset used to keep `od_ec_dec_tell` in step with the encoder.
+  tell_offs: i32
+}
 
-  extern {
-    fn od_ec_dec_init(
-      dec: *mut od_ec_dec, buf: *const ::std::os::raw::c_uchar, storage: u32
-    );
-    fn od_ec_decode_bool_q15(
-      dec: *mut od_ec_dec, f: ::std::os::raw::c_uint
-    ) -> ::std::os::raw::c_int;
-    fn od_ec_decode_cdf_q15(
-      dec: *mut od_ec_dec, cdf: *const u16, nsyms: ::std::os::raw::c_int
-    ) -> ::std::os::raw::c_int;
-  }
-
-  impl<'a> Reader<'a> {
-    fn new(buf: &'a [u8]) -> Self {
-      let mut r = Reader {
-        dec: unsafe { mem::uninitialized() },
-        _dummy: buf
-      };
+impl<'a> od_ec_dec<'a> {
+  fn new(buf: &'a [u8]) -> od_ec_dec<'a> {
+    let mut dec = od_ec_dec {
+      buf,
+      bptr: 0,
+      tell_offs: 10 - (OD_EC_WINDOW_SIZE - 8),
+      dif: (1 << (OD_EC_WINDOW_SIZE - 1)) - 1,
+      rng: 0x8000,
+      cnt: -15
+    };
+    dec.od_ec_dec_refill();
+    dec
+  }
+
+  /// Reads as many whole bytes as fit into `dif`. Past the end of the input,
+  /// 1 bits are shifted in instead.
+  fn od_ec_dec_refill(&mut self) {
+    let mut s = OD_EC_WINDOW_SIZE - 9 - (self.cnt as i32 + 15);
+    while s >= 0 && self.bptr < self.buf.len() {
+      self.dif ^= (self.buf[self.bptr] as od_ec_window) << s;
+      self.bptr += 1;
+      self.cnt += 8;
+      s -= 8;
+    }
+    if self.bptr >= self.buf.len() {
+      self.tell_offs += (OD_EC_LOTS_OF_BITS - self.cnt) as i32;
+      self.cnt = OD_EC_LOTS_OF_BITS;
+    }
+  }
 
-      unsafe { od_ec_dec_init(&mut r.dec, buf.as_ptr(), buf.len() as u32) };
+  /// Takes updated `dif` and range values, renormalizes them so that
+  /// 32768 <= `rng` < 65536 (reading more bytes if necessary), and stores
+  /// them back in the decoder context.
+  fn od_ec_dec_normalize(&mut self, dif: od_ec_window, rng: u32) {
+    let d = rng.leading_zeros() - 16;
+    self.cnt -= d as i16;
+    // This is equivalent to shifting in 1's instead of 0's.
+    self.dif = ((dif + 1) << d) - 1;
+    self.rng = (rng << d) as u16;
+    if self.cnt < 0 {
+      self.od_ec_dec_refill();
+    }
+  }
 
-      r
+  /// Decodes a single binary value.
+  /// `f`: The probability that the value is one, scaled by 32768, as passed
+  ///      to `od_ec_encode_bool_q15`.
+  fn od_ec_decode_bool_q15(&mut self, f: u16) -> bool {
+    let dif = self.dif;
+    let r = self.rng as u32;
+    // The range is renormalized after every symbol, whatever the input
+    debug_assert!(32768 <= r);
+    let v = (((r >> 8) * (f as u32 >> EC_PROB_SHIFT)) >> (7 - EC_PROB_SHIFT))
+      + EC_MIN_PROB;
+    let vw = v << (OD_EC_WINDOW_SIZE - 16);
+    if dif >= vw {
+      self.od_ec_dec_normalize(dif - vw, r - v);
+      false
+    } else {
+      self.od_ec_dec_normalize(dif, v);
+      true
     }
+  }
 
-    fn bool(&mut self, f: u32) -> bool {
-      unsafe { od_ec_decode_bool_q15(&mut self.dec, f) != 0 }
+  /// Decodes a symbol given an inverse CDF table in Q15, in the same form
+  /// as for `od_ec_encode_cdf_q15`. The last entry is taken to be 0, so the
+  /// search always ends within the table.
+  /// Return: The decoded symbol.
+  fn od_ec_decode_cdf_q15(&mut self, icdf: &[u16]) -> usize {
+    let dif = self.dif;
+    let r = self.rng as u32;
+    let n = icdf.len() - 1;
+    let c = dif >> (OD_EC_WINDOW_SIZE - 16);
+    let mut u;
+    let mut v = r;
+    let mut s = 0;
+    loop {
+      u = v;
+      v = if s < n {
+        (((r >> 8) * (icdf[s] as u32 >> EC_PROB_SHIFT))
+          >> (7 - EC_PROB_SHIFT))
+          + EC_MIN_PROB * (n - s) as u32
+      } else {
+        0
+      };
+      if c >= v {
+        break;
+      }
+      s += 1;
     }
+    self.od_ec_dec_normalize(dif - (v << (OD_EC_WINDOW_SIZE - 16)), u - v);
+    s
+  }
+
+  /// Returns the number of bits "used" by the decoded symbols so far, which
+  /// matches `od_ec_enc_tell` after encoding the same symbols.
+  fn od_ec_dec_tell(&self) -> u32 {
+    ((self.bptr * 8) as i32 - self.cnt as i32 + self.tell_offs) as u32
+  }
+
+  /// Returns the number of bits "used" by the decoded symbols so far, scaled
+  /// by `2**OD_BITRES`.
+  fn od_ec_dec_tell_frac(&self) -> u32 {
+    od_ec_enc::od_ec_tell_frac(self.od_ec_dec_tell(), self.rng as u32)
+  }
+}
+
+impl<'a> Reader<'a> {
+  pub fn new(buf: &'a [u8]) -> Reader<'a> {
+    Reader { dec: od_ec_dec::new(buf) }
+  }
+  pub fn cdf(&mut self, cdf: &[u16]) -> u32 {
+    self.dec.od_ec_decode_cdf_q15(cdf) as u32
+  }
+  pub fn bool(&mut self, f: u16) -> bool {
+    self.dec.od_ec_decode_bool_q15(f)
+  }
+  /// Decodes a symbol written by `Writer::symbol`, adapting `cdf` the same
+  /// way.
+  pub fn symbol(&mut self, cdf: &mut [u16], nsymbs: usize) -> u32 {
+    let s = self.cdf(&cdf[..nsymbs]);
+    Writer::update_cdf(cdf, s, nsymbs);
+    s
+  }
+  pub fn bit(&mut self) -> u16 {
+    self.bool(16384) as u16
+  }
 
-    fn cdf(&mut self, icdf: &[u16]) -> i32 {
-      let nsyms = icdf.len();
-      unsafe {
-        od_ec_decode_cdf_q15(&mut self.dec, icdf.as_ptr(), nsyms as i32)
+  /// Decodes a value written by `Writer::write_golomb`. A prefix too long
+  /// for any such value, as found in a corrupt input, saturates to
+  /// `u16::max_value()`.
+  pub fn golomb(&mut self) -> u16 {
+    let mut length = 1;
+    while self.bit() == 0 {
+      length += 1;
+      if length > 16 {
+        return u16::max_value();
       }
     }
+
+    let mut x = 1;
+    for _ in 1..length {
+      x = (x << 1) | self.bit();
+    }
+    x - 1
   }
 
+  pub fn tell_frac(&self) -> u32 {
+    self.dec.od_ec_dec_tell_frac()
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use rand::{ChaChaRng, Rng, SeedableRng};
+
   #[test]
   fn booleans() {
     let mut w = Writer::new();
@@ -644,4 +759,80 @@ mod test {
     assert_eq!(r.cdf(&cdf), 2);
     assert_eq!(r.cdf(&cdf), 2);
   }
+
+  #[derive(Debug, PartialEq)]
+  enum Op {
+    Symbol(u32),
+    Bit(u16),
+    Golomb(u16)
+  }
+
+  #[test]
+  fn roundtrip() {
+    let mut ra = ChaChaRng::from_seed([0; 32]);
+    let cdf = [24576, 16384, 8192, 0, 0];
+
+    let mut w = Writer::new();
+    let mut wcdf = cdf;
+    let mut ops = Vec::new();
+    for _ in 0..4096 {
+      let op = match ra.gen_range(0, 8) {
+        0 => Op::Bit(ra.gen_range(0, 2)),
+        1 => Op::Golomb(ra.gen_range(0, 300)),
+        // Skew the symbols so that the CDF adapts away from its start
+        _ => Op::Symbol(ra.gen_range(0, 4).min(ra.gen_range(0, 4)))
+      };
+      match op {
+        Op::Symbol(s) => w.symbol(s, &mut wcdf, 4),
+        Op::Bit(b) => w.bit(b),
+        Op::Golomb(g) => w.write_golomb(g)
+      }
+      ops.push((op, w.tell_frac()));
+    }
+
+    let b = w.done();
+
+    let mut r = Reader::new(&b);
+    let mut rcdf = cdf;
+    for (op, tell) in ops {
+      let read = match op {
+        Op::Symbol(_) => Op::Symbol(r.symbol(&mut rcdf, 4)),
+        Op::Bit(_) => Op::Bit(r.bit()),
+        Op::Golomb(_) => Op::Golomb(r.golomb())
+      };
+      assert_eq!(read, op);
+      assert_eq!(r.tell_frac(), tell);
+    }
+    assert_eq!(rcdf, wcdf);
+    assert!(rcdf != cdf);
+  }
+
+  #[test]
+  fn garbage() {
+    let mut ra = ChaChaRng::from_seed([0; 32]);
+    let random: Vec<u8> = (0..256).map(|_| ra.gen()).collect();
+    for buf in [&[0u8; 256][..], &[0xff; 256][..], &random[..]].iter() {
+      let mut r = Reader::new(buf);
+      let mut cdf = [24576, 16384, 8192, 0, 0];
+      // Keep decoding past the end of the input, where it is padded with
+      // 1 bits
+      for _ in 0..4096 {
+        match ra.gen_range(0, 4) {
+          0 => {
+            r.symbol(&mut cdf, 4);
+          }
+          1 => {
+            r.bool(ra.gen_range(1, 32768));
+          }
+          2 => {
+            r.golomb();
+          }
+          _ => {
+            r.cdf(&[32767, 1, 0]);
+          }
+        }
+      }
+      r.tell_frac();
+    }
+  }
 }
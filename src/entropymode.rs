@@ -0,0 +1,605 @@
+// Copyright (c) 2017-2018, The rav1e contributors. All rights reserved
+//
+// This source code is subject to the terms of the BSD 2 Clause License and
+// the Alliance for Open Media Patent License 1.0. If the BSD 2 Clause License
+// was not distributed with this source code in the LICENSE file, you can
+// obtain it at www.aomedia.org/license/software. If the Alliance for Open
+// Media Patent License 1.0 was not distributed with this source code in the
+// PATENTS file, you can obtain it at www.aomedia.org/license/patent.
+
+//! Default CDFs for the mode info syntax elements, from the AV1
+//! specification. Smaller alphabets stored in a wider array are padded with
+//! 32768.
+
+#![allow(non_upper_case_globals)]
+
+use context::*;
+use partition::*;
+
+macro_rules! cdf {
+  ($($x:expr),*) => {
+    [$(32768 - $x,)* 0, 0]
+  };
+}
+
+pub static default_partition_cdf:
+  [[u16; EXT_PARTITION_TYPES + 1]; PARTITION_CONTEXTS] = [
+  cdf!(19132, 25510, 30392, 32768, 32768, 32768, 32768, 32768, 32768),
+  cdf!(13928, 19855, 28540, 32768, 32768, 32768, 32768, 32768, 32768),
+  cdf!(12522, 23679, 28629, 32768, 32768, 32768, 32768, 32768, 32768),
+  cdf!(9896, 18783, 25853, 32768, 32768, 32768, 32768, 32768, 32768),
+  cdf!(15597, 20929, 24571, 26706, 27664, 28821, 29601, 30571, 31902),
+  cdf!(7925, 11043, 16785, 22470, 23971, 25043, 26651, 28701, 29834),
+  cdf!(5414, 13269, 15111, 20488, 22360, 24500, 25537, 26336, 32117),
+  cdf!(2662, 6362, 8614, 20860, 23053, 24778, 26436, 27829, 31171),
+  cdf!(18462, 20920, 23124, 27647, 28227, 29049, 29519, 30178, 31544),
+  cdf!(7689, 9060, 12056, 24992, 25660, 26182, 26951, 28041, 29052),
+  cdf!(6015, 9009, 10062, 24544, 25409, 26545, 27071, 27526, 32047),
+  cdf!(1394, 2208, 2796, 28614, 29061, 29466, 29840, 30185, 31899),
+  cdf!(20137, 21547, 23078, 29566, 29837, 30261, 30524, 30892, 31724),
+  cdf!(6732, 7490, 9497, 27944, 28250, 28515, 28969, 29630, 30104),
+  cdf!(5945, 7663, 8348, 28683, 29117, 29749, 30064, 30298, 32238),
+  cdf!(870, 1212, 1487, 31198, 31394, 31574, 31743, 31881, 32332),
+  cdf!(27899, 28219, 28529, 32484, 32539, 32619, 32639, 32768, 32768),
+  cdf!(6607, 6990, 8268, 32060, 32219, 32338, 32371, 32768, 32768),
+  cdf!(5429, 6676, 7122, 32027, 32227, 32531, 32582, 32768, 32768),
+  cdf!(711, 966, 1172, 32448, 32538, 32617, 32664, 32768, 32768)
+];
+
+pub static default_kf_y_mode_cdf:
+  [[[u16; INTRA_MODES + 1]; KF_MODE_CONTEXTS]; KF_MODE_CONTEXTS] = [
+  [
+    cdf!(15588, 17027, 19338, 20218, 20682, 21110, 21825, 23244, 24189, 28165,
+      29093, 30466),
+    cdf!(12016, 18066, 19516, 20303, 20719, 21444, 21888, 23032, 24434, 28658,
+      30172, 31409),
+    cdf!(10052, 10771, 22296, 22788, 23055, 23239, 24133, 25620, 26160, 29336,
+      29929, 31567),
+    cdf!(14091, 15406, 16442, 18808, 19136, 19546, 19998, 22096, 24746, 29585,
+      30958, 32462),
+    cdf!(12122, 13265, 15603, 16501, 18609, 20033, 22391, 25583, 26437, 30261,
+      31073, 32475)
+  ],
+  [
+    cdf!(10023, 19585, 20848, 21440, 21832, 22760, 23089, 24023, 25381, 29014,
+      30482, 31436),
+    cdf!(5983, 24099, 24560, 24886, 25066, 25795, 25913, 26423, 27610, 29905,
+      31276, 31794),
+    cdf!(7444, 12781, 20177, 20728, 21077, 21607, 22170, 23405, 24469, 27915,
+      29090, 30492),
+    cdf!(8537, 14689, 15432, 17087, 17408, 18172, 18408, 19825, 24649, 29153,
+      31096, 32210),
+    cdf!(7543, 14231, 15496, 16195, 17905, 20717, 21984, 24516, 26001, 29675,
+      30981, 31994)
+  ],
+  [
+    cdf!(12613, 13591, 21383, 22004, 22312, 22577, 23401, 25055, 25729, 29538,
+      30305, 32077),
+    cdf!(9687, 13470, 18506, 19230, 19604, 20147, 20695, 22062, 23219, 27743,
+      29211, 30907),
+    cdf!(6183, 6505, 26024, 26252, 26366, 26434, 27082, 28354, 28555, 30467,
+      30794, 32086),
+    cdf!(10718, 11734, 14954, 17224, 17565, 17924, 18561, 21523, 23878, 28975,
+      30287, 32252),
+    cdf!(9194, 9858, 16501, 17263, 18424, 19171, 21563, 25961, 26561, 30072,
+      30737, 32463)
+  ],
+  [
+    cdf!(12602, 14399, 15488, 18381, 18778, 19315, 19724, 21419, 25060, 29696,
+      30917, 32409),
+    cdf!(8203, 13821, 14524, 17105, 17439, 18131, 18404, 19468, 25225, 29485,
+      31158, 32342),
+    cdf!(8451, 9731, 15004, 17643, 18012, 18425, 19070, 21538, 24605, 29118,
+      30078, 32018),
+    cdf!(7714, 9048, 9516, 16667, 16817, 16994, 17153, 18767, 26743, 30389,
+      31536, 32528),
+    cdf!(8843, 10280, 11496, 15317, 16652, 17943, 19108, 22718, 25769, 29953,
+      30983, 32485)
+  ],
+  [
+    cdf!(12578, 13671, 15979, 16834, 19075, 20913, 22989, 25449, 26219, 30214,
+      31150, 32477),
+    cdf!(9563, 13626, 15080, 15892, 17756, 20863, 22207, 24236, 25380, 29653,
+      31143, 32277),
+    cdf!(8356, 8901, 17616, 18256, 19350, 20106, 22598, 25947, 26466, 29900,
+      30523, 32261),
+    cdf!(10835, 11815, 13124, 16042, 17018, 18039, 18947, 22753, 24615, 29489,
+      30883, 32482),
+    cdf!(7618, 8288, 9859, 10509, 15386, 18657, 22903, 28776, 29180, 31355,
+      31802, 32593)
+  ]
+];
+
+pub static default_angle_delta_cdf:
+  [[u16; 2 * MAX_ANGLE_DELTA + 1 + 1]; DIRECTIONAL_MODES] = [
+  cdf!(2180, 5032, 7567, 22776, 26989, 30217),
+  cdf!(2301, 5608, 8801, 23487, 26974, 30330),
+  cdf!(3780, 11018, 13699, 19354, 23083, 31286),
+  cdf!(4581, 11226, 15147, 17138, 21834, 28397),
+  cdf!(1737, 10927, 14509, 19588, 22745, 28823),
+  cdf!(2664, 10176, 12485, 17650, 21600, 30495),
+  cdf!(2240, 11096, 15453, 20341, 22561, 28917),
+  cdf!(3605, 10428, 12459, 17676, 21244, 30655)
+];
+
+pub static default_if_y_mode_cdf:
+  [[u16; INTRA_MODES + 1]; BLOCK_SIZE_GROUPS] = [
+  cdf!(22801, 23489, 24293, 24756, 25601, 26123, 26606, 27418, 27945, 29228,
+    29685, 30349),
+  cdf!(18673, 19845, 22631, 23318, 23950, 24649, 25527, 27364, 28152, 29701,
+    29984, 30852),
+  cdf!(19770, 20979, 23396, 23939, 24241, 24654, 25136, 27073, 27830, 29360,
+    29730, 30659),
+  cdf!(20155, 21301, 22838, 23178, 23261, 23533, 23703, 24804, 25352, 26575,
+    27016, 28049)
+];
+
+pub static default_uv_mode_cdf:
+  [[[u16; UV_INTRA_MODES + 1]; INTRA_MODES]; 2] = [
+  [
+    cdf!(22631, 24152, 25378, 25661, 25986, 26520, 27055, 27923, 28244, 30059,
+      30941, 31961, 32768),
+    cdf!(9513, 26881, 26973, 27046, 27118, 27664, 27739, 27824, 28359, 29505,
+      29800, 31796, 32768),
+    cdf!(9845, 9915, 28663, 28704, 28757, 28780, 29198, 29822, 29854, 30764,
+      31777, 32029, 32768),
+    cdf!(13639, 13897, 14171, 25331, 25606, 25727, 25953, 27148, 28577, 30612,
+      31355, 32493, 32768),
+    cdf!(9764, 9835, 9930, 9954, 25386, 27053, 27958, 28148, 28243, 31101,
+      31744, 32363, 32768),
+    cdf!(11825, 13589, 13677, 13720, 15048, 29213, 29301, 29458, 29711, 31161,
+      31441, 32550, 32768),
+    cdf!(14175, 14399, 16608, 16821, 17718, 17775, 28551, 30200, 30245, 31837,
+      32342, 32667, 32768),
+    cdf!(12885, 13038, 14978, 15590, 15673, 15748, 16176, 29128, 29267, 30643,
+      31961, 32461, 32768),
+    cdf!(12026, 13661, 13874, 15305, 15490, 15726, 15995, 16273, 28443, 30388,
+      30767, 32416, 32768),
+    cdf!(19052, 19840, 20579, 20916, 21150, 21467, 21885, 22719, 23174, 28861,
+      30379, 32175, 32768),
+    cdf!(18627, 19649, 20974, 21219, 21492, 21816, 22199, 23119, 23527, 27053,
+      31397, 32148, 32768),
+    cdf!(17026, 19004, 19997, 20339, 20586, 21103, 21349, 21907, 22482, 25896,
+      26541, 31819, 32768),
+    cdf!(12124, 13759, 14959, 14992, 15007, 15051, 15078, 15166, 15255, 15753,
+      16039, 16606, 32768)
+  ],
+  [
+    cdf!(10407, 11208, 12900, 13181, 13823, 14175, 14899, 15656, 15986, 20086,
+      20995, 22455, 24212),
+    cdf!(4532, 19780, 20057, 20215, 20428, 21071, 21199, 21451, 22099, 24228,
+      24693, 27032, 29472),
+    cdf!(5273, 5379, 20177, 20270, 20385, 20439, 20949, 21695, 21774, 23138,
+      24256, 24703, 26679),
+    cdf!(6740, 7167, 7662, 14152, 14536, 14785, 15034, 16741, 18371, 21520,
+      22206, 23389, 24182),
+    cdf!(4987, 5368, 5928, 6068, 19114, 20315, 21857, 22253, 22411, 24911,
+      25380, 26027, 26376),
+    cdf!(5370, 6889, 7247, 7393, 9498, 21114, 21402, 21753, 21981, 24780,
+      25386, 26517, 27176),
+    cdf!(4816, 4961, 7204, 7326, 8765, 8930, 20169, 20682, 20803, 23188, 23763,
+      24455, 24940),
+    cdf!(6608, 6740, 8529, 9049, 9257, 9356, 9735, 18827, 19059, 22336, 23204,
+      23964, 24793),
+    cdf!(5998, 7419, 7781, 8933, 9255, 9549, 9753, 10417, 18898, 22494, 23139,
+      24764, 25989),
+    cdf!(10660, 11298, 12550, 12957, 13322, 13624, 14040, 15004, 15534, 20714,
+      21789, 23443, 24861),
+    cdf!(10522, 11530, 12552, 12963, 13378, 13779, 14245, 15235, 15902, 20102,
+      22696, 23774, 25838),
+    cdf!(10099, 10691, 12639, 13049, 13386, 13665, 14125, 15163, 15636, 19676,
+      20474, 23519, 25208),
+    cdf!(3144, 5087, 7382, 7504, 7593, 7690, 7801, 8064, 8232, 9248, 9875,
+      10521, 29048)
+  ]
+];
+
+pub static default_intra_ext_tx_cdf:
+  [[[[u16; TX_TYPES + 1]; INTRA_MODES]; EXT_TX_SIZES]; EXT_TX_SETS_INTRA] = [
+  [
+    [
+      [0; TX_TYPES + 1], [0; TX_TYPES + 1], [0; TX_TYPES + 1],
+      [0; TX_TYPES + 1], [0; TX_TYPES + 1], [0; TX_TYPES + 1],
+      [0; TX_TYPES + 1], [0; TX_TYPES + 1], [0; TX_TYPES + 1],
+      [0; TX_TYPES + 1], [0; TX_TYPES + 1], [0; TX_TYPES + 1],
+      [0; TX_TYPES + 1]
+    ],
+    [
+      [0; TX_TYPES + 1], [0; TX_TYPES + 1], [0; TX_TYPES + 1],
+      [0; TX_TYPES + 1], [0; TX_TYPES + 1], [0; TX_TYPES + 1],
+      [0; TX_TYPES + 1], [0; TX_TYPES + 1], [0; TX_TYPES + 1],
+      [0; TX_TYPES + 1], [0; TX_TYPES + 1], [0; TX_TYPES + 1],
+      [0; TX_TYPES + 1]
+    ],
+    [
+      [0; TX_TYPES + 1], [0; TX_TYPES + 1], [0; TX_TYPES + 1],
+      [0; TX_TYPES + 1], [0; TX_TYPES + 1], [0; TX_TYPES + 1],
+      [0; TX_TYPES + 1], [0; TX_TYPES + 1], [0; TX_TYPES + 1],
+      [0; TX_TYPES + 1], [0; TX_TYPES + 1], [0; TX_TYPES + 1],
+      [0; TX_TYPES + 1]
+    ],
+    [
+      [0; TX_TYPES + 1], [0; TX_TYPES + 1], [0; TX_TYPES + 1],
+      [0; TX_TYPES + 1], [0; TX_TYPES + 1], [0; TX_TYPES + 1],
+      [0; TX_TYPES + 1], [0; TX_TYPES + 1], [0; TX_TYPES + 1],
+      [0; TX_TYPES + 1], [0; TX_TYPES + 1], [0; TX_TYPES + 1],
+      [0; TX_TYPES + 1]
+    ]
+  ],
+  [
+    [
+      cdf!(1535, 8035, 9461, 12751, 23467, 27825, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(564, 3335, 9709, 10870, 18143, 28094, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(672, 3247, 3676, 11982, 19415, 23127, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(5279, 13885, 15487, 18044, 23527, 30252, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(4423, 6074, 7985, 10416, 25693, 29298, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(1486, 4241, 9460, 10662, 16456, 27694, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(439, 2838, 3522, 6737, 18058, 23754, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(1190, 4233, 4855, 11670, 20281, 24377, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(1045, 4312, 8647, 10159, 18644, 29335, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(202, 3734, 4747, 7298, 17127, 24016, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(447, 4312, 6819, 8884, 16010, 23858, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(277, 4369, 5255, 8905, 16465, 22271, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(3409, 5436, 10599, 15599, 19687, 24040, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768)
+    ],
+    [
+      cdf!(1870, 13742, 14530, 16498, 23770, 27698, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(326, 8796, 14632, 15079, 19272, 27486, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(484, 7576, 7712, 14443, 19159, 22591, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(1126, 15340, 15895, 17023, 20896, 30279, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(655, 4854, 5249, 5913, 22099, 27138, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(1299, 6458, 8885, 9290, 14851, 25497, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(311, 5295, 5552, 6885, 16107, 22672, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(883, 8059, 8270, 11258, 17289, 21549, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(741, 7580, 9318, 10345, 16688, 29046, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(110, 7406, 7915, 9195, 16041, 23329, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(363, 7974, 9357, 10673, 15629, 24474, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(153, 7647, 8112, 9936, 15307, 19996, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(3511, 6332, 11165, 15335, 19323, 23594, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768)
+    ],
+    [
+      cdf!(4681, 9362, 14043, 18725, 23406, 28087, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(4681, 9362, 14043, 18725, 23406, 28087, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(4681, 9362, 14043, 18725, 23406, 28087, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(4681, 9362, 14043, 18725, 23406, 28087, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(4681, 9362, 14043, 18725, 23406, 28087, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(4681, 9362, 14043, 18725, 23406, 28087, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(4681, 9362, 14043, 18725, 23406, 28087, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(4681, 9362, 14043, 18725, 23406, 28087, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(4681, 9362, 14043, 18725, 23406, 28087, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(4681, 9362, 14043, 18725, 23406, 28087, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(4681, 9362, 14043, 18725, 23406, 28087, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(4681, 9362, 14043, 18725, 23406, 28087, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(4681, 9362, 14043, 18725, 23406, 28087, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768)
+    ],
+    [
+      cdf!(4681, 9362, 14043, 18725, 23406, 28087, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(4681, 9362, 14043, 18725, 23406, 28087, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(4681, 9362, 14043, 18725, 23406, 28087, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(4681, 9362, 14043, 18725, 23406, 28087, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(4681, 9362, 14043, 18725, 23406, 28087, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(4681, 9362, 14043, 18725, 23406, 28087, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(4681, 9362, 14043, 18725, 23406, 28087, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(4681, 9362, 14043, 18725, 23406, 28087, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(4681, 9362, 14043, 18725, 23406, 28087, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(4681, 9362, 14043, 18725, 23406, 28087, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(4681, 9362, 14043, 18725, 23406, 28087, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(4681, 9362, 14043, 18725, 23406, 28087, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(4681, 9362, 14043, 18725, 23406, 28087, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768)
+    ]
+  ],
+  [
+    [
+      cdf!(6554, 13107, 19661, 26214, 32768, 32768, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(6554, 13107, 19661, 26214, 32768, 32768, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(6554, 13107, 19661, 26214, 32768, 32768, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(6554, 13107, 19661, 26214, 32768, 32768, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(6554, 13107, 19661, 26214, 32768, 32768, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(6554, 13107, 19661, 26214, 32768, 32768, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(6554, 13107, 19661, 26214, 32768, 32768, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(6554, 13107, 19661, 26214, 32768, 32768, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(6554, 13107, 19661, 26214, 32768, 32768, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(6554, 13107, 19661, 26214, 32768, 32768, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(6554, 13107, 19661, 26214, 32768, 32768, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(6554, 13107, 19661, 26214, 32768, 32768, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(6554, 13107, 19661, 26214, 32768, 32768, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768)
+    ],
+    [
+      cdf!(6554, 13107, 19661, 26214, 32768, 32768, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(6554, 13107, 19661, 26214, 32768, 32768, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(6554, 13107, 19661, 26214, 32768, 32768, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(6554, 13107, 19661, 26214, 32768, 32768, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(6554, 13107, 19661, 26214, 32768, 32768, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(6554, 13107, 19661, 26214, 32768, 32768, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(6554, 13107, 19661, 26214, 32768, 32768, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(6554, 13107, 19661, 26214, 32768, 32768, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(6554, 13107, 19661, 26214, 32768, 32768, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(6554, 13107, 19661, 26214, 32768, 32768, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(6554, 13107, 19661, 26214, 32768, 32768, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(6554, 13107, 19661, 26214, 32768, 32768, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(6554, 13107, 19661, 26214, 32768, 32768, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768)
+    ],
+    [
+      cdf!(1127, 12814, 22772, 27483, 32768, 32768, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(145, 6761, 11980, 26667, 32768, 32768, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(362, 5887, 11678, 16725, 32768, 32768, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(385, 15213, 18587, 30693, 32768, 32768, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(25, 2914, 23134, 27903, 32768, 32768, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(60, 4470, 11749, 23991, 32768, 32768, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(37, 3332, 14511, 21448, 32768, 32768, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(157, 6320, 13036, 17439, 32768, 32768, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(119, 6719, 12906, 29396, 32768, 32768, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(47, 5537, 12576, 21499, 32768, 32768, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(269, 6076, 11258, 23115, 32768, 32768, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(83, 5615, 12001, 17228, 32768, 32768, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(1968, 5556, 12023, 18547, 32768, 32768, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768)
+    ],
+    [
+      cdf!(6554, 13107, 19661, 26214, 32768, 32768, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(6554, 13107, 19661, 26214, 32768, 32768, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(6554, 13107, 19661, 26214, 32768, 32768, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(6554, 13107, 19661, 26214, 32768, 32768, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(6554, 13107, 19661, 26214, 32768, 32768, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(6554, 13107, 19661, 26214, 32768, 32768, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(6554, 13107, 19661, 26214, 32768, 32768, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(6554, 13107, 19661, 26214, 32768, 32768, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(6554, 13107, 19661, 26214, 32768, 32768, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(6554, 13107, 19661, 26214, 32768, 32768, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(6554, 13107, 19661, 26214, 32768, 32768, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(6554, 13107, 19661, 26214, 32768, 32768, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768),
+      cdf!(6554, 13107, 19661, 26214, 32768, 32768, 32768, 32768, 32768, 32768,
+        32768, 32768, 32768, 32768, 32768)
+    ]
+  ]
+];
+
+pub static default_inter_ext_tx_cdf:
+  [[[u16; TX_TYPES + 1]; EXT_TX_SIZES]; EXT_TX_SETS_INTER] = [
+  [
+    [0; TX_TYPES + 1], [0; TX_TYPES + 1], [0; TX_TYPES + 1], [0; TX_TYPES + 1]
+  ],
+  [
+    cdf!(4458, 5560, 7695, 9709, 13330, 14789, 17537, 20266, 21504, 22848,
+      23934, 25474, 27727, 28915, 30631),
+    cdf!(1645, 2573, 4778, 5711, 7807, 8622, 10522, 15357, 17674, 20408, 22517,
+      25010, 27116, 28856, 30749),
+    cdf!(2048, 4096, 6144, 8192, 10240, 12288, 14336, 16384, 18432, 20480,
+      22528, 24576, 26624, 28672, 30720),
+    cdf!(2048, 4096, 6144, 8192, 10240, 12288, 14336, 16384, 18432, 20480,
+      22528, 24576, 26624, 28672, 30720)
+  ],
+  [
+    cdf!(2731, 5461, 8192, 10923, 13653, 16384, 19115, 21845, 24576, 27307,
+      30037, 32768, 32768, 32768, 32768),
+    cdf!(2731, 5461, 8192, 10923, 13653, 16384, 19115, 21845, 24576, 27307,
+      30037, 32768, 32768, 32768, 32768),
+    cdf!(770, 2421, 5225, 12907, 15819, 18927, 21561, 24089, 26595, 28526,
+      30529, 32768, 32768, 32768, 32768),
+    cdf!(2731, 5461, 8192, 10923, 13653, 16384, 19115, 21845, 24576, 27307,
+      30037, 32768, 32768, 32768, 32768)
+  ],
+  [
+    cdf!(16384, 32768, 32768, 32768, 32768, 32768, 32768, 32768, 32768, 32768,
+      32768, 32768, 32768, 32768, 32768),
+    cdf!(4167, 32768, 32768, 32768, 32768, 32768, 32768, 32768, 32768, 32768,
+      32768, 32768, 32768, 32768, 32768),
+    cdf!(1998, 32768, 32768, 32768, 32768, 32768, 32768, 32768, 32768, 32768,
+      32768, 32768, 32768, 32768, 32768),
+    cdf!(748, 32768, 32768, 32768, 32768, 32768, 32768, 32768, 32768, 32768,
+      32768, 32768, 32768, 32768, 32768)
+  ]
+];
+
+pub static default_skip_cdfs: [[u16; 3]; SKIP_CONTEXTS] =
+  [cdf!(31671), cdf!(16515), cdf!(4576)];
+
+pub static default_intra_inter_cdf: [[u16; 3]; INTRA_INTER_CONTEXTS] =
+  [cdf!(806), cdf!(16662), cdf!(20186), cdf!(26538)];
+
+pub static default_newmv_cdf: [[u16; 3]; NEWMV_MODE_CONTEXTS] = [
+  cdf!(24035),
+  cdf!(16630),
+  cdf!(15339),
+  cdf!(8386),
+  cdf!(12222),
+  cdf!(4676)
+];
+
+pub static default_zeromv_cdf: [[u16; 3]; GLOBALMV_MODE_CONTEXTS] =
+  [cdf!(2175), cdf!(1054)];
+
+pub static default_refmv_cdf: [[u16; 3]; REFMV_MODE_CONTEXTS] = [
+  cdf!(23974),
+  cdf!(24188),
+  cdf!(17848),
+  cdf!(28622),
+  cdf!(24312),
+  cdf!(19923)
+];
+
+pub static default_single_ref_cdf: [[[u16; 3]; SINGLE_REFS - 1];
+  REF_CONTEXTS] = [
+  [cdf!(4897), cdf!(1555), cdf!(4236), cdf!(8650), cdf!(904), cdf!(1444)],
+  [
+    cdf!(16973),
+    cdf!(16751),
+    cdf!(19647),
+    cdf!(24773),
+    cdf!(11014),
+    cdf!(15087)
+  ],
+  [
+    cdf!(29744),
+    cdf!(30279),
+    cdf!(31194),
+    cdf!(31895),
+    cdf!(26875),
+    cdf!(30304)
+  ]
+];
+
+pub static default_comp_inter_cdf: [[u16; 3]; COMP_INTER_CONTEXTS] =
+  [cdf!(26828), cdf!(24035), cdf!(12031), cdf!(10640), cdf!(2901)];
+
+pub static default_comp_ref_type_cdf: [[u16; 3]; COMP_REF_TYPE_CONTEXTS] =
+  [cdf!(1198), cdf!(2070), cdf!(9166), cdf!(7499), cdf!(22475)];
+
+pub static default_comp_ref_cdf: [[[u16; 3]; FWD_REFS - 1]; REF_CONTEXTS] = [
+  [cdf!(4946), cdf!(9468), cdf!(1503)],
+  [cdf!(19891), cdf!(22441), cdf!(15160)],
+  [cdf!(30731), cdf!(31059), cdf!(27544)]
+];
+
+pub static default_comp_bwdref_cdf: [[[u16; 3]; BWD_REFS - 1]; REF_CONTEXTS] =
+  [
+    [cdf!(2235), cdf!(1423)],
+    [cdf!(17182), cdf!(15175)],
+    [cdf!(30606), cdf!(30489)]
+  ];
+
+pub static default_compound_mode_cdf: [[u16; INTER_COMPOUND_MODES + 1];
+  INTER_MODE_CONTEXTS] = [
+  cdf!(7760, 13823, 15808, 17641, 19156, 20666, 26891),
+  cdf!(10730, 19452, 21145, 22749, 24039, 25131, 28724),
+  cdf!(10664, 20221, 21588, 22906, 24295, 25387, 28436),
+  cdf!(13298, 16984, 20471, 24182, 25067, 25736, 26422),
+  cdf!(18904, 23325, 25242, 27432, 27898, 28258, 30758),
+  cdf!(10725, 17454, 20124, 22820, 24195, 25168, 26046),
+  cdf!(17125, 24273, 25814, 27492, 28214, 28704, 30592),
+  cdf!(13046, 23214, 24505, 25942, 27435, 28442, 29330)
+];
+
+pub static default_drl_cdf: [[u16; 3]; DRL_MODE_CONTEXTS] =
+  [cdf!(13104), cdf!(24560), cdf!(18945)];
+
+const default_nmv_component: NMVComponent = NMVComponent {
+  classes_cdf: cdf!(
+    28672, 30976, 31858, 32320, 32551, 32656, 32740, 32757, 32762, 32767
+  ),
+  class0_fp_cdf: [cdf!(16384, 24576, 26624), cdf!(12288, 21248, 24128)],
+  fp_cdf: cdf!(8192, 17408, 21248),
+  sign_cdf: cdf!(128 * 128),
+  class0_hp_cdf: cdf!(160 * 128),
+  hp_cdf: cdf!(128 * 128),
+  class0_cdf: cdf!(216 * 128),
+  bits_cdf: [
+    cdf!(128 * 136),
+    cdf!(128 * 140),
+    cdf!(128 * 148),
+    cdf!(128 * 160),
+    cdf!(128 * 176),
+    cdf!(128 * 192),
+    cdf!(128 * 224),
+    cdf!(128 * 234),
+    cdf!(128 * 234),
+    cdf!(128 * 240)
+  ]
+};
+
+pub static default_nmv_context: NMVContext = NMVContext {
+  joints_cdf: cdf!(4096, 11264, 19328),
+  comps: [default_nmv_component, default_nmv_component]
+};
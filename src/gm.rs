@@ -0,0 +1,682 @@
+// Copyright (c) 2018, The rav1e contributors. All rights reserved
+//
+// This source code is subject to the terms of the BSD 2 Clause License and
+// the Alliance for Open Media Patent License 1.0. If the BSD 2 Clause License
+// was not distributed with this source code in the LICENSE file, you can
+// obtain it at www.aomedia.org/license/software. If the Alliance for Open
+// Media Patent License 1.0 was not distributed with this source code in the
+// PATENTS file, you can obtain it at www.aomedia.org/license/patent.
+
+//! Global motion: estimation of the warp from a frame to a reference, and
+//! what the decoder derives from its parameters.
+
+#![cfg_attr(feature = "cargo-clippy", allow(cast_lossless))]
+
+use context::*;
+use partition::*;
+use plane::*;
+use std::cmp;
+use FrameInvariants;
+
+/// Fractional bits of the warp parameters
+pub const WARPEDMODEL_PREC_BITS: usize = 16;
+
+/// Parameters of the identity warp
+pub const DEFAULT_GM_PARAMS: [i32; 6] =
+  [0, 0, 1 << WARPEDMODEL_PREC_BITS, 0, 0, 1 << WARPEDMODEL_PREC_BITS];
+
+const WARP_PARAM_REDUCE_BITS: usize = 6;
+const DIV_LUT_BITS: usize = 8;
+const DIV_LUT_PREC_BITS: usize = 14;
+
+const GM_ABS_TRANS_ONLY_BITS: usize = 9;
+const GM_TRANS_ONLY_PREC_BITS: usize = 3;
+const GM_ABS_TRANS_BITS: usize = 12;
+const GM_TRANS_PREC_BITS: usize = 6;
+const GM_ABS_ALPHA_BITS: usize = 12;
+const GM_ALPHA_PREC_BITS: usize = 15;
+
+// Side of the square cells at most one corner is picked from
+const CORNER_CELL: usize = 16;
+// Most corners matched per frame
+const MAX_CORNERS: usize = 256;
+// Half side of the patches compared at full resolution
+const PATCH_RADIUS: isize = 6;
+// Search range of the matches, in full resolution samples
+const MATCH_RANGE: isize = 32;
+const RANSAC_ITERATIONS: usize = 256;
+// Distance in samples to its projection within which a match agrees with
+// a model
+const INLIER_THRESHOLD: f64 = 1.5;
+// Fewest matches agreeing with a model for it to be used
+const MIN_INLIERS: usize = 16;
+
+/// Parameters coded for a warp of type gm_type, in coding order. ROTZOOM
+/// derives the last two from the middle ones.
+pub fn gm_coded_params(gm_type: GlobalMVMode) -> &'static [usize] {
+  match gm_type {
+    GlobalMVMode::IDENTITY => &[],
+    GlobalMVMode::TRANSLATION => &[0, 1],
+    GlobalMVMode::ROTZOOM => &[2, 3, 0, 1],
+    GlobalMVMode::AFFINE => &[2, 3, 4, 5, 0, 1]
+  }
+}
+
+/// Range and precision in bits of parameter idx of a warp of type gm_type
+pub fn gm_param_bits(
+  gm_type: GlobalMVMode, idx: usize, allow_high_precision_mv: bool
+) -> (usize, usize) {
+  let hp = allow_high_precision_mv as usize;
+  if idx >= 2 {
+    (GM_ABS_ALPHA_BITS, GM_ALPHA_PREC_BITS)
+  } else if gm_type == GlobalMVMode::TRANSLATION {
+    (GM_ABS_TRANS_ONLY_BITS - 1 + hp, GM_TRANS_ONLY_PREC_BITS - 1 + hp)
+  } else {
+    (GM_ABS_TRANS_BITS, GM_TRANS_PREC_BITS)
+  }
+}
+
+/// Parameter idx of a warp of type gm_type as it is coded, the difference
+/// to the identity at the precision given by gm_param_bits
+pub fn gm_coded_value(
+  params: &[i32; 6], gm_type: GlobalMVMode, idx: usize,
+  allow_high_precision_mv: bool
+) -> i32 {
+  let (_, prec_bits) = gm_param_bits(gm_type, idx, allow_high_precision_mv);
+  let sub = if idx % 3 == 2 {
+    1 << prec_bits
+  } else {
+    0
+  };
+  (params[idx] >> (WARPEDMODEL_PREC_BITS - prec_bits)) - sub
+}
+
+fn round2(x: i64, n: usize) -> i64 {
+  (x + (1 << (n - 1))) >> n
+}
+
+fn round2signed(x: i64, n: usize) -> i64 {
+  if x >= 0 {
+    round2(x, n)
+  } else {
+    -round2(-x, n)
+  }
+}
+
+// Shift and factor of an approximate division by d
+fn resolve_divisor(d: i32) -> (usize, i64) {
+  let abs_d = d.abs() as u32;
+  let n = 31 - abs_d.leading_zeros() as usize;
+  let e = (abs_d - (1 << n)) as i64;
+  let f = if n > DIV_LUT_BITS {
+    round2(e, n - DIV_LUT_BITS)
+  } else {
+    e << (DIV_LUT_BITS - n)
+  };
+  // Div_Lut[f], the rounded inverse of 1 + f / 256
+  let lut_d = (1 << DIV_LUT_BITS) + f;
+  let factor = ((1 << (DIV_LUT_PREC_BITS + DIV_LUT_BITS)) + lut_d / 2) / lut_d;
+  let factor = if d < 0 {
+    -factor
+  } else {
+    factor
+  };
+  (n + DIV_LUT_PREC_BITS, factor)
+}
+
+/// Shears alpha, beta, gamma and delta of the warp by params, or None if
+/// the decoder does not warp with these parameters.
+pub fn setup_shear(params: &[i32; 6]) -> Option<[i32; 4]> {
+  let one = 1 << WARPEDMODEL_PREC_BITS;
+  let (div_shift, div_factor) = resolve_divisor(params[2]);
+  let v = (params[4] as i64) << WARPEDMODEL_PREC_BITS;
+  let w = params[3] as i64 * params[4] as i64;
+  let shears = [
+    params[2] as i64 - one,
+    params[3] as i64,
+    round2signed(v * div_factor, div_shift),
+    params[5] as i64 - round2signed(w * div_factor, div_shift) - one
+  ];
+
+  let mut reduced = [0; 4];
+  for (r, &s) in reduced.iter_mut().zip(shears.iter()) {
+    let s = cmp::max(-32768, cmp::min(32767, s));
+    *r = (round2signed(s, WARP_PARAM_REDUCE_BITS) << WARP_PARAM_REDUCE_BITS)
+      as i32;
+  }
+  let (alpha, beta, gamma, delta) =
+    (reduced[0], reduced[1], reduced[2], reduced[3]);
+  if 4 * alpha.abs() + 7 * beta.abs() >= one as i32
+    || 4 * gamma.abs() + 4 * delta.abs() >= one as i32
+  {
+    return None;
+  }
+  Some(reduced)
+}
+
+/// Motion vector of GLOBALMV blocks of size bsize at bo, predicted from a
+/// reference with the warp gm_type and params.
+pub fn global_mv(
+  gm_type: GlobalMVMode, params: &[i32; 6], bo: &BlockOffset,
+  bsize: BlockSize, allow_high_precision_mv: bool
+) -> MotionVector {
+  let shift = WARPEDMODEL_PREC_BITS - 3;
+  let mv = match gm_type {
+    GlobalMVMode::IDENTITY => MotionVector::default(),
+    // The vertical component comes from the first parameter, which the
+    // warp otherwise uses as the horizontal translation
+    GlobalMVMode::TRANSLATION => MotionVector {
+      row: (params[0] >> shift) as i16,
+      col: (params[1] >> shift) as i16
+    },
+    _ => {
+      // Motion of the sample at the center of the block
+      let x = ((bo.x << MI_SIZE_LOG2) + bsize.width() / 2 - 1) as i64;
+      let y = ((bo.y << MI_SIZE_LOG2) + bsize.height() / 2 - 1) as i64;
+      let one = 1 << WARPEDMODEL_PREC_BITS;
+      let xc = (params[2] as i64 - one) * x
+        + params[3] as i64 * y
+        + params[0] as i64;
+      let yc = params[4] as i64 * x
+        + (params[5] as i64 - one) * y
+        + params[1] as i64;
+      if allow_high_precision_mv {
+        MotionVector {
+          row: round2signed(yc, shift) as i16,
+          col: round2signed(xc, shift) as i16
+        }
+      } else {
+        MotionVector {
+          row: round2signed(yc, shift + 1) as i16 * 2,
+          col: round2signed(xc, shift + 1) as i16 * 2
+        }
+      }
+    }
+  };
+  mv.lowered_precision(allow_high_precision_mv)
+}
+
+// Visible luma samples of a frame
+struct Image {
+  data: Vec<i32>,
+  w: usize,
+  h: usize
+}
+
+impl Image {
+  fn new(plane: &Plane, w: usize, h: usize) -> Image {
+    let stride = plane.cfg.stride;
+    let mut data = Vec::with_capacity(w * h);
+    for row in plane.data.chunks(stride).take(h) {
+      data.extend(row[..w].iter().map(|&v| v as i32));
+    }
+    Image { data, w, h }
+  }
+
+  // The image downscaled by 2 in both directions
+  fn half(&self) -> Image {
+    let (w, h) = (self.w / 2, self.h / 2);
+    let mut data = Vec::with_capacity(w * h);
+    for y in 0..h {
+      for x in 0..w {
+        let i = 2 * y * self.w + 2 * x;
+        let sum = self.data[i]
+          + self.data[i + 1]
+          + self.data[i + self.w]
+          + self.data[i + self.w + 1];
+        data.push((sum + 2) >> 2);
+      }
+    }
+    Image { data, w, h }
+  }
+
+  // Sample at (x, y), with the edges replicated outside the image
+  fn p(&self, x: isize, y: isize) -> i32 {
+    let x = cmp::max(0, cmp::min(self.w as isize - 1, x)) as usize;
+    let y = cmp::max(0, cmp::min(self.h as isize - 1, y)) as usize;
+    self.data[y * self.w + x]
+  }
+
+  // SAD of the patches of radius r centered on (x, y) in the image and on
+  // (rx, ry) in rf
+  fn patch_sad(
+    &self, rf: &Image, x: isize, y: isize, rx: isize, ry: isize, r: isize
+  ) -> i32 {
+    let mut sad = 0;
+    for j in -r..r + 1 {
+      for i in -r..r + 1 {
+        sad += (self.p(x + i, y + j) - rf.p(rx + i, ry + j)).abs();
+      }
+    }
+    sad
+  }
+}
+
+// Corners of an image, the points of each cell where the smaller
+// eigenvalue of the structure tensor of the gradients is the largest
+fn detect_corners(img: &Image) -> Vec<(isize, isize)> {
+  let (w, h) = (img.w, img.h);
+  // Corners leave room for the patches around them, and the tensor sums
+  // the gradients of 5x5 windows
+  let margin = PATCH_RADIUS as usize + 2;
+  let r = 2;
+  if w <= 2 * margin || h <= 2 * margin {
+    return Vec::new();
+  }
+
+  // Integral images of the products of the gradients
+  let stride = w + 1;
+  let mut sums = vec![[0i64; 3]; stride * (h + 1)];
+  for y in 0..h {
+    for x in 0..w {
+      let (xi, yi) = (x as isize, y as isize);
+      let gx = (img.p(xi + 1, yi) - img.p(xi - 1, yi)) as i64;
+      let gy = (img.p(xi, yi + 1) - img.p(xi, yi - 1)) as i64;
+      let prods = [gx * gx, gx * gy, gy * gy];
+      for k in 0..3 {
+        sums[(y + 1) * stride + x + 1][k] = prods[k]
+          + sums[y * stride + x + 1][k]
+          + sums[(y + 1) * stride + x][k]
+          - sums[y * stride + x][k];
+      }
+    }
+  }
+  let window = |x: usize, y: usize, k: usize| {
+    (sums[(y + r + 1) * stride + x + r + 1][k]
+      - sums[(y - r) * stride + x + r + 1][k]
+      - sums[(y + r + 1) * stride + x - r][k]
+      + sums[(y - r) * stride + x - r][k]) as f64
+  };
+
+  let mut corners = Vec::new();
+  for cell_y in 0..h / CORNER_CELL {
+    for cell_x in 0..w / CORNER_CELL {
+      let mut best = (0.0, 0, 0);
+      let y_lo = cmp::max(margin, cell_y * CORNER_CELL);
+      let y_hi = cmp::min(h - margin, (cell_y + 1) * CORNER_CELL);
+      let x_lo = cmp::max(margin, cell_x * CORNER_CELL);
+      let x_hi = cmp::min(w - margin, (cell_x + 1) * CORNER_CELL);
+      for y in y_lo..y_hi {
+        for x in x_lo..x_hi {
+          let (a, b, c) = (window(x, y, 0), window(x, y, 1), window(x, y, 2));
+          let score = (a + c - ((a - c) * (a - c) + 4.0 * b * b).sqrt()) / 2.0;
+          if score > best.0 {
+            best = (score, x as isize, y as isize);
+          }
+        }
+      }
+      if best.0 > 0.0 {
+        corners.push(best);
+      }
+    }
+  }
+
+  // The strongest corners, ignoring those of nearly flat cells
+  corners.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap());
+  let threshold = corners.first().map_or(0.0, |c| c.0 / 100.0);
+  corners
+    .iter()
+    .take_while(|c| c.0 >= threshold)
+    .take(MAX_CORNERS)
+    .map(|&(_, x, y)| (x, y))
+    .collect()
+}
+
+// A corner of the source and its position in the reference
+#[derive(Clone, Copy)]
+struct Match {
+  x: f64,
+  y: f64,
+  rx: f64,
+  ry: f64
+}
+
+// Offset of the minimum of the parabola through (-1, l), (0, c), (1, r)
+fn subpel_offset(l: i32, c: i32, r: i32) -> f64 {
+  let curvature = l - 2 * c + r;
+  if curvature > 0 {
+    (l - r) as f64 / (2 * curvature) as f64
+  } else {
+    0.0
+  }
+}
+
+// Position of the corners of src in rf, searched at half resolution, then
+// refined at full resolution and to a fraction of a sample
+fn match_corners(
+  src: &Image, rf: &Image, corners: &[(isize, isize)]
+) -> Vec<Match> {
+  let (src_half, rf_half) = (src.half(), rf.half());
+  let half_range = MATCH_RANGE / 2;
+
+  corners
+    .iter()
+    .map(|&(x, y)| {
+      let (hx, hy) = (x / 2, y / 2);
+      let mut best = (i32::max_value(), 0, 0);
+      for dy in -half_range..half_range + 1 {
+        for dx in -half_range..half_range + 1 {
+          let sad = src_half.patch_sad(
+            &rf_half,
+            hx,
+            hy,
+            hx + dx,
+            hy + dy,
+            PATCH_RADIUS / 2
+          );
+          if sad < best.0 {
+            best = (sad, dx, dy);
+          }
+        }
+      }
+
+      let (cx, cy) = (x + 2 * best.1, y + 2 * best.2);
+      let mut sads = [[0; 5]; 5];
+      let mut best = (i32::max_value(), 2, 2);
+      for j in 0..5 {
+        for i in 0..5 {
+          let (rx, ry) = (cx + i as isize - 2, cy + j as isize - 2);
+          sads[j][i] = src.patch_sad(rf, x, y, rx, ry, PATCH_RADIUS);
+          if sads[j][i] < best.0 {
+            best = (sads[j][i], i, j);
+          }
+        }
+      }
+      let (i, j) = (best.1, best.2);
+      let mut rx = (cx + i as isize - 2) as f64;
+      let mut ry = (cy + j as isize - 2) as f64;
+      if i > 0 && i < 4 {
+        rx += subpel_offset(sads[j][i - 1], sads[j][i], sads[j][i + 1]);
+      }
+      if j > 0 && j < 4 {
+        ry += subpel_offset(sads[j - 1][i], sads[j][i], sads[j + 1][i]);
+      }
+      Match {
+        x: x as f64,
+        y: y as f64,
+        rx,
+        ry
+      }
+    })
+    .collect()
+}
+
+fn num_params(gm_type: GlobalMVMode) -> usize {
+  match gm_type {
+    GlobalMVMode::IDENTITY => 0,
+    GlobalMVMode::TRANSLATION => 2,
+    GlobalMVMode::ROTZOOM => 4,
+    GlobalMVMode::AFFINE => 6
+  }
+}
+
+// Equations of a match in the first num_params parameters of a warp
+fn equations(gm_type: GlobalMVMode, m: &Match) -> [([f64; 6], f64); 2] {
+  match gm_type {
+    GlobalMVMode::TRANSLATION => [
+      ([1.0, 0.0, 0.0, 0.0, 0.0, 0.0], m.rx - m.x),
+      ([0.0, 1.0, 0.0, 0.0, 0.0, 0.0], m.ry - m.y)
+    ],
+    GlobalMVMode::ROTZOOM => [
+      ([1.0, 0.0, m.x, m.y, 0.0, 0.0], m.rx),
+      ([0.0, 1.0, m.y, -m.x, 0.0, 0.0], m.ry)
+    ],
+    _ => [
+      ([1.0, 0.0, m.x, m.y, 0.0, 0.0], m.rx),
+      ([0.0, 1.0, 0.0, 0.0, m.x, m.y], m.ry)
+    ]
+  }
+}
+
+// Least squares warp of type gm_type mapping the matches, solved from the
+// normal equations
+fn fit_model(gm_type: GlobalMVMode, matches: &[Match]) -> Option<[f64; 6]> {
+  let n = num_params(gm_type);
+  let mut a = [[0.0f64; 7]; 6];
+  for m in matches {
+    for &(ref row, rhs) in equations(gm_type, m).iter() {
+      for i in 0..n {
+        for j in 0..n {
+          a[i][j] += row[i] * row[j];
+        }
+        a[i][6] += row[i] * rhs;
+      }
+    }
+  }
+
+  for col in 0..n {
+    let pivot = (col..n)
+      .max_by(|&i, &j| a[i][col].abs().partial_cmp(&a[j][col].abs()).unwrap())
+      .unwrap();
+    if a[pivot][col].abs() < 1e-6 {
+      return None;
+    }
+    a.swap(col, pivot);
+    for row in 0..n {
+      if row != col {
+        let f = a[row][col] / a[col][col];
+        for k in col..7 {
+          a[row][k] -= f * a[col][k];
+        }
+      }
+    }
+  }
+
+  let mut model = [0.0, 0.0, 1.0, 0.0, 0.0, 1.0];
+  for i in 0..n {
+    model[i] = a[i][6] / a[i][i];
+  }
+  if gm_type == GlobalMVMode::ROTZOOM {
+    model[4] = -model[3];
+    model[5] = model[2];
+  }
+  Some(model)
+}
+
+fn agrees(model: &[f64; 6], m: &Match) -> bool {
+  let dx = model[2] * m.x + model[3] * m.y + model[0] - m.rx;
+  let dy = model[4] * m.x + model[5] * m.y + model[1] - m.ry;
+  dx * dx + dy * dy < INLIER_THRESHOLD * INLIER_THRESHOLD
+}
+
+// Warp of type gm_type agreeing with the most matches, fitted to those
+// matches, and how many agree with it
+fn ransac(
+  gm_type: GlobalMVMode, matches: &[Match]
+) -> Option<([f64; 6], usize)> {
+  let sample_size = (num_params(gm_type) + 1) / 2;
+  if matches.len() < sample_size {
+    return None;
+  }
+  let count =
+    |model: &[f64; 6]| matches.iter().filter(|m| agrees(model, m)).count();
+
+  // Fixed seed, for the encoder to be deterministic
+  let mut seed: u64 = 1;
+  let mut best: Option<([f64; 6], usize)> = None;
+  for _ in 0..RANSAC_ITERATIONS {
+    let mut sample: Vec<usize> = Vec::with_capacity(sample_size);
+    while sample.len() < sample_size {
+      seed = seed
+        .wrapping_mul(6364136223846793005)
+        .wrapping_add(1442695040888963407);
+      let idx = (seed >> 33) as usize % matches.len();
+      if !sample.contains(&idx) {
+        sample.push(idx);
+      }
+    }
+    let sample: Vec<Match> = sample.iter().map(|&i| matches[i]).collect();
+    if let Some(model) = fit_model(gm_type, &sample) {
+      let n = count(&model);
+      if best.map_or(true, |(_, best_n)| n > best_n) {
+        best = Some((model, n));
+      }
+    }
+  }
+
+  best.and_then(|(model, _)| {
+    let agreeing: Vec<Match> =
+      matches.iter().filter(|m| agrees(&model, m)).cloned().collect();
+    fit_model(gm_type, &agreeing).map(|refined| (refined, count(&refined)))
+  })
+}
+
+// Parameters of a warp of type gm_type at the precision they are coded
+// with, or None if they are out of range or unusable for warping
+fn quantize_model(
+  gm_type: GlobalMVMode, model: &[f64; 6], allow_high_precision_mv: bool
+) -> Option<[i32; 6]> {
+  // The vertical translation comes first, see global_mv
+  let mut model = *model;
+  if gm_type == GlobalMVMode::TRANSLATION {
+    model.swap(0, 1);
+  }
+
+  let mut params = DEFAULT_GM_PARAMS;
+  for &idx in gm_coded_params(gm_type) {
+    let (abs_bits, prec_bits) =
+      gm_param_bits(gm_type, idx, allow_high_precision_mv);
+    let scaled = (model[idx] * (1 << prec_bits) as f64).round();
+    if !(scaled.abs() < (1 << 20) as f64) {
+      return None;
+    }
+    let sub = if idx % 3 == 2 {
+      1 << prec_bits
+    } else {
+      0
+    };
+    let v = scaled as i32 - sub;
+    if v.abs() > 1 << abs_bits {
+      return None;
+    }
+    params[idx] = (v + sub) << (WARPEDMODEL_PREC_BITS - prec_bits);
+  }
+  if gm_type == GlobalMVMode::ROTZOOM {
+    params[4] = -params[3];
+    params[5] = params[2];
+  }
+
+  if gm_type > GlobalMVMode::TRANSLATION && setup_shear(&params).is_none() {
+    return None;
+  }
+  Some(params)
+}
+
+/// Global motion from the visible luma of src to that of ref_plane: the
+/// type and parameters of the warp agreeing with most matches of corners
+/// between them, or the identity if too few matches agree with any.
+pub fn estimate_global_motion(
+  fi: &FrameInvariants, src: &Plane, ref_plane: &Plane
+) -> (GlobalMVMode, [i32; 6]) {
+  let src = Image::new(src, fi.width, fi.height);
+  let rf = Image::new(ref_plane, fi.width, fi.height);
+  let corners = detect_corners(&src);
+  let matches = match_corners(&src, &rf, &corners);
+
+  let mut best = (GlobalMVMode::IDENTITY, DEFAULT_GM_PARAMS, 0);
+  for &gm_type in &[
+    GlobalMVMode::TRANSLATION,
+    GlobalMVMode::ROTZOOM,
+    GlobalMVMode::AFFINE
+  ] {
+    if let Some((model, n)) = ransac(gm_type, &matches) {
+      // Models with more parameters need clearly more matches to agree
+      if n < MIN_INLIERS || 3 * n < matches.len() || n <= best.2 + best.2 / 16
+      {
+        continue;
+      }
+      if let Some(params) =
+        quantize_model(gm_type, &model, fi.allow_high_precision_mv)
+      {
+        best = (gm_type, params, n);
+      }
+    }
+  }
+
+  if best.1 == DEFAULT_GM_PARAMS {
+    (GlobalMVMode::IDENTITY, DEFAULT_GM_PARAMS)
+  } else {
+    (best.0, best.1)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use rand::{ChaChaRng, Rng, SeedableRng};
+
+  const W: usize = 256;
+  const H: usize = 256;
+  const GRID: usize = 8;
+
+  // Source frame of a smooth random texture, and a reference where the
+  // sample at warp(x, y) is the source sample at (x, y)
+  fn frames(unwarp: &Fn(f64, f64) -> (f64, f64)) -> (Plane, Plane) {
+    let mut ra = ChaChaRng::from_seed([0; 32]);
+    let cells = W / GRID + 2;
+    let grid: Vec<f64> =
+      (0..cells * cells).map(|_| ra.gen_range(0, 256) as f64).collect();
+    let texture = |x: f64, y: f64| {
+      let (gx, gy) = (x / GRID as f64, y / GRID as f64);
+      let gx = gx.max(0.0).min((cells - 2) as f64);
+      let gy = gy.max(0.0).min((cells - 2) as f64);
+      let (ix, iy) = (gx.floor() as usize, gy.floor() as usize);
+      let (fx, fy) = (gx - ix as f64, gy - iy as f64);
+      let g = |i: usize, j: usize| grid[(iy + j) * cells + ix + i];
+      let top = g(0, 0) * (1.0 - fx) + g(1, 0) * fx;
+      let bottom = g(0, 1) * (1.0 - fx) + g(1, 1) * fx;
+      (top * (1.0 - fy) + bottom * fy).round() as u16
+    };
+
+    let mut src = Plane::new(W, H, 0, 0);
+    let mut rf = Plane::new(W, H, 0, 0);
+    for y in 0..H {
+      for x in 0..W {
+        src.data[y * W + x] = texture(x as f64, y as f64);
+        let (ux, uy) = unwarp(x as f64, y as f64);
+        rf.data[y * W + x] = texture(ux, uy);
+      }
+    }
+    (src, rf)
+  }
+
+  #[test]
+  fn estimate_translation() {
+    let fi = FrameInvariants::new(W, H, 100, 3);
+    let (src, rf) = frames(&|x, y| (x - 5.0, y + 3.0));
+    let (gm_type, params) = estimate_global_motion(&fi, &src, &rf);
+    assert_eq!(gm_type, GlobalMVMode::TRANSLATION);
+    // Vertical translation first
+    assert_eq!(params, [-3 << 16, 5 << 16, 1 << 16, 0, 0, 1 << 16]);
+
+    let bo = BlockOffset { x: 8, y: 8 };
+    let mv = global_mv(gm_type, &params, &bo, BlockSize::BLOCK_16X16, true);
+    assert_eq!(mv, MotionVector { row: -24, col: 40 });
+  }
+
+  #[test]
+  fn estimate_zoom() {
+    let fi = FrameInvariants::new(W, H, 100, 3);
+    let (c, s) = (W as f64 / 2.0, 1.03);
+    let (src, rf) = frames(&|x, y| (c + (x - c) / s, c + (y - c) / s));
+    let (gm_type, params) = estimate_global_motion(&fi, &src, &rf);
+    assert_eq!(gm_type, GlobalMVMode::ROTZOOM);
+    assert!((params[2] - (s * 65536.0) as i32).abs() < 256);
+    assert!(params[3].abs() < 256);
+    assert!(setup_shear(&params).is_some());
+
+    // Blocks move away from the center
+    let bo = BlockOffset { x: 0, y: 0 };
+    let mv = global_mv(gm_type, &params, &bo, BlockSize::BLOCK_16X16, true);
+    assert!(mv.row < 0 && mv.col < 0);
+  }
+
+  #[test]
+  fn shear_of_identity() {
+    assert_eq!(setup_shear(&DEFAULT_GM_PARAMS), Some([0; 4]));
+    // Too strong a zoom is not warped
+    let mut params = DEFAULT_GM_PARAMS;
+    params[2] += 1 << 14;
+    assert_eq!(setup_shear(&params), None);
+  }
+}
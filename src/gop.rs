@@ -0,0 +1,249 @@
+// Copyright (c) 2018, The rav1e contributors. All rights reserved
+//
+// This source code is subject to the terms of the BSD 2 Clause License and
+// the Alliance for Open Media Patent License 1.0. If the BSD 2 Clause License
+// was not distributed with this source code in the LICENSE file, you can
+// obtain it at www.aomedia.org/license/software. If the Alliance for Open
+// Media Patent License 1.0 was not distributed with this source code in the
+// PATENTS file, you can obtain it at www.aomedia.org/license/patent.
+
+//! Coding order of the frames between two anchors, and the choice of
+//! their references.
+
+use ReferenceFramesSet;
+use NUM_REF_FRAMES;
+use REFS_PER_FRAME;
+
+/// Largest mini-GOP length accepted by `EncoderConfig::validate`
+pub const MAX_MINI_GOP_LEN: usize = 16;
+
+/// A frame of a mini-GOP, in coding order
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FramePlan {
+  /// Display order position, from 1 for the frame following the anchor
+  pub offset: usize,
+  pub show_frame: bool,
+  pub show_existing_frame: bool
+}
+
+impl FramePlan {
+  fn shown(offset: usize) -> FramePlan {
+    FramePlan {
+      offset,
+      show_frame: true,
+      show_existing_frame: false
+    }
+  }
+
+  fn hidden(offset: usize) -> FramePlan {
+    FramePlan {
+      offset,
+      show_frame: false,
+      show_existing_frame: false
+    }
+  }
+
+  fn show_existing(offset: usize) -> FramePlan {
+    FramePlan {
+      offset,
+      show_frame: true,
+      show_existing_frame: true
+    }
+  }
+}
+
+/// Coding order of the `len` frames following an anchor. The last of them
+/// is coded first as a hidden ALTREF, then the frames in between are coded
+/// as a pyramid of hidden frames referring to both sides, each shown with
+/// show_existing_frame once the frames before it are out.
+pub fn plan_mini_gop(len: usize) -> Vec<FramePlan> {
+  assert!(len > 0);
+  let mut plan = Vec::with_capacity(2 * len);
+  if len == 1 {
+    plan.push(FramePlan::shown(1));
+  } else {
+    plan.push(FramePlan::hidden(len));
+    plan_between(0, len, &mut plan);
+    plan.push(FramePlan::show_existing(len));
+  }
+  plan
+}
+
+// Frames strictly between lo and hi, both of which are already coded
+fn plan_between(lo: usize, hi: usize, plan: &mut Vec<FramePlan>) {
+  if hi - lo < 2 {
+    return;
+  }
+  let mid = (lo + hi) / 2;
+  if mid - lo == 1 {
+    plan.push(FramePlan::shown(mid));
+  } else {
+    plan.push(FramePlan::hidden(mid));
+    plan_between(lo, mid, plan);
+    plan.push(FramePlan::show_existing(mid));
+  }
+  plan_between(mid, hi, plan);
+}
+
+/// Slot holding the frame `number`, if any.
+pub fn find_slot(refs: &ReferenceFramesSet, number: u64) -> Option<usize> {
+  refs.frames.iter().position(|f| match *f {
+    Some(ref f) => f.number == number,
+    None => false
+  })
+}
+
+/// Slots of LAST_FRAME to ALTREF_FRAME for the frame `number`. LAST_FRAME
+/// to LAST3_FRAME are the closest past frames and GOLDEN_FRAME the oldest,
+/// BWDREF_FRAME and ALTREF2_FRAME the closest future frames and ALTREF_FRAME
+/// the furthest. Missing references fall back to the closest frames.
+pub fn select_references(
+  refs: &ReferenceFramesSet, number: u64
+) -> [usize; REFS_PER_FRAME] {
+  let mut past = Vec::new();
+  let mut future = Vec::new();
+  for (i, f) in refs.frames.iter().enumerate() {
+    if let Some(ref f) = *f {
+      if find_slot(refs, f.number) != Some(i) {
+        continue;
+      }
+      if f.number < number {
+        past.push((f.number, i));
+      } else {
+        future.push((f.number, i));
+      }
+    }
+  }
+  past.sort_by(|a, b| b.cmp(a));
+  future.sort();
+
+  let last = past.first().or(future.first()).expect("no reference frames").1;
+  let last2 = past.get(1).map_or(last, |r| r.1);
+  let last3 = past.get(2).map_or(last2, |r| r.1);
+  let golden = past.last().map_or(last, |r| r.1);
+  let bwd = future.first().map_or(last, |r| r.1);
+  let altref2 = future.get(1).map_or(bwd, |r| r.1);
+  let altref = future.last().map_or(bwd, |r| r.1);
+  [last, last2, last3, golden, bwd, altref2, altref]
+}
+
+/// Slot to store a new frame into. Copies of a frame are replaced first,
+/// the frames not shown yet and the last shown frame are kept, and the
+/// oldest frame goes last since it serves as GOLDEN_FRAME.
+pub fn refresh_slot(refs: &ReferenceFramesSet, last_shown: u64) -> usize {
+  let oldest =
+    refs.frames.iter().filter_map(|f| f.as_ref()).map(|f| f.number).min();
+  let rank = |i: usize| match refs.frames[i] {
+    None => (0, 0),
+    Some(ref f) if find_slot(refs, f.number) != Some(i) => (1, 0),
+    Some(ref f) if f.number >= last_shown => (4, 0),
+    Some(ref f) if Some(f.number) == oldest => (3, 0),
+    Some(ref f) => (2, f.number)
+  };
+  let slot = (0..NUM_REF_FRAMES).min_by_key(|&i| rank(i)).unwrap();
+  assert!(rank(slot).0 < 4, "every reference slot is in use");
+  slot
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  // Store the frames in order, each in the slots set in its flags
+  fn reference_set(frames: &[(u64, u32)]) -> ReferenceFramesSet {
+    let mut refs = ReferenceFramesSet::default();
+    for &(number, refresh_frame_flags) in frames {
+      refs.refresh(refresh_frame_flags, &::test::reference_frame(number));
+    }
+    refs
+  }
+
+  fn one_per_slot(numbers: &[u64]) -> Vec<(u64, u32)> {
+    numbers.iter().enumerate().map(|(i, &n)| (n, 1 << i)).collect()
+  }
+
+  #[test]
+  fn mini_gop_order() {
+    let order = |len| -> Vec<(usize, bool, bool)> {
+      plan_mini_gop(len)
+        .iter()
+        .map(|p| (p.offset, p.show_frame, p.show_existing_frame))
+        .collect()
+    };
+    assert_eq!(order(1), [(1, true, false)]);
+    assert_eq!(
+      order(2),
+      [(2, false, false), (1, true, false), (2, true, true)]
+    );
+    assert_eq!(
+      order(4),
+      [
+        (4, false, false),
+        (2, false, false),
+        (1, true, false),
+        (2, true, true),
+        (3, true, false),
+        (4, true, true)
+      ]
+    );
+  }
+
+  #[test]
+  fn mini_gop_shows_in_display_order() {
+    for len in 1..MAX_MINI_GOP_LEN + 1 {
+      let plan = plan_mini_gop(len);
+      let shown: Vec<usize> =
+        plan.iter().filter(|p| p.show_frame).map(|p| p.offset).collect();
+      assert_eq!(shown, (1..len + 1).collect::<Vec<_>>());
+      let coded = plan.iter().filter(|p| !p.show_existing_frame).count();
+      assert_eq!(coded, len);
+    }
+  }
+
+  #[test]
+  fn references_from_both_sides() {
+    // Down the pyramid of a mini-GOP of 8, from its ALTREF
+    let refs =
+      reference_set(&[(0, 0xff), (8, 1 << 3), (4, 1 << 2), (2, 1 << 1)]);
+    assert_eq!(select_references(&refs, 3), [1, 0, 0, 0, 2, 3, 3]);
+    let refs =
+      reference_set(&[(0, 0xff), (4, 1 << 3), (1, 1 << 1), (2, 1 << 2)]);
+    assert_eq!(select_references(&refs, 3), [2, 1, 0, 0, 3, 3, 3]);
+  }
+
+  #[test]
+  fn missing_references_fall_back() {
+    // Only copies of the key frame
+    assert_eq!(select_references(&reference_set(&[(0, 0xff)]), 1), [0; 7]);
+    // No future frames
+    let refs_past = reference_set(&[(0, 0xff), (1, 1 << 1), (2, 1 << 2)]);
+    assert_eq!(select_references(&refs_past, 3), [2, 1, 0, 0, 2, 2, 2]);
+    // A single past and future frame
+    let refs_altref = reference_set(&[(0, 0xff), (4, 1 << 5)]);
+    assert_eq!(select_references(&refs_altref, 2), [0, 0, 0, 0, 5, 5, 5]);
+    // No past frames
+    assert_eq!(select_references(&reference_set(&[(4, 0b110)]), 2), [1; 7]);
+  }
+
+  #[test]
+  fn refresh_slot_order() {
+    // Empty slots first
+    assert_eq!(refresh_slot(&reference_set(&[(0, 1), (1, 1 << 2)]), 1), 1);
+    // Then copies of a frame
+    let copies = reference_set(&[(0, 0xff), (1, 1 << 1)]);
+    assert_eq!(refresh_slot(&copies, 1), 2);
+    // Then the oldest past frame but the golden one
+    let past = one_per_slot(&[0, 3, 1, 2, 8, 4, 6, 5]);
+    assert_eq!(refresh_slot(&reference_set(&past), 4), 2);
+    // The golden frame last, the last shown and later frames never
+    let future = one_per_slot(&[0, 5, 6, 7, 8, 9, 10, 11]);
+    assert_eq!(refresh_slot(&reference_set(&future), 5), 0);
+  }
+
+  #[test]
+  #[should_panic(expected = "every reference slot is in use")]
+  fn refresh_slot_all_in_use() {
+    let frames = one_per_slot(&[4, 5, 6, 7, 8, 9, 10, 11]);
+    refresh_slot(&reference_set(&frames), 4);
+  }
+}
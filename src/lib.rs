@@ -7,34 +7,49 @@
 // Media Patent License 1.0 was not distributed with this source code in the
 // PATENTS file, you can obtain it at www.aomedia.org/license/patent.
 
-#![allow(safe_extern_statics)]
 #![cfg_attr(feature = "cargo-clippy", allow(collapsible_if))]
 
 extern crate bitstream_io;
 extern crate backtrace;
-extern crate clap;
 extern crate libc;
 extern crate rand;
 extern crate y4m;
+#[cfg(test)]
+extern crate aom_sys;
 
 #[macro_use]
 extern crate enum_iterator_derive;
 
 use std::fs::File;
 use std::io::prelude::*;
+use std::io::SeekFrom;
+use std::borrow::Borrow;
+use std::collections::VecDeque;
+use std::rc::Rc;
 use bitstream_io::{BE, LE, BitWriter};
-use clap::{App, Arg};
 
 // for benchmarking purpose
 pub mod ec;
 pub mod partition;
 pub mod plane;
 pub mod context;
+mod entropymode;
+mod token_cdfs;
+mod scan_order;
 pub mod transform;
 pub mod quantize;
 pub mod predict;
 pub mod rdo;
+pub mod me;
+pub mod gop;
+pub mod gm;
 pub mod util;
+pub mod webm;
+pub mod mp4;
+pub mod avif;
+pub mod annexb;
+#[cfg(feature = "capi")]
+pub mod capi;
 
 use context::*;
 use partition::*;
@@ -45,50 +60,298 @@ use rdo::*;
 use ec::*;
 use std::fmt;
 use util::*;
+use gop::*;
+use gm::*;
 
-extern {
-    pub fn av1_rtcd();
-    pub fn aom_dsp_rtcd();
-}
-
+#[derive(Clone)]
 pub struct Frame {
-    pub planes: [Plane; 3]
+    pub planes: Vec<Plane>
 }
 
 impl Frame {
-    pub fn new(width: usize, height:usize) -> Frame {
-        Frame {
-            planes: [
-                Plane::new(width, height, 0, 0),
-                Plane::new(width/2, height/2, 1, 1),
-                Plane::new(width/2, height/2, 1, 1)
-            ]
+    /// Allocate the planes for `chroma_sampling`, only luma for monochrome.
+    pub fn new(width: usize, height:usize, chroma_sampling: ChromaSampling) -> Frame {
+        let mut planes = vec![Plane::new(width, height, 0, 0)];
+        if chroma_sampling != ChromaSampling::CS400 {
+            let (xdec, ydec) = chroma_sampling.get_decimation();
+            for _ in 1..3 {
+                planes.push(Plane::new((width + xdec) >> xdec, (height + ydec) >> ydec,
+                                       xdec, ydec));
+            }
+        }
+        Frame { planes }
+    }
+}
+
+#[allow(dead_code,non_camel_case_types)]
+#[derive(Debug,Clone,Copy,PartialEq)]
+pub enum ChromaSampling {
+    CS420,
+    CS422,
+    CS444,
+    CS400,
+}
+
+impl ChromaSampling {
+    /// Horizontal and vertical log2 decimation of the chroma planes.
+    /// Monochrome is signaled as subsampled in both directions.
+    pub fn get_decimation(self) -> (usize, usize) {
+        match self {
+            ChromaSampling::CS420 | ChromaSampling::CS400 => (1, 1),
+            ChromaSampling::CS422 => (1, 0),
+            ChromaSampling::CS444 => (0, 0),
+        }
+    }
+
+    pub fn from_y4m(csp: y4m::Colorspace) -> Option<ChromaSampling> {
+        use y4m::Colorspace::*;
+        match csp {
+            C420 | C420jpeg | C420paldv | C420mpeg2 | C420p10 | C420p12 =>
+                Some(ChromaSampling::CS420),
+            C422 | C422p10 | C422p12 => Some(ChromaSampling::CS422),
+            C444 | C444p10 | C444p12 => Some(ChromaSampling::CS444),
+            Cmono => Some(ChromaSampling::CS400),
+            _ => None,
+        }
+    }
+}
+
+#[allow(dead_code,non_camel_case_types)]
+#[derive(Debug,Clone,Copy,PartialEq)]
+pub enum ChromaSamplePosition {
+    UNKNOWN = 0,
+    VERTICAL = 1,
+    COLOCATED = 2,
+}
+
+impl ChromaSamplePosition {
+    pub fn from_y4m(csp: y4m::Colorspace) -> ChromaSamplePosition {
+        match csp {
+            y4m::Colorspace::C420mpeg2 => ChromaSamplePosition::VERTICAL,
+            y4m::Colorspace::C420paldv => ChromaSamplePosition::COLOCATED,
+            _ => ChromaSamplePosition::UNKNOWN,
+        }
+    }
+}
+
+#[allow(dead_code,non_camel_case_types)]
+#[derive(Debug,Clone,Copy,PartialEq)]
+pub enum ColorPrimaries {
+    BT709 = 1,
+    UNSPECIFIED = 2,
+    BT470M = 4,
+    BT470BG = 5,
+    BT601 = 6,
+    SMPTE240 = 7,
+    GENERIC_FILM = 8,
+    BT2020 = 9,
+    XYZ = 10,
+    SMPTE431 = 11,
+    SMPTE432 = 12,
+    EBU3213 = 22,
+}
+
+#[allow(dead_code,non_camel_case_types)]
+#[derive(Debug,Clone,Copy,PartialEq)]
+pub enum TransferCharacteristics {
+    BT709 = 1,
+    UNSPECIFIED = 2,
+    BT470M = 4,
+    BT470BG = 5,
+    BT601 = 6,
+    SMPTE240 = 7,
+    LINEAR = 8,
+    LOG100 = 9,
+    LOG100_SQRT10 = 10,
+    IEC61966 = 11,
+    BT1361 = 12,
+    SRGB = 13,
+    BT2020_10BIT = 14,
+    BT2020_12BIT = 15,
+    SMPTE2084 = 16,
+    SMPTE428 = 17,
+    HLG = 18,
+}
+
+#[allow(dead_code,non_camel_case_types)]
+#[derive(Debug,Clone,Copy,PartialEq)]
+pub enum MatrixCoefficients {
+    IDENTITY = 0,
+    BT709 = 1,
+    UNSPECIFIED = 2,
+    FCC = 4,
+    BT470BG = 5,
+    BT601 = 6,
+    SMPTE240 = 7,
+    YCGCO = 8,
+    BT2020_NCL = 9,
+    BT2020_CL = 10,
+    SMPTE2085 = 11,
+    CHROMAT_NCL = 12,
+    CHROMAT_CL = 13,
+    ICTCP = 14,
+}
+
+#[allow(dead_code,non_camel_case_types)]
+#[derive(Debug,Clone,Copy,PartialEq)]
+pub enum PixelRange {
+    LIMITED,
+    FULL,
+}
+
+/// Display timing signaled in the sequence header
+#[derive(Debug,Clone,Copy)]
+pub struct TimingInfo {
+    pub num_units_in_display_tick: u32,
+    pub time_scale: u32,
+    // Some(n) when every picture lasts exactly n ticks
+    pub num_ticks_per_picture: Option<u32>
+}
+
+impl TimingInfo {
+    /// Constant frame rate of `num`/`den` frames per second
+    pub fn new(num: usize, den: usize) -> TimingInfo {
+        TimingInfo {
+            num_units_in_display_tick: den as u32,
+            time_scale: num as u32,
+            num_ticks_per_picture: Some(1)
         }
     }
 }
 
 pub struct Sequence {
-    pub profile: u8
+    pub profile: u8,
+    pub level: u8,
+    pub tier: u8,
+    pub num_bits_width: u32,
+    pub num_bits_height: u32,
+    pub max_frame_width: u32,
+    pub max_frame_height: u32,
+    pub bit_depth: usize,
+    pub chroma_sampling: ChromaSampling,
+    pub chroma_sample_position: ChromaSamplePosition,
+    pub color_primaries: ColorPrimaries,
+    pub transfer_characteristics: TransferCharacteristics,
+    pub matrix_coefficients: MatrixCoefficients,
+    pub pixel_range: PixelRange,
+    pub timing_info: Option<TimingInfo>,
+    pub still_picture: bool,
+    pub reduced_still_picture_hdr: bool
 }
 
 impl Sequence {
-    pub fn new() -> Sequence {
+    pub fn new(width: usize, height: usize) -> Sequence {
+        let width_bits = 32 - (width as u32).leading_zeros();
+        let height_bits = 32 - (height as u32).leading_zeros();
+
         Sequence {
-            profile: 0
+            profile: 0,
+            level: 31, // maximum parameters
+            tier: 0,
+            num_bits_width: width_bits,
+            num_bits_height: height_bits,
+            max_frame_width: width as u32,
+            max_frame_height: height as u32,
+            bit_depth: 8,
+            chroma_sampling: ChromaSampling::CS420,
+            chroma_sample_position: ChromaSamplePosition::UNKNOWN,
+            color_primaries: ColorPrimaries::UNSPECIFIED,
+            transfer_characteristics: TransferCharacteristics::UNSPECIFIED,
+            matrix_coefficients: MatrixCoefficients::UNSPECIFIED,
+            pixel_range: PixelRange::LIMITED,
+            timing_info: None,
+            still_picture: false,
+            reduced_still_picture_hdr: false
         }
     }
+
+    /// Check the fields set by the caller rather than by `Encoder::new`
+    /// against what the sequence header can signal.
+    fn validate(&self) -> Result<(), EncoderError> {
+        let (w, h) = (self.max_frame_width as usize, self.max_frame_height as usize);
+        if w == 0 || h == 0 || self.num_bits_width > 16 || self.num_bits_height > 16 ||
+           (w - 1) >> self.num_bits_width != 0 || (h - 1) >> self.num_bits_height != 0 {
+            return Err(EncoderError::UnsupportedInput(
+                format!("frame size {}x{} is out of range", w, h)));
+        }
+        if self.level > 31 || self.tier > 1 {
+            return Err(EncoderError::UnsupportedInput(
+                format!("level {} tier {} is out of range", self.level, self.tier)));
+        }
+        if let Some(ref t) = self.timing_info {
+            if t.num_units_in_display_tick == 0 || t.time_scale == 0 ||
+               t.num_ticks_per_picture == Some(0) {
+                return Err(EncoderError::UnsupportedInput(
+                    "timing info with a zero time base".to_string()));
+            }
+        }
+        // This includes sRGB
+        if self.matrix_coefficients == MatrixCoefficients::IDENTITY &&
+           self.chroma_sampling != ChromaSampling::CS444 {
+            return Err(EncoderError::UnsupportedInput(
+                "identity matrix coefficients require 4:4:4".to_string()));
+        }
+        Ok(())
+    }
+
+    fn color_description_present(&self) -> bool {
+        self.color_primaries != ColorPrimaries::UNSPECIFIED ||
+        self.transfer_characteristics != TransferCharacteristics::UNSPECIFIED ||
+        self.matrix_coefficients != MatrixCoefficients::UNSPECIFIED
+    }
 }
 
 pub struct FrameState {
     pub input: Frame,
-    pub rec: Frame
+    pub rec: Frame,
+    /// CDFs the frame starts from, also saved with it as they are not
+    /// updated at the end of the frame
+    pub cdfs: CDFContext,
+    /// Motion vectors to store with the frame in the reference slots
+    pub frame_mvs: Vec<Vec<Option<SavedMotionVector>>>
 }
 
 impl FrameState {
     pub fn new(fi: &FrameInvariants) -> FrameState {
         FrameState {
-            input: Frame::new(fi.padded_w, fi.padded_h),
-            rec: Frame::new(fi.padded_w, fi.padded_h),
+            input: Frame::new(fi.padded_w, fi.padded_h, fi.chroma_sampling),
+            rec: Frame::new(fi.padded_w, fi.padded_h, fi.chroma_sampling),
+            cdfs: CDFContext::new(fi.qindex as u8),
+            frame_mvs: Vec::new(),
+        }
+    }
+}
+
+/// Decoded frame stored in a reference slot, with the state later frames
+/// may load from it
+pub struct ReferenceFrame {
+    pub frame: Frame,
+    pub cdfs: CDFContext,
+    pub frame_type: FrameType,
+    /// Display order index
+    pub number: u64,
+    pub order_hint: u32,
+    /// Order hints of the references of the frame, LAST_FRAME to
+    /// ALTREF_FRAME, all 0 for intra frames
+    pub ref_order_hints: [u32; REFS_PER_FRAME],
+    /// Motion vectors of the frame by 8x8 block, for the temporal
+    /// candidates of the frames predicted from it
+    pub frame_mvs: Vec<Vec<Option<SavedMotionVector>>>
+}
+
+/// The reference slots, kept in sync with the decoder by refresh_frame_flags
+#[derive(Default)]
+pub struct ReferenceFramesSet {
+    pub frames: [Option<Rc<ReferenceFrame>>; NUM_REF_FRAMES]
+}
+
+impl ReferenceFramesSet {
+    /// Store `frame` in every slot set in `refresh_frame_flags`.
+    pub fn refresh(&mut self, refresh_frame_flags: u32, frame: &Rc<ReferenceFrame>) {
+        for (i, slot) in self.frames.iter_mut().enumerate() {
+            if refresh_frame_flags & (1 << i) != 0 {
+                *slot = Some(frame.clone());
+            }
         }
     }
 }
@@ -124,6 +387,8 @@ impl Fixed for usize {
 pub struct FrameInvariants {
     pub qindex: usize,
     pub speed: usize,
+    pub bit_depth: usize,
+    pub chroma_sampling: ChromaSampling,
     pub width: usize,
     pub height: usize,
     pub padded_w: usize,
@@ -133,6 +398,7 @@ pub struct FrameInvariants {
     pub w_in_b: usize,
     pub h_in_b: usize,
     pub number: u64,
+    pub order_hint: u32,
     pub show_frame: bool,
     pub error_resilient: bool,
     pub intra_only: bool,
@@ -143,7 +409,23 @@ pub struct FrameInvariants {
     pub reference_mode: ReferenceMode,
     pub use_prev_frame_mvs: bool,
     pub min_partition_size: BlockSize,
+    pub tile_cols_log2: usize,
+    pub tile_rows_log2: usize,
     pub globalmv_transformation_type: [GlobalMVMode; ALTREF_FRAME + 1],
+    /// Global motion of each reference, as the parameters of the warp from
+    /// the frame to the reference
+    pub gm_params: [[i32; 6]; ALTREF_FRAME + 1],
+    pub refresh_frame_flags: u32,
+    pub ref_frame_idx: [usize; REFS_PER_FRAME],
+    /// Slots of LAST_FRAME to ALTREF_FRAME, as given by ref_frame_idx
+    pub ref_frames: [Option<Rc<ReferenceFrame>>; REFS_PER_FRAME],
+    pub frame_to_show_map_idx: usize,
+    /// Order hints of the frames in the reference slots, signalled by error
+    /// resilient frames
+    pub ref_order_hint: [u32; NUM_REF_FRAMES],
+    /// Saved motion vectors of the references projected onto the frame, by
+    /// 8x8 block, when use_prev_frame_mvs
+    pub motion_field: Vec<Vec<Option<TemporalMotionVector>>>,
 }
 
 impl FrameInvariants {
@@ -160,6 +442,8 @@ impl FrameInvariants {
         FrameInvariants {
             qindex,
             speed,
+            bit_depth: 8,
+            chroma_sampling: ChromaSampling::CS420,
             width,
             height,
             padded_w: width.align_power_of_two(3),
@@ -169,8 +453,9 @@ impl FrameInvariants {
             w_in_b: 2 * width.align_power_of_two_and_shift(3), // MiCols, ((width+7)/8)<<3 >> MI_SIZE_LOG2
             h_in_b: 2 * height.align_power_of_two_and_shift(3), // MiRows, ((height+7)/8)<<3 >> MI_SIZE_LOG2
             number: 0,
+            order_hint: 0,
             show_frame: true,
-            error_resilient: true,
+            error_resilient: false,
             intra_only: false,
             allow_high_precision_mv: true,
             frame_type: FrameType::KEY,
@@ -179,7 +464,50 @@ impl FrameInvariants {
             reference_mode: ReferenceMode::SINGLE,
             use_prev_frame_mvs: false,
             min_partition_size,
+            tile_cols_log2: 0,
+            tile_rows_log2: 0,
             globalmv_transformation_type: [GlobalMVMode::IDENTITY; ALTREF_FRAME + 1],
+            gm_params: [DEFAULT_GM_PARAMS; ALTREF_FRAME + 1],
+            refresh_frame_flags: 0,
+            ref_frame_idx: [0; REFS_PER_FRAME],
+            ref_frames: Default::default(),
+            frame_to_show_map_idx: 0,
+            ref_order_hint: [0; NUM_REF_FRAMES],
+            motion_field: Vec::new(),
+        }
+    }
+
+    /// Width and height of the tiles in superblocks, but for those of the
+    /// last tile column and row which may be smaller
+    fn tile_size_sb(&self) -> (usize, usize) {
+        (self.sb_width.align_power_of_two_and_shift(self.tile_cols_log2),
+         self.sb_height.align_power_of_two_and_shift(self.tile_rows_log2))
+    }
+
+    /// Order hint of the reference ref_frame, 0 if its slot is empty
+    fn ref_frame_order_hint(&self, ref_frame: usize) -> u32 {
+        self.ref_frames[ref_frame - LAST_FRAME].as_ref().map_or(0, |r| r.order_hint)
+    }
+
+    /// Whether the reference ref_frame follows the frame in display order
+    fn ref_frame_sign_bias(&self, ref_frame: usize) -> bool {
+        relative_dist(self.ref_frame_order_hint(ref_frame), self.order_hint) > 0
+    }
+
+    /// Whether skip mode may be signalled, with the nearest past reference
+    /// paired with a future one or with the next nearest past one
+    fn skip_mode_allowed(&self) -> bool {
+        let hints: Vec<u32> = (LAST_FRAME..ALTREF_FRAME+1).map(|r| self.ref_frame_order_hint(r)).collect();
+        let forward = hints.iter().cloned()
+            .filter(|&h| relative_dist(h, self.order_hint) < 0)
+            .fold(None, |best, h| match best {
+                Some(b) if relative_dist(h, b) <= 0 => Some(b),
+                _ => Some(h)
+            });
+        match forward {
+            Some(forward) => hints.iter().any(|&h| relative_dist(h, self.order_hint) > 0 ||
+                                                   relative_dist(h, forward) < 0),
+            None => false
         }
     }
 }
@@ -191,7 +519,7 @@ impl fmt::Display for FrameInvariants{
 }
 
 #[allow(dead_code,non_camel_case_types)]
-#[derive(Debug,PartialEq,EnumIterator)]
+#[derive(Debug,Clone,Copy,PartialEq,EnumIterator)]
 pub enum FrameType {
     KEY,
     INTER,
@@ -209,15 +537,16 @@ pub enum ReferenceMode {
   SELECT = 2,
 }
 
-/*const NONE_FRAME: isize = -1;
-const INTRA_FRAME: usize = 0;*/
+// Second reference of blocks without compound prediction
+const NONE_FRAME: usize = 8;
+const INTRA_FRAME: usize = 0;
 const LAST_FRAME: usize = 1;
 
-/*const LAST2_FRAME: usize = 2;
+const LAST2_FRAME: usize = 2;
 const LAST3_FRAME: usize = 3;
 const GOLDEN_FRAME: usize = 4;
 const BWDREF_FRAME: usize = 5;
-const ALTREF2_FRAME: usize = 6;*/
+const ALTREF2_FRAME: usize = 6;
 const ALTREF_FRAME: usize = 7;
 /*const LAST_REF_FRAMES: usize = LAST3_FRAME - LAST_FRAME + 1;
 
@@ -244,285 +573,874 @@ impl fmt::Display for FrameType{
 }
 
 
+/// Encoding settings, independent of the input and output.
+#[derive(Clone,Debug)]
 pub struct EncoderConfig {
-    pub input_file: Box<Read>,
-    pub output_file: Box<Write>,
-    pub rec_file: Option<Box<Write>>,
-    pub limit: u64,
     pub quantizer: usize,
-    pub speed: usize
+    pub speed: usize,
+    pub key_frame_interval: u64,
+    /// Largest number of frames coded after an anchor before showing the
+    /// next one, from 1 to `MAX_MINI_GOP_LEN`. Packets lag behind the input
+    /// by up to `mini_gop_len - 1` frames.
+    pub mini_gop_len: usize,
+    pub bit_depth: usize,
+    /// Log2 of the number of tile columns, each coded independently, up to
+    /// 6. It is raised to the minimum the frame width requires.
+    pub tile_cols_log2: usize,
+    /// Log2 of the number of tile rows, up to 6. It is raised to the minimum
+    /// the frame size requires.
+    pub tile_rows_log2: usize,
+    /// Encode a single intra frame with a reduced still picture header
+    pub still_picture: bool
 }
 
-impl EncoderConfig {
-    pub fn from_cli() -> EncoderConfig {
-        let matches = App::new("rav1e")
-            .version("0.1.0")
-            .about("AV1 video encoder")
-           .arg(Arg::with_name("INPUT")
-                .help("Uncompressed YUV4MPEG2 video input")
-                .required(true)
-                .index(1))
-            .arg(Arg::with_name("OUTPUT")
-                .help("Compressed AV1 in IVF video output")
-                .short("o")
-                .long("output")
-                .required(true)
-                .takes_value(true))
-            .arg(Arg::with_name("RECONSTRUCTION")
-                .short("r")
-                .takes_value(true))
-            .arg(Arg::with_name("LIMIT")
-                .help("Maximum number of frames to encode")
-                .short("l")
-                .long("limit")
-                .takes_value(true)
-                .default_value("0"))
-            .arg(Arg::with_name("QP")
-                .help("Quantizer (0-255)")
-                .long("quantizer")
-                .takes_value(true)
-                .default_value("100"))
-            .arg(Arg::with_name("SPEED")
-                .help("Speed level (0(slow)-10(fast))")
-                .short("s")
-                .long("speed")
-                .takes_value(true)
-                .default_value("3"))
-            .get_matches();
-
+impl Default for EncoderConfig {
+    fn default() -> EncoderConfig {
         EncoderConfig {
-            input_file: match matches.value_of("INPUT").unwrap() {
-                "-" => Box::new(std::io::stdin()) as Box<Read>,
-                f => Box::new(File::open(&f).unwrap()) as Box<Read>
-            },
-            output_file: match matches.value_of("OUTPUT").unwrap() {
-                "-" => Box::new(std::io::stdout()) as Box<Write>,
-                f => Box::new(File::create(&f).unwrap()) as Box<Write>
-            },
-            rec_file: matches.value_of("RECONSTRUCTION").map(|f| {
-                Box::new(File::create(&f).unwrap()) as Box<Write>
-            }),
-            limit: matches.value_of("LIMIT").unwrap().parse().unwrap(),
-            quantizer: matches.value_of("QP").unwrap().parse().unwrap(),
-            speed: matches.value_of("SPEED").unwrap().parse().unwrap()
+            quantizer: 100,
+            speed: 3,
+            key_frame_interval: 30,
+            mini_gop_len: 4,
+            bit_depth: 8,
+            tile_cols_log2: 0,
+            tile_rows_log2: 0,
+            still_picture: false
+        }
+    }
+}
+
+#[derive(Debug,Clone,Copy,PartialEq)]
+pub enum ConfigError {
+    InvalidQuantizer(usize),
+    InvalidSpeed(usize),
+    InvalidKeyFrameInterval(u64),
+    InvalidMiniGopLen(usize),
+    UnsupportedBitDepth(usize),
+    /// Log2 of the tile columns and rows, more than the frame size allows
+    InvalidTiles(usize, usize)
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ConfigError::InvalidQuantizer(q) =>
+                write!(f, "quantizer {} is out of range 1-255", q),
+            ConfigError::InvalidSpeed(s) =>
+                write!(f, "speed {} is out of range 0-10", s),
+            ConfigError::InvalidKeyFrameInterval(k) =>
+                write!(f, "key frame interval {} must be at least 1", k),
+            ConfigError::InvalidMiniGopLen(l) =>
+                write!(f, "mini-GOP length {} is out of range 1-{}", l, MAX_MINI_GOP_LEN),
+            ConfigError::UnsupportedBitDepth(b) =>
+                write!(f, "bit depth {} is not supported", b),
+            ConfigError::InvalidTiles(c, r) =>
+                write!(f, "{}x{} tiles are too many for the frame size", 1 << c, 1 << r),
         }
     }
 }
 
-pub fn write_ivf_header(output_file: &mut Write, width: usize, height: usize, num: usize, den: usize) {
+impl std::error::Error for ConfigError {
+    fn description(&self) -> &str {
+        "invalid encoder configuration"
+    }
+}
+
+/// Errors returned by the encoder and the muxers instead of panicking.
+#[derive(Debug)]
+pub enum EncoderError {
+    /// The settings were rejected by `EncoderConfig::validate`, or the
+    /// tiles by `Encoder::new` for the frame size
+    InvalidConfig(ConfigError),
+    /// The input frames cannot be encoded, e.g. because of their format
+    UnsupportedInput(String),
+    Io(std::io::Error),
+    /// An invariant of the encoder was violated
+    Internal(&'static str)
+}
+
+impl fmt::Display for EncoderError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            EncoderError::InvalidConfig(ref e) =>
+                write!(f, "invalid configuration: {}", e),
+            EncoderError::UnsupportedInput(ref s) =>
+                write!(f, "unsupported input: {}", s),
+            EncoderError::Io(ref e) => write!(f, "I/O error: {}", e),
+            EncoderError::Internal(s) => write!(f, "internal error: {}", s),
+        }
+    }
+}
+
+impl std::error::Error for EncoderError {
+    fn description(&self) -> &str {
+        match *self {
+            EncoderError::InvalidConfig(_) => "invalid encoder configuration",
+            EncoderError::UnsupportedInput(_) => "unsupported input",
+            EncoderError::Io(_) => "I/O error",
+            EncoderError::Internal(_) => "internal encoder error",
+        }
+    }
+
+    fn cause(&self) -> Option<&std::error::Error> {
+        match *self {
+            EncoderError::InvalidConfig(ref e) => Some(e),
+            EncoderError::Io(ref e) => Some(e),
+            _ => None
+        }
+    }
+}
+
+impl From<ConfigError> for EncoderError {
+    fn from(e: ConfigError) -> EncoderError {
+        EncoderError::InvalidConfig(e)
+    }
+}
+
+impl From<std::io::Error> for EncoderError {
+    fn from(e: std::io::Error) -> EncoderError {
+        EncoderError::Io(e)
+    }
+}
+
+impl EncoderConfig {
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        // qindex 0 would signal lossless coding, which needs the Walsh-Hadamard
+        // transform
+        if self.quantizer == 0 || self.quantizer > 255 {
+            return Err(ConfigError::InvalidQuantizer(self.quantizer));
+        }
+        if self.speed > 10 {
+            return Err(ConfigError::InvalidSpeed(self.speed));
+        }
+        if self.key_frame_interval == 0 {
+            return Err(ConfigError::InvalidKeyFrameInterval(self.key_frame_interval));
+        }
+        if self.mini_gop_len == 0 || self.mini_gop_len > MAX_MINI_GOP_LEN {
+            return Err(ConfigError::InvalidMiniGopLen(self.mini_gop_len));
+        }
+        if self.bit_depth != 8 && self.bit_depth != 10 && self.bit_depth != 12 {
+            return Err(ConfigError::UnsupportedBitDepth(self.bit_depth));
+        }
+        if self.tile_cols_log2 > tile_log2(1, MAX_TILE_COLS) ||
+           self.tile_rows_log2 > tile_log2(1, MAX_TILE_ROWS) {
+            return Err(ConfigError::InvalidTiles(self.tile_cols_log2, self.tile_rows_log2));
+        }
+        Ok(())
+    }
+}
+
+pub fn write_ivf_header(output_file: &mut Write, width: usize, height: usize, num: usize, den: usize)
+                        -> Result<(), EncoderError> {
     let mut bw = BitWriter::<LE>::new(output_file);
-    bw.write_bytes(b"DKIF").unwrap();
-    bw.write(16, 0).unwrap(); // version
-    bw.write(16, 32).unwrap(); // version
-    bw.write_bytes(b"AV01").unwrap();
-    bw.write(16, width as u16).unwrap();
-    bw.write(16, height as u16).unwrap();
-    bw.write(32, num as u32).unwrap();
-    bw.write(32, den as u32).unwrap();
-    bw.write(32, 0).unwrap();
-    bw.write(32, 0).unwrap();
-}
-
-pub fn write_ivf_frame(output_file: &mut Write, pts: u64, data: &[u8]) {
+    bw.write_bytes(b"DKIF")?;
+    bw.write(16, 0)?; // version
+    bw.write(16, 32)?; // version
+    bw.write_bytes(b"AV01")?;
+    bw.write(16, width as u16)?;
+    bw.write(16, height as u16)?;
+    bw.write(32, num as u32)?;
+    bw.write(32, den as u32)?;
+    bw.write(32, 0)?;
+    bw.write(32, 0)?;
+    Ok(())
+}
+
+pub fn write_ivf_frame(output_file: &mut Write, pts: u64, data: &[u8])
+                       -> Result<(), EncoderError> {
     let mut bw = BitWriter::<LE>::new(output_file);
-    bw.write(32, data.len() as u32).unwrap();
-    bw.write(64, pts).unwrap();
-    bw.write_bytes(data).unwrap();
+    bw.write(32, data.len() as u32)?;
+    bw.write(64, pts)?;
+    bw.write_bytes(data)?;
+    Ok(())
+}
+
+/// Patch the frame count of an IVF header written by `write_ivf_header`.
+pub fn update_ivf_frame_count(output_file: &mut File, frame_count: u32)
+                              -> Result<(), EncoderError> {
+    let end = output_file.seek(SeekFrom::Current(0))?;
+    output_file.seek(SeekFrom::Start(24))?;
+    {
+        let mut bw = BitWriter::<LE>::new(output_file as &mut Write);
+        bw.write(32, frame_count)?;
+    }
+    output_file.seek(SeekFrom::Start(end))?;
+    Ok(())
+}
+
+/// Destination of the encoded stream
+pub enum Output {
+    Stdout(std::io::Stdout),
+    File(File)
+}
+
+impl Write for Output {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match *self {
+            Output::Stdout(ref mut w) => w.write(buf),
+            Output::File(ref mut w) => w.write(buf)
+        }
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        match *self {
+            Output::Stdout(ref mut w) => w.flush(),
+            Output::File(ref mut w) => w.flush()
+        }
+    }
+}
+
+#[derive(Debug,Clone,Copy,PartialEq)]
+pub enum OutputFormat {
+    IVF,
+    WebM,
+    MP4,
+    AVIF,
+    AnnexB
+}
+
+impl OutputFormat {
+    /// Pick the container from the extension of the output path, IVF being
+    /// the default.
+    pub fn from_path(path: &str) -> OutputFormat {
+        let path = path.to_lowercase();
+        if path.ends_with(".webm") || path.ends_with(".mkv") {
+            OutputFormat::WebM
+        } else if path.ends_with(".mp4") {
+            OutputFormat::MP4
+        } else if path.ends_with(".avif") {
+            OutputFormat::AVIF
+        } else {
+            OutputFormat::IVF
+        }
+    }
+}
+
+/// Container writer receiving the encoded frames
+pub trait Muxer {
+    fn write_frame(&mut self, pts: u64, data: &[u8], frame_type: FrameType)
+                   -> Result<(), EncoderError>;
+    /// Write whatever the container needs once all frames are in.
+    fn finish(&mut self) -> Result<(), EncoderError>;
+}
+
+pub struct IvfMuxer {
+    output: Output,
+    frame_count: u32
+}
+
+impl IvfMuxer {
+    pub fn new(mut output: Output, width: usize, height: usize,
+               num: usize, den: usize) -> Result<IvfMuxer, EncoderError> {
+        write_ivf_header(&mut output, width, height, num, den)?;
+        Ok(IvfMuxer {
+            output,
+            frame_count: 0
+        })
+    }
+}
+
+impl Muxer for IvfMuxer {
+    fn write_frame(&mut self, pts: u64, data: &[u8], _frame_type: FrameType)
+                   -> Result<(), EncoderError> {
+        write_ivf_frame(&mut self.output, pts, data)?;
+        self.frame_count += 1;
+        Ok(())
+    }
+    fn finish(&mut self) -> Result<(), EncoderError> {
+        // The frame count can only be patched in seekable outputs
+        if let Output::File(ref mut f) = self.output {
+            update_ivf_frame_count(f, self.frame_count)?;
+        }
+        self.output.flush()?;
+        Ok(())
+    }
+}
+
+/// Create the muxer for `format`, writing into `output`.
+pub fn new_muxer(output: Output, format: OutputFormat, sequence: &Sequence,
+                 num: usize, den: usize) -> Result<Box<Muxer>, EncoderError> {
+    let width = sequence.max_frame_width as usize;
+    let height = sequence.max_frame_height as usize;
+    if num == 0 || den == 0 {
+        return Err(EncoderError::UnsupportedInput(
+            format!("frame rate {}/{} is invalid", num, den)));
+    }
+    Ok(match format {
+        OutputFormat::IVF =>
+            Box::new(IvfMuxer::new(output, width, height, num, den)?),
+        OutputFormat::WebM =>
+            Box::new(webm::WebMMuxer::new(output, sequence, num, den)?),
+        OutputFormat::MP4 =>
+            Box::new(mp4::Mp4Muxer::new(output, sequence, num, den)?),
+        OutputFormat::AVIF =>
+            Box::new(avif::AvifMuxer::new(output, sequence)?),
+        OutputFormat::AnnexB =>
+            Box::new(annexb::AnnexBMuxer::new(output))
+    })
+}
+
+#[allow(dead_code,non_camel_case_types)]
+#[derive(Debug,Clone,Copy,PartialEq)]
+pub enum OBU_Type {
+    OBU_SEQUENCE_HEADER = 1,
+    OBU_TEMPORAL_DELIMITER = 2,
+    OBU_FRAME_HEADER = 3,
+    OBU_TILE_GROUP = 4,
+    OBU_METADATA = 5,
+    OBU_FRAME = 6,
+    OBU_REDUNDANT_FRAME_HEADER = 7,
+    OBU_TILE_LIST = 8,
+    OBU_PADDING = 15,
+}
+
+const PRIMARY_REF_NONE: u32 = 7;
+const ORDER_HINT_BITS: u32 = 7;
+const NUM_REF_FRAMES: usize = 8;
+const REFS_PER_FRAME: usize = 7;
+const ALL_REF_FRAMES_MASK: u32 = (1 << NUM_REF_FRAMES) - 1;
+const MAX_TILE_WIDTH: usize = 4096;
+const MAX_TILE_AREA: usize = 4096 * 2304;
+const MAX_TILE_ROWS: usize = 64;
+const MAX_TILE_COLS: usize = 64;
+/// Bytes of the size preceding each tile but the last in a tile group
+const TILE_SIZE_BYTES: usize = 4;
+
+/// Smallest k such that `blk_size << k` is at least `target`
+fn tile_log2(blk_size: usize, target: usize) -> usize {
+    let mut k = 0;
+    while (blk_size << k) < target {
+        k += 1;
+    }
+    k
+}
+
+/// Bounds on the log2 of the number of tile columns and rows of a frame of
+/// `sb_cols`x`sb_rows` superblocks, split with uniform tile spacing
+struct TileLimits {
+    min_log2_cols: usize,
+    max_log2_cols: usize,
+    min_log2_tiles: usize,
+    max_log2_rows: usize
+}
+
+impl TileLimits {
+    fn new(sb_cols: usize, sb_rows: usize) -> TileLimits {
+        let sb_size_log2 = 6;
+        let max_tile_width_sb = MAX_TILE_WIDTH >> sb_size_log2;
+        let max_tile_area_sb = MAX_TILE_AREA >> (2 * sb_size_log2);
+        let min_log2_cols = tile_log2(max_tile_width_sb, sb_cols);
+        TileLimits {
+            min_log2_cols,
+            max_log2_cols: tile_log2(1, std::cmp::min(sb_cols, MAX_TILE_COLS)),
+            min_log2_tiles: std::cmp::max(min_log2_cols,
+                                          tile_log2(max_tile_area_sb, sb_rows * sb_cols)),
+            max_log2_rows: tile_log2(1, std::cmp::min(sb_rows, MAX_TILE_ROWS))
+        }
+    }
+
+    /// Minimum log2 of the tile rows with 1 << `log2_cols` tile columns
+    fn min_log2_rows(&self, log2_cols: usize) -> usize {
+        self.min_log2_tiles.saturating_sub(log2_cols)
+    }
+}
+
+/// Signed distance from the frame of order hint `b` to the frame of order
+/// hint `a`, as order hints wrap around
+fn relative_dist(a: u32, b: u32) -> i32 {
+    let m = 1 << (ORDER_HINT_BITS - 1);
+    let diff = a as i32 - b as i32;
+    (diff & (m - 1)) - (diff & m)
 }
 
 trait UncompressedHeader {
-    fn write_frame_size(&mut self, fi: &FrameInvariants) -> Result<(), std::io::Error>;
-    fn write_sequence_header(&mut self, fi: &FrameInvariants)
-                                    -> Result<(), std::io::Error>;
-    fn write_bitdepth_colorspace_sampling(&mut self) -> Result<(), std::io::Error>;
-    fn write_frame_setup(&mut self) -> Result<(), std::io::Error>;
+    // Start of OBU Headers
+    fn write_obu_header(&mut self, obu_type: OBU_Type, obu_extension: u32)
+                        -> Result<(), std::io::Error>;
+    fn write_uleb128(&mut self, value: u64) -> Result<(), std::io::Error>;
+    fn write_uvlc(&mut self, value: u32) -> Result<(), std::io::Error>;
+    fn write_sequence_header_obu(&mut self, seq: &Sequence)
+                                 -> Result<(), EncoderError>;
+    fn write_frame_header_obu(&mut self, seq: &Sequence, fi: &FrameInvariants)
+                              -> Result<(), EncoderError>;
+    fn write_timing_info(&mut self, timing_info: &TimingInfo)
+                         -> Result<(), EncoderError>;
+    fn write_color_config(&mut self, seq: &Sequence) -> Result<(), EncoderError>;
+    fn write_trailing_bits(&mut self) -> Result<(), std::io::Error>;
+    // End of OBU Headers
+
+    fn write_frame_size(&mut self) -> Result<(), std::io::Error>;
+    fn write_render_size(&mut self) -> Result<(), std::io::Error>;
+    fn write_tile_info(&mut self, fi: &FrameInvariants) -> Result<(), EncoderError>;
+    fn write_quantization(&mut self, fi: &FrameInvariants) -> Result<(), std::io::Error>;
     fn write_loop_filter(&mut self) -> Result<(), std::io::Error>;
-    fn write_cdef(&mut self) -> Result<(), std::io::Error>;
+    fn write_global_motion(&mut self, fi: &FrameInvariants) -> Result<(), std::io::Error>;
 }
 
 impl<'a> UncompressedHeader for BitWriter<'a, BE> {
-    fn write_frame_size(&mut self, fi: &FrameInvariants) -> Result<(), std::io::Error> {
-        // width_bits and height_bits will have to be moved to the sequence header OBU
-        // when we add support for it.
-        let width_bits = 32 - (fi.width as u32).leading_zeros();
-        let height_bits = 32 - (fi.height as u32).leading_zeros();
-        assert!(width_bits <= 16);
-        assert!(height_bits <= 16);
-        self.write(4, width_bits - 1)?;
-        self.write(4, height_bits - 1)?;
-        self.write(width_bits, (fi.width - 1) as u16)?;
-        self.write(height_bits, (fi.height - 1) as u16)?;
+    fn write_obu_header(&mut self, obu_type: OBU_Type, obu_extension: u32)
+                        -> Result<(), std::io::Error> {
+        self.write_bit(false)?; // forbidden bit
+        self.write(4, obu_type as u32)?;
+        self.write_bit(obu_extension != 0)?;
+        self.write_bit(true)?; // obu_has_payload_length_field
+        self.write_bit(false)?; // reserved
+
+        if obu_extension != 0 {
+            self.write(8, obu_extension & 0xFF)?;
+        }
+
         Ok(())
     }
-    fn write_sequence_header(&mut self, fi: &FrameInvariants)
-        -> Result<(), std::io::Error> {
-        self.write_frame_size(fi)?;
-        self.write(1,0)?; // don't use frame ids
-        self.write(1,0)?; // screen content tools forced
-        self.write(1,0)?; // screen content tools forced off
-        Ok(())
+    fn write_uleb128(&mut self, value: u64) -> Result<(), std::io::Error> {
+        let mut v = value;
+        loop {
+            let byte = (v & 0x7F) as u8;
+            v >>= 7;
+            if v == 0 {
+                self.write(8, byte)?;
+                return Ok(());
+            }
+            self.write(8, byte | 0x80)?; // more bytes follow
+        }
     }
-    fn write_bitdepth_colorspace_sampling(&mut self) -> Result<(), std::io::Error> {
-        self.write(1,0)?; // 8 bit video
-        self.write(1,0)?; // not monochrome
-        self.write(4,0)?; // colorspace
-        self.write(1,0)?; // color range
+    fn write_uvlc(&mut self, value: u32) -> Result<(), std::io::Error> {
+        let v = value as u64 + 1;
+        let leading_zeros = 63 - v.leading_zeros();
+        for _ in 0..leading_zeros {
+            self.write_bit(false)?;
+        }
+        self.write_bit(true)?;
+        if leading_zeros > 0 {
+            self.write(leading_zeros, (v - (1 << leading_zeros)) as u32)?;
+        }
         Ok(())
     }
-    fn write_frame_setup(&mut self) -> Result<(), std::io::Error> {
+    fn write_sequence_header_obu(&mut self, seq: &Sequence)
+                                 -> Result<(), EncoderError> {
+        self.write(3, seq.profile)?; // profile
+        self.write_bit(seq.still_picture)?;
+        self.write_bit(seq.reduced_still_picture_hdr)?;
+
+        if seq.reduced_still_picture_hdr {
+            if !seq.still_picture || seq.timing_info.is_some() {
+                return Err(EncoderError::Internal("reduced still picture header for a sequence"));
+            }
+            self.write(5, seq.level)?; // seq_level_idx
+        } else {
+            self.write_bit(seq.timing_info.is_some())?; // timing_info_present_flag
+            if let Some(ref timing_info) = seq.timing_info {
+                self.write_timing_info(timing_info)?;
+                self.write_bit(false)?; // no decoder model info
+            }
+            self.write_bit(false)?; // no initial display delay
+            self.write(5, 0)?; // one operating point
+            self.write(12, 0)?; // operating_point_idc
+            self.write(5, seq.level)?; // seq_level_idx
+            if seq.level > 7 {
+                self.write(1, seq.tier)?; // seq_tier
+            }
+        }
+
+        self.write(4, seq.num_bits_width - 1)?;
+        self.write(4, seq.num_bits_height - 1)?;
+        self.write(seq.num_bits_width, (seq.max_frame_width - 1) as u32)?;
+        self.write(seq.num_bits_height, (seq.max_frame_height - 1) as u32)?;
+
+        if !seq.reduced_still_picture_hdr {
+            self.write_bit(false)?; // don't use frame ids
+        }
+        self.write_bit(false)?; // 64x64 superblocks
+        self.write_bit(false)?; // no filter intra
+        self.write_bit(false)?; // no intra edge filter
+        if !seq.reduced_still_picture_hdr {
+            self.write_bit(false)?; // no interintra compound
+            self.write_bit(false)?; // no masked compound
+            self.write_bit(false)?; // no warped motion
+            self.write_bit(false)?; // no dual filter
+            self.write_bit(true)?; // order hint
+            self.write_bit(false)?; // no distance weighted compound
+            self.write_bit(true)?; // reference frame motion vectors
+            self.write_bit(false)?; // don't choose screen content tools
+            self.write_bit(false)?; // screen content tools forced off
+            self.write(3, ORDER_HINT_BITS - 1)?; // order_hint_bits_minus_1
+        }
         self.write_bit(false)?; // no superres
-        self.write_bit(false)?; // scaling active
+        self.write_bit(false)?; // no cdef
+        self.write_bit(false)?; // no loop restoration
+
+        self.write_color_config(seq)?;
+
+        self.write_bit(false)?; // no film grain
+
+        self.write_trailing_bits()?;
         Ok(())
     }
-    fn write_loop_filter(&mut self) -> Result<(), std::io::Error> {
-        self.write(6,0)?; // loop filter level 0
-        self.write(6,0)?; // loop filter level 1
-        self.write(3,0)?; // loop filter sharpness
-        self.write_bit(false) // loop filter deltas enabled
-    }
-    fn write_cdef(&mut self) -> Result<(), std::io::Error> {
-        self.write(2,0)?; // cdef clpf damping
-        self.write(2,0)?; // cdef bits
-        for _ in 0..1 {
-            self.write(6,0)?; // cdef y strength
-            self.write(6,0)?; // cdef uv strength
+    fn write_timing_info(&mut self, timing_info: &TimingInfo)
+                         -> Result<(), EncoderError> {
+        self.write(32, timing_info.num_units_in_display_tick)?;
+        self.write(32, timing_info.time_scale)?;
+        self.write_bit(timing_info.num_ticks_per_picture.is_some())?; // equal_picture_interval
+        if let Some(num_ticks_per_picture) = timing_info.num_ticks_per_picture {
+            if num_ticks_per_picture == 0 {
+                return Err(EncoderError::Internal("picture lasting no ticks"));
+            }
+            self.write_uvlc(num_ticks_per_picture - 1)?;
         }
         Ok(())
     }
-}
+    fn write_color_config(&mut self, seq: &Sequence) -> Result<(), EncoderError> {
+        let high_bitdepth = seq.bit_depth > 8;
+        self.write_bit(high_bitdepth)?;
+        if seq.profile == 2 && high_bitdepth {
+            self.write_bit(seq.bit_depth == 12)?; // twelve_bit
+        }
 
-fn write_uncompressed_header(packet: &mut Write, sequence: &Sequence,
-                            fi: &FrameInvariants) -> Result<(), std::io::Error> {
-    let mut bw = BitWriter::<BE>::new(packet);
-    bw.write(2,2)?; // AOM_FRAME_MARKER, 0x2
-    bw.write(2,sequence.profile)?; // profile 0
-    if fi.show_existing_frame {
-        bw.write_bit(true)?; // show_existing_frame=1
-        bw.write(3,0)?; // show last frame
-        bw.byte_align()?;
-        return Ok(());
-    }
-    bw.write_bit(false)?; // show_existing_frame=0
-    bw.write_bit(fi.frame_type == FrameType::INTER)?; // keyframe : 0, inter: 1
-    bw.write_bit(fi.show_frame)?; // show frame
-    /*
-    if fi.intra_only {
-        bw.write_bit(true)?; // disable intra edge
-    }
-    */
-    if fi.frame_type == FrameType::KEY || fi.frame_type == FrameType::INTRA_ONLY {
-        assert!(fi.intra_only);
-    }
-    if fi.frame_type != FrameType::KEY {
-        if fi.show_frame { assert!(!fi.intra_only); }
-        else { bw.write_bit( fi.intra_only )?; };
-    };
-    bw.write_bit(fi.error_resilient)?; // error resilient
+        let mono_chrome = seq.chroma_sampling == ChromaSampling::CS400;
+        if seq.profile == 1 {
+            if mono_chrome {
+                return Err(EncoderError::Internal("monochrome in the High profile"));
+            }
+        } else {
+            self.write_bit(mono_chrome)?;
+        }
+
+        let color_description_present = seq.color_description_present();
+        self.write_bit(color_description_present)?;
+        if color_description_present {
+            self.write(8, seq.color_primaries as u8)?;
+            self.write(8, seq.transfer_characteristics as u8)?;
+            self.write(8, seq.matrix_coefficients as u8)?;
+        }
+
+        if mono_chrome {
+            self.write_bit(seq.pixel_range == PixelRange::FULL)?; // color_range
+            return Ok(());
+        }
+
+        let invalid_sampling = EncoderError::Internal("chroma sampling not allowed by the profile");
+
+        if seq.color_primaries == ColorPrimaries::BT709 &&
+           seq.transfer_characteristics == TransferCharacteristics::SRGB &&
+           seq.matrix_coefficients == MatrixCoefficients::IDENTITY {
+            // sRGB is implicitly full range 4:4:4
+            if seq.chroma_sampling != ChromaSampling::CS444 {
+                return Err(invalid_sampling);
+            }
+        } else {
+            self.write_bit(seq.pixel_range == PixelRange::FULL)?; // color_range
+            match seq.profile {
+                0 => if seq.chroma_sampling != ChromaSampling::CS420 {
+                    return Err(invalid_sampling);
+                },
+                1 => if seq.chroma_sampling != ChromaSampling::CS444 {
+                    return Err(invalid_sampling);
+                },
+                _ => {
+                    if seq.bit_depth == 12 {
+                        let subsampling_x = seq.chroma_sampling != ChromaSampling::CS444;
+                        self.write_bit(subsampling_x)?;
+                        if subsampling_x {
+                            self.write_bit(seq.chroma_sampling == ChromaSampling::CS420)?; // subsampling_y
+                        }
+                    } else if seq.chroma_sampling != ChromaSampling::CS422 {
+                        return Err(invalid_sampling);
+                    }
+                }
+            }
+            if seq.chroma_sampling == ChromaSampling::CS420 {
+                self.write(2, seq.chroma_sample_position as u32)?;
+            }
+        }
 
-    if fi.frame_type == FrameType::KEY || fi.intra_only {
-        bw.write_sequence_header(fi)?;
+        self.write_bit(false)?; // no separate uv delta q
+        Ok(())
     }
+    fn write_frame_header_obu(&mut self, seq: &Sequence, fi: &FrameInvariants)
+                              -> Result<(), EncoderError> {
+        let frame_is_intra = fi.frame_type == FrameType::KEY ||
+                             fi.frame_type == FrameType::INTRA_ONLY;
+        if frame_is_intra != fi.intra_only {
+            return Err(EncoderError::Internal("intra_only does not match the frame type"));
+        }
+
+        if seq.reduced_still_picture_hdr {
+            // A single shown keyframe is implied
+            if fi.show_existing_frame || fi.frame_type != FrameType::KEY || !fi.show_frame {
+                return Err(EncoderError::Internal("still picture frame is not a shown keyframe"));
+            }
+        } else {
+            self.write_bit(fi.show_existing_frame)?;
+            if fi.show_existing_frame {
+                self.write(3, fi.frame_to_show_map_idx as u32)?;
+                self.write_trailing_bits()?;
+                return Ok(());
+            }
+
+            self.write(2, fi.frame_type as u32)?;
+            self.write_bit(fi.show_frame)?;
+            if !fi.show_frame {
+                self.write_bit(true)?; // showable_frame
+            }
+        }
+        let error_resilient = fi.frame_type == FrameType::S ||
+            (fi.frame_type == FrameType::KEY && fi.show_frame) ||
+            fi.error_resilient;
+        if fi.frame_type != FrameType::S &&
+            !(fi.frame_type == FrameType::KEY && fi.show_frame) {
+            self.write_bit(error_resilient)?;
+        }
+        if fi.use_prev_frame_mvs && (frame_is_intra || error_resilient) {
+            return Err(EncoderError::Internal("reference frame motion vectors in an error resilient frame"));
+        }
 
-    //bw.write(8+7,0)?; // frame id
+        self.write_bit(false)?; // don't disable cdf update
+        if seq.reduced_still_picture_hdr {
+            // the reduced sequence header lets each frame select them
+            self.write_bit(false)?; // no screen content tools
+        }
+        // otherwise allow_screen_content_tools is forced off by the sequence header
+        if fi.frame_type != FrameType::S && !seq.reduced_still_picture_hdr {
+            self.write_bit(false)?; // no frame size override
+        }
+        // order hints are enabled by all but the reduced sequence header
+        let enable_order_hint = !seq.reduced_still_picture_hdr;
+        if enable_order_hint {
+            self.write(ORDER_HINT_BITS, fi.order_hint)?;
+        }
 
-    bw.write_bit(false)?; // no override frame size
+        // CDFs and global motion are never loaded from a reference
+        if !(frame_is_intra || error_resilient) {
+            self.write(3, PRIMARY_REF_NONE)?;
+        }
+
+        if !(fi.frame_type == FrameType::S ||
+             (fi.frame_type == FrameType::KEY && fi.show_frame)) {
+            if fi.frame_type == FrameType::INTRA_ONLY &&
+               fi.refresh_frame_flags == ALL_REF_FRAMES_MASK {
+                return Err(EncoderError::Internal("intra-only frame refreshing every slot"));
+            }
+            self.write(8, fi.refresh_frame_flags)?;
+        }
+        if (!frame_is_intra || fi.refresh_frame_flags != ALL_REF_FRAMES_MASK) &&
+           error_resilient && enable_order_hint {
+            for &order_hint in fi.ref_order_hint.iter() {
+                self.write(ORDER_HINT_BITS, order_hint)?; // ref_order_hint
+            }
+        }
 
-    if fi.frame_type == FrameType::KEY {
-        bw.write_bitdepth_colorspace_sampling()?;
-        bw.write(1,0)?; // separate uv delta q
-        bw.write_frame_setup()?;
-    } else { // Inter frame info goes here
-        if fi.intra_only {
-            bw.write_bitdepth_colorspace_sampling()?;
-            bw.write(1,0)?; // separate uv delta q
-            bw.write(8,0)?; // refresh_frame_flags
-            bw.write_frame_setup()?;
+        if frame_is_intra {
+            self.write_frame_size()?;
+            self.write_render_size()?;
         } else {
-            bw.write(8,0)?; // refresh_frame_flags
-            // TODO: More Inter frame info goes here
-            for _ in 0..7 {
-                bw.write(3,0)?; // dummy ref_frame = 0 until real MC happens
+            if enable_order_hint {
+                self.write_bit(false)?; // no short signaling of the references
+            }
+            for &i in fi.ref_frame_idx.iter() {
+                self.write(3, i as u32)?;
             }
-            bw.write_frame_setup()?;
-            bw.write_bit(fi.allow_high_precision_mv)?;
-            bw.write_bit(false)?; // frame_interp_filter is NOT switchable
-            bw.write(2,0)?;	// EIGHTTAP_REGULAR
-            if !fi.intra_only && !fi.error_resilient {
-                bw.write_bit(false)?; // do not use_ref_frame_mvs
+            self.write_frame_size()?;
+            self.write_render_size()?;
+            self.write_bit(fi.allow_high_precision_mv)?;
+            self.write_bit(false)?; // frame_interp_filter is NOT switchable
+            self.write(2, 0)?; // EIGHTTAP_REGULAR
+            self.write_bit(false)?; // no switchable motion mode
+            if !error_resilient {
+                self.write_bit(fi.use_prev_frame_mvs)?; // use_ref_frame_mvs
             }
         }
-    };
 
+        if !seq.reduced_still_picture_hdr {
+            self.write_bit(true)?; // disable frame end update cdf
+        }
 
-    bw.write(3,0x0)?; // frame context
-    bw.write_loop_filter()?;
-    bw.write(8,fi.qindex as u8)?; // qindex
-    bw.write_bit(false)?; // y dc delta q
-    bw.write_bit(false)?; // uv dc delta q
-    bw.write_bit(false)?; // uv ac delta q
-    bw.write_bit(false)?; // no qm
-    bw.write_bit(false)?; // segmentation off
-    bw.write_bit(false)?; // no delta q
-    bw.write_cdef()?;
-    bw.write(6,0)?; // no y, u or v loop restoration
-    bw.write_bit(false)?; // tx mode select
+        self.write_tile_info(fi)?;
+        self.write_quantization(fi)?;
+        self.write_bit(false)?; // segmentation off
+        if fi.qindex > 0 {
+            self.write_bit(false)?; // no delta q
+        }
 
-    //fi.reference_mode = ReferenceMode::SINGLE;
+        // a frame coded with qindex 0 is lossless and has no loop filter
+        if fi.qindex > 0 {
+            self.write_loop_filter()?;
+        }
+        // cdef and loop restoration are disabled by the sequence header
 
-    if fi.reference_mode != ReferenceMode::SINGLE {
-        // setup_compound_reference_mode();
-    }
+        if fi.qindex > 0 {
+            self.write_bit(false)?; // tx mode select
+        }
 
-    if !fi.intra_only {
-        bw.write_bit(false)?; } // do not use inter_intra
-    if !fi.intra_only && fi.reference_mode != ReferenceMode::SINGLE {
-        bw.write_bit(false)?; } // do not allow_masked_compound
+        if !frame_is_intra {
+            let reference_select = fi.reference_mode != ReferenceMode::SINGLE;
+            self.write_bit(reference_select)?;
+            if reference_select && enable_order_hint && fi.skip_mode_allowed() {
+                self.write_bit(false)?; // skip mode not present
+            }
+        }
+        // warped motion is disabled by the sequence header
+
+        self.write_bit(fi.use_reduced_tx_set)?; // reduced tx
 
-    bw.write_bit(fi.use_reduced_tx_set)?; // reduced tx
+        if !frame_is_intra {
+            self.write_global_motion(fi)?;
+        }
 
-    if !fi.intra_only {
+        self.write_trailing_bits()?;
+        Ok(())
+    }
+    fn write_trailing_bits(&mut self) -> Result<(), std::io::Error> {
+        self.write_bit(true)?; // trailing one bit
+        self.byte_align()
+    }
+    fn write_frame_size(&mut self) -> Result<(), std::io::Error> {
+        // frame size comes from the sequence header without an override
+        // superres is disabled by the sequence header
+        Ok(())
+    }
+    fn write_render_size(&mut self) -> Result<(), std::io::Error> {
+        self.write_bit(false) // render size same as frame size
+    }
+    fn write_tile_info(&mut self, fi: &FrameInvariants) -> Result<(), EncoderError> {
+        let limits = TileLimits::new(fi.sb_width, fi.sb_height);
+        let min_log2_tile_rows = limits.min_log2_rows(fi.tile_cols_log2);
+        if fi.tile_cols_log2 < limits.min_log2_cols || fi.tile_cols_log2 > limits.max_log2_cols ||
+           fi.tile_rows_log2 < min_log2_tile_rows || fi.tile_rows_log2 > limits.max_log2_rows {
+            return Err(EncoderError::Internal("tiles out of range for the frame size"));
+        }
+
+        self.write_bit(true)?; // uniform tile spacing
+        // tile_cols_log2 and tile_rows_log2 are incremented from their
+        // minimum, up to their maximum
+        for _ in limits.min_log2_cols..fi.tile_cols_log2 {
+            self.write_bit(true)?; // increment_tile_cols_log2
+        }
+        if fi.tile_cols_log2 < limits.max_log2_cols {
+            self.write_bit(false)?;
+        }
+        for _ in min_log2_tile_rows..fi.tile_rows_log2 {
+            self.write_bit(true)?; // increment_tile_rows_log2
+        }
+        if fi.tile_rows_log2 < limits.max_log2_rows {
+            self.write_bit(false)?;
+        }
+        if fi.tile_cols_log2 + fi.tile_rows_log2 > 0 {
+            // the CDFs are not saved at the end of the frame
+            self.write((fi.tile_cols_log2 + fi.tile_rows_log2) as u32, 0)?; // context_update_tile_id
+            self.write(2, TILE_SIZE_BYTES as u32 - 1)?; // tile_size_bytes_minus_1
+        }
+        Ok(())
+    }
+    fn write_quantization(&mut self, fi: &FrameInvariants) -> Result<(), std::io::Error> {
+        self.write(8, fi.qindex as u8)?; // base_q_idx
+        self.write_bit(false)?; // y dc delta q
+        if fi.chroma_sampling != ChromaSampling::CS400 {
+            self.write_bit(false)?; // uv dc delta q
+            self.write_bit(false)?; // uv ac delta q
+        }
+        self.write_bit(false) // no qm
+    }
+    fn write_loop_filter(&mut self) -> Result<(), std::io::Error> {
+        self.write(6,0)?; // loop filter level 0
+        self.write(6,0)?; // loop filter level 1
+        self.write(3,0)?; // loop filter sharpness
+        self.write_bit(false) // loop filter deltas enabled
+    }
+    fn write_global_motion(&mut self, fi: &FrameInvariants) -> Result<(), std::io::Error> {
+        // Without a primary reference frame, the decoder predicts the
+        // parameters of every reference from the defaults
         for i in LAST_FRAME..ALTREF_FRAME+1 {
             let mode = fi.globalmv_transformation_type[i];
-            bw.write_bit(mode != GlobalMVMode::IDENTITY)?;
+            self.write_bit(mode != GlobalMVMode::IDENTITY)?;
             if mode != GlobalMVMode::IDENTITY {
-                bw.write_bit(mode == GlobalMVMode::ROTZOOM)?;
+                self.write_bit(mode == GlobalMVMode::ROTZOOM)?;
                 if mode != GlobalMVMode::ROTZOOM {
-                    bw.write_bit(mode == GlobalMVMode::TRANSLATION)?;
+                    self.write_bit(mode == GlobalMVMode::TRANSLATION)?;
                 }
             }
-            match mode {
-                GlobalMVMode::IDENTITY => { /* Nothing to do */ }
-                GlobalMVMode::TRANSLATION => {
-                    let mv_x = 0;
-                    let mv_x_ref = 0;
-                    let mv_y = 0;
-                    let mv_y_ref = 0;
-                    let bits = 12 - 6 + 3 - !fi.allow_high_precision_mv as u8;
-                    let bits_diff = 12 - 3 + fi.allow_high_precision_mv as u8;
-                    BCodeWriter::write_s_refsubexpfin(&mut bw, (1 << bits) + 1,
-                                                      3, mv_x_ref >> bits_diff,
-                                                      mv_x >> bits_diff)?;
-                    BCodeWriter::write_s_refsubexpfin(&mut bw, (1 << bits) + 1,
-                                                      3, mv_y_ref >> bits_diff,
-                                                      mv_y >> bits_diff)?;
-                }
-                GlobalMVMode::ROTZOOM => unimplemented!(),
-                GlobalMVMode::AFFINE => unimplemented!(),
-            };
+            for &idx in gm_coded_params(mode) {
+                let hp = fi.allow_high_precision_mv;
+                let (abs_bits, _) = gm_param_bits(mode, idx, hp);
+                let r = gm_coded_value(&DEFAULT_GM_PARAMS, mode, idx, hp);
+                let v = gm_coded_value(&fi.gm_params[i], mode, idx, hp);
+                BCodeWriter::write_s_refsubexpfin(self, (1 << abs_bits) + 1, 3,
+                                                  r as i16, v as i16)?;
+            }
         }
+        Ok(())
+    }
+}
+
+/// Write a complete OBU, with its size field, wrapping `payload`.
+fn write_obu(packet: &mut Write, obu_type: OBU_Type, payload: &[u8])
+             -> Result<(), std::io::Error> {
+    let mut bw = BitWriter::<BE>::new(packet);
+    bw.write_obu_header(obu_type, 0)?;
+    bw.write_uleb128(payload.len() as u64)?;
+    bw.write_bytes(payload)
+}
+
+/// Sequence header OBU, including its OBU header and size.
+fn sequence_header_obu(sequence: &Sequence) -> Result<Vec<u8>, EncoderError> {
+    let mut buf = Vec::new();
+    {
+        let mut bw = BitWriter::<BE>::new(&mut buf);
+        bw.write_sequence_header_obu(sequence)?;
+    }
+    let mut obu = Vec::new();
+    write_obu(&mut obu, OBU_Type::OBU_SEQUENCE_HEADER, &buf)?;
+    Ok(obu)
+}
+
+/// AV1CodecConfigurationRecord (av1C) describing `sequence`, as carried by
+/// the Matroska and ISOBMFF containers.
+fn av1c_record(sequence: &Sequence) -> Result<Vec<u8>, EncoderError> {
+    let mut record = Vec::new();
+    {
+        let mut bw = BitWriter::<BE>::new(&mut record);
+        bw.write_bit(true).unwrap(); // marker
+        bw.write(7, 1).unwrap(); // version
+        bw.write(3, sequence.profile).unwrap();
+        bw.write(5, sequence.level).unwrap();
+        bw.write(1, sequence.tier).unwrap();
+        bw.write_bit(sequence.bit_depth > 8).unwrap(); // high_bitdepth
+        bw.write_bit(sequence.bit_depth == 12).unwrap(); // twelve_bit
+        let (xdec, ydec) = sequence.chroma_sampling.get_decimation();
+        bw.write_bit(sequence.chroma_sampling == ChromaSampling::CS400).unwrap(); // mono_chrome
+        bw.write_bit(xdec == 1).unwrap(); // chroma_subsampling_x
+        bw.write_bit(ydec == 1).unwrap(); // chroma_subsampling_y
+        bw.write(2, sequence.chroma_sample_position as u8).unwrap();
+        bw.write(3, 0).unwrap(); // reserved
+        bw.write_bit(false).unwrap(); // no initial_presentation_delay
+        bw.write(4, 0).unwrap(); // reserved
     }
+    record.extend_from_slice(&sequence_header_obu(sequence)?);
+    Ok(record)
+}
+
+/// Strip the temporal delimiter that leads every packet, since containers
+/// signal temporal unit boundaries themselves.
+fn strip_temporal_delimiter(data: &[u8]) -> &[u8] {
+    let td = [(OBU_Type::OBU_TEMPORAL_DELIMITER as u8) << 3 | 0x2, 0];
+    if data.starts_with(&td) {
+        &data[td.len()..]
+    } else {
+        data
+    }
+}
 
-    bw.write_bit(true)?; // uniform tile spacing
-    if fi.width > 64 {
-        bw.write(1,0)?; // tile cols
+/// Write the OBUs preceding the tile data of a frame: a sequence header on
+/// keyframes and the frame header.
+fn write_obus(packet: &mut Write, sequence: &Sequence,
+              fi: &FrameInvariants) -> Result<(), EncoderError> {
+    if fi.frame_type == FrameType::KEY && !fi.show_existing_frame {
+        packet.write_all(&sequence_header_obu(sequence)?)?;
     }
-    if fi.height > 64 {
-        bw.write(1,0)?; // tile rows
+
+    let mut buf = Vec::new();
+    {
+        let mut bw = BitWriter::<BE>::new(&mut buf);
+        bw.write_frame_header_obu(sequence, fi)?;
     }
-    // if tile_cols * tile_rows > 1
-    //.write_bit(true)?; // loop filter across tiles
-    bw.write(2,3)?; // tile_size_bytes
-    bw.byte_align()?;
+    write_obu(packet, OBU_Type::OBU_FRAME_HEADER, &buf)?;
     Ok(())
 }
 
@@ -544,7 +1462,10 @@ pub fn encode_tx_block(fi: &FrameInvariants, fs: &mut FrameState, cw: &mut Conte
     let rec = &mut fs.rec.planes[p];
     let PlaneConfig { stride, xdec, ydec } = fs.input.planes[p].cfg;
 
-    mode.predict(&mut rec.mut_slice(po), tx_size);
+    if mode.is_intra() {
+        mode.predict(&mut rec.mut_slice(po), tx_size, fi.bit_depth,
+                     cw.bc.has_left(bo), cw.bc.has_above(bo));
+    }
 
     if skip { return; }
 
@@ -561,28 +1482,51 @@ pub fn encode_tx_block(fi: &FrameInvariants, fs: &mut FrameState, cw: &mut Conte
 
 
     forward_transform(&residual.array, coeffs, tx_size.width(), tx_size, tx_type);
-    quantize_in_place(fi.qindex, coeffs, tx_size);
+    quantize_in_place(fi.qindex, coeffs, tx_size, fi.bit_depth);
 
     cw.write_coeffs_lv_map(p, bo, &coeffs, tx_size, tx_type, plane_bsize, xdec, ydec,
                             fi.use_reduced_tx_set);
 
     // Reconstruct
-    dequantize(fi.qindex, &coeffs, &mut rcoeffs.array, tx_size);
+    dequantize(fi.qindex, &coeffs, &mut rcoeffs.array, tx_size, fi.bit_depth);
 
-    inverse_transform_add(&rcoeffs.array, &mut rec.mut_slice(po).as_mut_slice(), stride, tx_size, tx_type);
+    inverse_transform_add(&rcoeffs.array, &mut rec.mut_slice(po).as_mut_slice(), stride, tx_size, tx_type, fi.bit_depth);
 }
 
 fn encode_block(fi: &FrameInvariants, fs: &mut FrameState, cw: &mut ContextWriter,
             luma_mode: PredictionMode, chroma_mode: PredictionMode,
+            ref_frames: [usize; 2], mvs: [MotionVector; 2],
             bsize: BlockSize, bo: &BlockOffset, skip: bool) {
-    let is_inter = luma_mode >= PredictionMode::NEARESTMV;
+    let is_inter = !luma_mode.is_intra();
 
     cw.bc.set_skip(bo, bsize, skip);
     cw.write_skip(bo, skip);
 
     if fi.frame_type == FrameType::INTER {
         cw.write_is_inter(bo, is_inter);
-        if !is_inter {
+        if is_inter {
+            let reference_select = fi.reference_mode == ReferenceMode::SELECT;
+            cw.write_ref_frames(bo, bsize, reference_select, ref_frames);
+            let mv_stack = cw.bc.find_mv_stack(fi, bo, bsize, ref_frames);
+            if luma_mode >= PredictionMode::NEAREST_NEARESTMV {
+                cw.write_compound_mode(luma_mode, mv_stack.mode_context);
+            } else {
+                cw.write_inter_mode(luma_mode, mv_stack.mode_context);
+            }
+            // NEWMV modes are predicted from the first candidate, and NEARMV
+            // modes use the second one
+            let is_newmv = luma_mode == PredictionMode::NEWMV || luma_mode == PredictionMode::NEW_NEWMV;
+            let ref_mv_idx = luma_mode.has_nearmv() as usize;
+            if is_newmv || luma_mode.has_nearmv() {
+                cw.write_drl_mode(luma_mode, ref_mv_idx, &mv_stack);
+            }
+            if is_newmv {
+                let num_mvs = if ref_frames[1] != NONE_FRAME { 2 } else { 1 };
+                for (mv, ref_mv) in mvs[..num_mvs].iter().zip(mv_stack.mvs[ref_mv_idx].iter()) {
+                    cw.write_mv(mv, ref_mv, fi.allow_high_precision_mv);
+                }
+            }
+        } else {
             cw.write_intra_mode(bsize, luma_mode);
         }
     } else {
@@ -590,14 +1534,18 @@ fn encode_block(fi: &FrameInvariants, fs: &mut FrameState, cw: &mut ContextWrite
     }
 
     cw.bc.set_mode(bo, bsize, luma_mode);
+    cw.bc.set_block_size(bo, bsize);
+    cw.bc.set_ref_frames(bo, bsize, ref_frames);
+    cw.bc.set_motion_vectors(bo, bsize, mvs);
 
-    let PlaneConfig { xdec, ydec, .. } = fs.input.planes[1].cfg;
+    let (xdec, ydec) = fi.chroma_sampling.get_decimation();
 
     if luma_mode.is_directional() && bsize >= BlockSize::BLOCK_8X8 {
         cw.write_angle_delta(0, luma_mode);
     }
 
-    if has_chroma(bo, bsize, xdec, ydec) {
+    if !is_inter && fi.chroma_sampling != ChromaSampling::CS400 &&
+        has_chroma(bo, bsize, xdec, ydec) {
         cw.write_intra_uv_mode(chroma_mode, luma_mode, bsize);
         if chroma_mode.is_directional() && bsize >= BlockSize::BLOCK_8X8 {
             cw.write_angle_delta(0, chroma_mode);
@@ -621,21 +1569,28 @@ fn encode_block(fi: &FrameInvariants, fs: &mut FrameState, cw: &mut ContextWrite
 
     let tx_type = if tx_set_type > TxSetType::EXT_TX_SET_DCTONLY && fi.speed <= 3 {
         // FIXME: there is one redundant transform type decision per encoded block
-        rdo_tx_type_decision(fi, fs, cw, luma_mode, bsize, bo, tx_size, tx_set_type)
+        rdo_tx_type_decision(fi, fs, cw, luma_mode, ref_frames, mvs, bsize, bo, tx_size, tx_set_type)
     } else {
         TxType::DCT_DCT
     };
 
-    write_tx_blocks(fi, fs, cw, luma_mode, chroma_mode, bo, bsize, tx_size, tx_type, skip);
+    write_tx_blocks(fi, fs, cw, luma_mode, chroma_mode, ref_frames, mvs, bo, bsize, tx_size, tx_type, skip);
 }
 
 pub fn write_tx_blocks(fi: &FrameInvariants, fs: &mut FrameState, cw: &mut ContextWriter,
-                       luma_mode: PredictionMode, chroma_mode: PredictionMode, bo: &BlockOffset,
+                       luma_mode: PredictionMode, chroma_mode: PredictionMode,
+                       ref_frames: [usize; 2], mvs: [MotionVector; 2], bo: &BlockOffset,
                        bsize: BlockSize, tx_size: TxSize, tx_type: TxType, skip: bool) {
     let bw = bsize.width_mi() / tx_size.width_mi();
     let bh = bsize.height_mi() / tx_size.height_mi();
+    let is_inter = !luma_mode.is_intra();
 
-    let PlaneConfig { xdec, ydec, .. } = fs.input.planes[1].cfg;
+    // Inter blocks are predicted as a whole rather than per transform block
+    if is_inter {
+        let po = bo.plane_offset(&fs.input.planes[0].cfg);
+        luma_mode.predict_inter(fi, 0, &po, &mut fs.rec.planes[0].mut_slice(&po),
+                                bsize.width(), bsize.height(), bsize, &ref_frames, &mvs);
+    }
 
     for by in 0..bh {
         for bx in 0..bw {
@@ -649,53 +1604,63 @@ pub fn write_tx_blocks(fi: &FrameInvariants, fs: &mut FrameState, cw: &mut Conte
         }
     }
 
-    // these are only valid for 4:2:0
-    let uv_tx_size = match bsize {
-        BlockSize::BLOCK_4X4 | BlockSize::BLOCK_8X8 => TxSize::TX_4X4,
-        BlockSize::BLOCK_16X16 => TxSize::TX_8X8,
-        BlockSize::BLOCK_32X32 => TxSize::TX_16X16,
-        _ => TxSize::TX_32X32
-    };
+    let (xdec, ydec) = fi.chroma_sampling.get_decimation();
+
+    if fi.chroma_sampling == ChromaSampling::CS400 || !has_chroma(bo, bsize, xdec, ydec) {
+        return;
+    }
+
+    let plane_bsize = get_plane_block_size(bsize, xdec, ydec);
+    let uv_tx_size = get_uv_tx_size(plane_bsize);
 
-    let mut bw_uv = (bw * tx_size.width_mi()) >> xdec;
-    let mut bh_uv = (bh * tx_size.height_mi()) >> ydec;
+    let bw_uv = plane_bsize.width_mi() / uv_tx_size.width_mi();
+    let bh_uv = plane_bsize.height_mi() / uv_tx_size.height_mi();
 
-    if (bw_uv == 0 || bh_uv == 0) && has_chroma(bo, bsize, xdec, ydec) {
-        bw_uv = 1;
-        bh_uv = 1;
+    // Inter chroma blocks reuse the luma transform type
+    let mut uv_tx_type = if is_inter {
+        tx_type
+    } else {
+        uv_intra_mode_to_tx_type_context(chroma_mode)
+    };
+    // The decoder falls back to DCT_DCT when the transform set of
+    // uv_tx_size does not include the mode's transform type
+    let tx_set_type = get_ext_tx_set_type(uv_tx_size, is_inter, fi.use_reduced_tx_set);
+    if av1_ext_tx_used[tx_set_type as usize][uv_tx_type as usize] == 0 {
+        uv_tx_type = TxType::DCT_DCT;
     }
+    let partition_x = (bo.x & LOCAL_BLOCK_MASK) >> xdec << MI_SIZE_LOG2;
+    let partition_y = (bo.y & LOCAL_BLOCK_MASK) >> ydec << MI_SIZE_LOG2;
 
-    bw_uv /= uv_tx_size.width_mi();
-    bh_uv /= uv_tx_size.height_mi();
+    for p in 1..3 {
+        let sb_offset = bo.sb_offset().plane_offset(&fs.input.planes[p].cfg);
 
-    let plane_bsize = get_plane_block_size(bsize, xdec, ydec);
+        if is_inter {
+            let po = PlaneOffset {
+                x: sb_offset.x + partition_x,
+                y: sb_offset.y + partition_y
+            };
+            chroma_mode.predict_inter(fi, p, &po, &mut fs.rec.planes[p].mut_slice(&po),
+                                      plane_bsize.width(), plane_bsize.height(), bsize,
+                                      &ref_frames, &mvs);
+        }
 
-    if bw_uv > 0 && bh_uv > 0 {
-        let uv_tx_type = uv_intra_mode_to_tx_type_context(chroma_mode);
-        let partition_x = (bo.x & LOCAL_BLOCK_MASK) >> xdec << MI_SIZE_LOG2;
-        let partition_y = (bo.y & LOCAL_BLOCK_MASK) >> ydec << MI_SIZE_LOG2;
-
-        for p in 1..3 {
-            let sb_offset = bo.sb_offset().plane_offset(&fs.input.planes[p].cfg);
-
-            for by in 0..bh_uv {
-                for bx in 0..bw_uv {
-                    let tx_bo =
-                        BlockOffset {
-                            x: bo.x + ((bx * uv_tx_size.width_mi()) << xdec) -
-                                ((bw * tx_size.width_mi() == 1) as usize),
-                            y: bo.y + ((by * uv_tx_size.height_mi()) << ydec) -
-                                ((bh * tx_size.height_mi() == 1) as usize)
-                        };
-
-                    let po = PlaneOffset {
-                        x: sb_offset.x + partition_x + bx * uv_tx_size.width(),
-                        y: sb_offset.y + partition_y + by * uv_tx_size.height()
+        for by in 0..bh_uv {
+            for bx in 0..bw_uv {
+                let tx_bo =
+                    BlockOffset {
+                        x: bo.x + ((bx * uv_tx_size.width_mi()) << xdec) -
+                            ((bsize.width_mi() == 1 && xdec == 1) as usize),
+                        y: bo.y + ((by * uv_tx_size.height_mi()) << ydec) -
+                            ((bsize.height_mi() == 1 && ydec == 1) as usize)
                     };
 
-                    encode_tx_block(fi, fs, cw, p, &tx_bo, chroma_mode, uv_tx_size, uv_tx_type,
-                                    plane_bsize, &po, skip);
-                }
+                let po = PlaneOffset {
+                    x: sb_offset.x + partition_x + bx * uv_tx_size.width(),
+                    y: sb_offset.y + partition_y + by * uv_tx_size.height()
+                };
+
+                encode_tx_block(fi, fs, cw, p, &tx_bo, chroma_mode, uv_tx_size, uv_tx_type,
+                                plane_bsize, &po, skip);
             }
         }
     }
@@ -725,6 +1690,8 @@ bsize: BlockSize, bo: &BlockOffset) -> f64 {
         bo: bo.clone(),
         pred_mode_luma: PredictionMode::DC_PRED,
         pred_mode_chroma: PredictionMode::DC_PRED,
+        pred_ref_frames: [INTRA_FRAME, NONE_FRAME],
+        pred_mvs: [MotionVector::default(); 2],
         skip: false
     }; // Best decision that is not PARTITION_SPLIT
 
@@ -743,10 +1710,11 @@ bsize: BlockSize, bo: &BlockOffset) -> f64 {
 
         let mode_decision = rdo_mode_decision(fi, fs, cw, bsize, bo).part_modes[0].clone();
         let (mode_luma, mode_chroma) = (mode_decision.pred_mode_luma, mode_decision.pred_mode_chroma);
+        let (ref_frames, mvs) = (mode_decision.pred_ref_frames, mode_decision.pred_mvs);
         let skip = mode_decision.skip;
         rd_cost = mode_decision.rd_cost;
 
-        encode_block(fi, fs, cw, mode_luma, mode_chroma, bsize, bo, skip);
+        encode_block(fi, fs, cw, mode_luma, mode_chroma, ref_frames, mvs, bsize, bo, skip);
 
         best_decision = mode_decision;
     }
@@ -781,8 +1749,9 @@ bsize: BlockSize, bo: &BlockOffset) -> f64 {
 
             // FIXME: redundant block re-encode
             let (mode_luma, mode_chroma) = (best_decision.pred_mode_luma, best_decision.pred_mode_chroma);
+            let (ref_frames, mvs) = (best_decision.pred_ref_frames, best_decision.pred_mvs);
             let skip = best_decision.skip;
-            encode_block(fi, fs, cw, mode_luma, mode_chroma, bsize, bo, skip);
+            encode_block(fi, fs, cw, mode_luma, mode_chroma, ref_frames, mvs, bsize, bo, skip);
         }
     }
 
@@ -851,10 +1820,11 @@ fn encode_partition_topdown(fi: &FrameInvariants, fs: &mut FrameState, cw: &mut
                 };
 
             let (mode_luma, mode_chroma) = (part_decision.pred_mode_luma, part_decision.pred_mode_chroma);
+            let (ref_frames, mvs) = (part_decision.pred_ref_frames, part_decision.pred_mvs);
             let skip = part_decision.skip;
 
             // FIXME: every final block that has gone through the RDO decision process is encoded twice
-            encode_block(fi, fs, cw, mode_luma, mode_chroma, bsize, bo, skip);
+            encode_block(fi, fs, cw, mode_luma, mode_chroma, ref_frames, mvs, bsize, bo, skip);
         },
         PartitionType::PARTITION_SPLIT => {
             if rdo_output.part_modes.len() >= 4 {
@@ -888,128 +1858,611 @@ fn encode_partition_topdown(fi: &FrameInvariants, fs: &mut FrameState, cw: &mut
     }
 }
 
-fn encode_tile(fi: &FrameInvariants, fs: &mut FrameState) -> Vec<u8> {
-    let w = ec::Writer::new();
-    let fc = CDFContext::new(fi.qindex as u8);
-    let bc = BlockContext::new(fi.w_in_b, fi.h_in_b);
-    let mut cw = ContextWriter::new(w, fc,  bc);
-
-    for sby in 0..fi.sb_height {
-        cw.bc.reset_left_contexts();
-
-        for sbx in 0..fi.sb_width {
-            let sbo = SuperBlockOffset { x: sbx, y: sby };
-            let bo = sbo.block_offset(0, 0);
-
-            // Encode SuperBlock
-            if fi.speed == 0 {
-                encode_partition_bottomup(fi, fs, &mut cw, BlockSize::BLOCK_64X64, &bo);
-            }
-            else {
-                encode_partition_topdown(fi, fs, &mut cw, BlockSize::BLOCK_64X64, &bo, &None);
+/// Encode the tiles of the frame in raster order, each with its own entropy
+/// coder starting from the CDFs of the frame.
+fn encode_tiles(fi: &FrameInvariants, fs: &mut FrameState) -> Vec<Vec<u8>> {
+    let (tile_w, tile_h) = fi.tile_size_sb();
+    let mut bc = BlockContext::new(fi.w_in_b, fi.h_in_b);
+    let mut tiles = Vec::new();
+
+    let mut tile_y = 0;
+    while tile_y < fi.sb_height {
+        let sb_rows = tile_y..std::cmp::min(tile_y + tile_h, fi.sb_height);
+        let mut tile_x = 0;
+        while tile_x < fi.sb_width {
+            let sb_cols = tile_x..std::cmp::min(tile_x + tile_w, fi.sb_width);
+            let start = SuperBlockOffset { x: sb_cols.start, y: sb_rows.start }.block_offset(0, 0);
+            let end = SuperBlockOffset { x: sb_cols.end, y: sb_rows.end }.block_offset(0, 0);
+            bc.set_tile(start.x..std::cmp::min(end.x, fi.w_in_b),
+                        start.y..std::cmp::min(end.y, fi.h_in_b));
+            let mut cw = ContextWriter::new(ec::Writer::new(), fs.cdfs.clone(), bc);
+
+            for sby in sb_rows.clone() {
+                cw.bc.reset_left_contexts();
+
+                for sbx in sb_cols.clone() {
+                    let sbo = SuperBlockOffset { x: sbx, y: sby };
+                    let bo = sbo.block_offset(0, 0);
+
+                    // Encode SuperBlock
+                    if fi.speed == 0 {
+                        encode_partition_bottomup(fi, fs, &mut cw, BlockSize::BLOCK_64X64, &bo);
+                    }
+                    else {
+                        encode_partition_topdown(fi, fs, &mut cw, BlockSize::BLOCK_64X64, &bo, &None);
+                    }
+                }
             }
+            tiles.push(cw.w.done());
+            bc = cw.bc;
+            tile_x += tile_w;
         }
+        tile_y += tile_h;
     }
-    let mut h = cw.w.done();
-    h.push(0); // superframe anti emulation
-    h
+    fs.frame_mvs = bc.saved_motion_vectors(fi);
+    tiles
 }
 
-fn encode_frame(sequence: &Sequence, fi: &FrameInvariants, fs: &mut FrameState, last_rec: &Option<Frame>) -> Vec<u8> {
+/// Tile group OBU payload holding all the tiles of a frame, each but the
+/// last preceded by its size.
+fn tile_group(tiles: &[Vec<u8>]) -> Result<Vec<u8>, std::io::Error> {
+    let mut buf = Vec::new();
+    {
+        let mut bw = BitWriter::<BE>::new(&mut buf);
+        if tiles.len() > 1 {
+            bw.write_bit(false)?; // tile_start_and_end_present_flag
+            bw.byte_align()?;
+        }
+    }
+    for (i, tile) in tiles.iter().enumerate() {
+        if i + 1 < tiles.len() {
+            let mut bw = BitWriter::<LE>::new(&mut buf);
+            bw.write(8 * TILE_SIZE_BYTES as u32, tile.len() as u32 - 1)?; // tile_size_minus_1
+        }
+        buf.extend_from_slice(tile);
+    }
+    Ok(buf)
+}
+
+fn encode_frame(sequence: &Sequence, fi: &FrameInvariants, fs: &mut FrameState, refs: &ReferenceFramesSet) -> Result<Vec<u8>, EncoderError> {
     let mut packet = Vec::new();
-    write_uncompressed_header(&mut packet, sequence, fi).unwrap();
+    write_obus(&mut packet, sequence, fi)?;
     if fi.show_existing_frame {
-        match last_rec {
-            Some(ref rec) => for p in 0..3 {
-                fs.rec.planes[p].data.copy_from_slice(rec.planes[p].data.as_slice());
+        match refs.frames[fi.frame_to_show_map_idx] {
+            Some(ref rec) => for (plane, rec_plane) in fs.rec.planes.iter_mut().zip(rec.frame.planes.iter()) {
+                plane.data.copy_from_slice(rec_plane.data.as_slice());
             },
-            None => (),
+            None => return Err(EncoderError::Internal("no frame to show"))
         }
     } else {
-        let tile = encode_tile(fi, fs);
-        packet.write(&tile).unwrap();
-    }
-    packet
-}
-
-/// Encode and write a frame.
-pub fn process_frame(sequence: &Sequence, fi: &FrameInvariants,
-                     output_file: &mut Write,
-                     y4m_dec: &mut y4m::Decoder<Box<Read>>,
-                     y4m_enc: Option<&mut y4m::Encoder<Box<Write>>>,
-                     last_rec: &mut Option<Frame>) -> bool {
-    unsafe {
-        av1_rtcd();
-        aom_dsp_rtcd();
-    }
-    let width = fi.width;
-    let height = fi.height;
-    let y4m_bits = y4m_dec.get_bit_depth();
-    let y4m_bytes = y4m_dec.get_bytes_per_sample();
-    let csp = y4m_dec.get_colorspace();
-    match csp {
-        y4m::Colorspace::C420 | 
-        y4m::Colorspace::C420jpeg |
-        y4m::Colorspace::C420paldv | 
-        y4m::Colorspace::C420mpeg2 => {},
-        _ => {
-            panic!("Colorspace {:?} is not supported yet.", csp);
-        },
+        let tiles = encode_tiles(fi, fs);
+        write_obu(&mut packet, OBU_Type::OBU_TILE_GROUP, &tile_group(&tiles)?)?;
     }
-    match y4m_dec.read_frame() {
-        Ok(y4m_frame) => {
-            let y4m_y = y4m_frame.get_y_plane();
-            let y4m_u = y4m_frame.get_u_plane();
-            let y4m_v = y4m_frame.get_v_plane();
-            eprintln!("{}", fi);
-            let mut fs = FrameState::new(&fi);
-            fs.input.planes[0].copy_from_raw_u8(&y4m_y, width*y4m_bytes, y4m_bytes);
-            fs.input.planes[1].copy_from_raw_u8(&y4m_u, width*y4m_bytes/2, y4m_bytes);
-            fs.input.planes[2].copy_from_raw_u8(&y4m_v, width*y4m_bytes/2, y4m_bytes);
-
-            // We cannot currently encode > 8 bit input!
-            match y4m_bits {
-                8 => {},
-                10 | 12 => {
-                    for plane in 0..3 {
-                        for row in fs.input.planes[plane].data.chunks_mut(fs.rec.planes[plane].cfg.stride) {
-                            for col in row.iter_mut() { *col >>= y4m_bits-8 }
-                        }
-                    }
-                },
-                _ => panic! ("unknown input bit depth!"),
-            }
-
-            let packet = encode_frame(&sequence, &fi, &mut fs, &last_rec);
-            write_ivf_frame(output_file, fi.number, packet.as_ref());
-            if let Some(mut y4m_enc) = y4m_enc {
-                let mut rec_y = vec![128 as u8; width*height];
-                let mut rec_u = vec![128 as u8; width*height/4];
-                let mut rec_v = vec![128 as u8; width*height/4];
-                for (y, line) in rec_y.chunks_mut(width).enumerate() {
-                    for (x, pixel) in line.iter_mut().enumerate() {
-                        let stride = fs.rec.planes[0].cfg.stride;
-                        *pixel = fs.rec.planes[0].data[y*stride+x] as u8;
-                    }
-                }
-                for (y, line) in rec_u.chunks_mut(width/2).enumerate() {
-                    for (x, pixel) in line.iter_mut().enumerate() {
-                        let stride = fs.rec.planes[1].cfg.stride;
-                        *pixel = fs.rec.planes[1].data[y*stride+x] as u8;
+    Ok(packet)
+}
+
+/// Encoded frame, as returned by `Encoder::receive_packet`.
+pub struct Packet {
+    pub data: Vec<u8>,
+    pub rec: Option<Frame>,
+    /// Presentation timestamp given to `send_frame`
+    pub pts: u64,
+    pub frame_type: FrameType
+}
+
+impl fmt::Display for Packet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Frame {} - {} - {} bytes", self.pts, self.frame_type, self.data.len())
+    }
+}
+
+/// Frame of a mini-GOP waiting to be coded
+struct PlannedFrame {
+    plan: FramePlan,
+    /// Display order index
+    number: u64,
+    pts: u64,
+    /// Source of the frame, None when it is shown again
+    input: Option<Frame>
+}
+
+/// Encoder context: frames go in through `send_frame` and come out as
+/// packets from `receive_packet`, in the same order. Frames are coded out
+/// of order within mini-GOPs, so packets lag behind by up to
+/// `EncoderConfig::mini_gop_len - 1` frames.
+pub struct Encoder {
+    sequence: Sequence,
+    fi: FrameInvariants,
+    frame_q: VecDeque<(u64, Frame)>,
+    gop_q: VecDeque<PlannedFrame>,
+    refs: ReferenceFramesSet,
+    frame_count: u64,
+    last_shown: u64,
+    key_frame_interval: u64,
+    mini_gop_len: usize,
+    output_rec: bool,
+    flushed: bool
+}
+
+impl Encoder {
+    /// Create an encoder for `sequence`, whose bit depth and still picture
+    /// signaling are set from `config`, and profile from its chroma sampling
+    /// and bit depth.
+    pub fn new(config: &EncoderConfig, mut sequence: Sequence)
+               -> Result<Encoder, EncoderError> {
+        config.validate()?;
+
+        sequence.bit_depth = config.bit_depth;
+        // 4:4:4 up to 10-bit is the High profile, while 4:2:2 and any
+        // 12-bit format are only allowed in the Professional profile
+        sequence.profile = match sequence.chroma_sampling {
+            _ if config.bit_depth == 12 => 2,
+            ChromaSampling::CS420 | ChromaSampling::CS400 => 0,
+            ChromaSampling::CS444 => 1,
+            ChromaSampling::CS422 => 2,
+        };
+        sequence.still_picture = config.still_picture;
+        sequence.reduced_still_picture_hdr = config.still_picture;
+        if config.still_picture {
+            sequence.timing_info = None;
+        }
+        sequence.validate()?;
+
+        let mut fi = FrameInvariants::new(sequence.max_frame_width as usize,
+                                          sequence.max_frame_height as usize,
+                                          config.quantizer, config.speed);
+        fi.bit_depth = config.bit_depth;
+        fi.chroma_sampling = sequence.chroma_sampling;
+        // Frames wider or larger than the largest tile need more tiles
+        let limits = TileLimits::new(fi.sb_width, fi.sb_height);
+        fi.tile_cols_log2 = std::cmp::max(config.tile_cols_log2, limits.min_log2_cols);
+        fi.tile_rows_log2 = std::cmp::max(config.tile_rows_log2,
+                                          limits.min_log2_rows(fi.tile_cols_log2));
+        if fi.tile_cols_log2 > limits.max_log2_cols || fi.tile_rows_log2 > limits.max_log2_rows {
+            return Err(ConfigError::InvalidTiles(config.tile_cols_log2, config.tile_rows_log2).into());
+        }
+        Ok(Encoder {
+            sequence,
+            fi,
+            frame_q: VecDeque::new(),
+            gop_q: VecDeque::new(),
+            refs: ReferenceFramesSet::default(),
+            frame_count: 0,
+            last_shown: 0,
+            key_frame_interval: config.key_frame_interval,
+            mini_gop_len: config.mini_gop_len,
+            output_rec: false,
+            flushed: false
+        })
+    }
+
+    /// Return the reconstructed frames along with the packets.
+    pub fn set_output_reconstruction(&mut self, output_rec: bool) {
+        self.output_rec = output_rec;
+    }
+
+    pub fn sequence(&self) -> &Sequence {
+        &self.sequence
+    }
+
+    /// Allocate a frame suitable for `send_frame`.
+    pub fn new_frame(&self) -> Frame {
+        Frame::new(self.fi.padded_w, self.fi.padded_h, self.fi.chroma_sampling)
+    }
+
+    /// Queue a frame to be encoded with presentation timestamp `pts`. The
+    /// frame may be owned or borrowed, each plane is read following its
+    /// own stride. Samples past the frame size are filled from its edges.
+    pub fn send_frame<F: Borrow<Frame>>(&mut self, frame: F, pts: u64)
+                                        -> Result<(), EncoderError> {
+        if self.flushed {
+            return Err(EncoderError::Internal("frame sent after flush"));
+        }
+        if self.sequence.still_picture && self.frame_count + (self.frame_q.len() as u64) > 0 {
+            return Err(EncoderError::UnsupportedInput(
+                "a still picture holds a single frame".to_string()));
+        }
+        let mut input = self.new_frame();
+        for (dst, src) in input.planes.iter_mut().zip(frame.borrow().planes.iter()) {
+            let width = std::cmp::min(dst.cfg.stride, src.cfg.stride);
+            for (dst_row, src_row) in dst.data.chunks_mut(dst.cfg.stride)
+                                              .zip(src.data.chunks(src.cfg.stride)) {
+                dst_row[..width].copy_from_slice(&src_row[..width]);
+            }
+            let PlaneConfig { xdec, ydec, .. } = dst.cfg;
+            dst.pad((self.fi.width + xdec) >> xdec, (self.fi.height + ydec) >> ydec);
+        }
+        self.frame_q.push_back((pts, input));
+        Ok(())
+    }
+
+    /// Signal the end of the stream; the queued frames are still returned
+    /// by `receive_packet`.
+    pub fn flush(&mut self) {
+        self.flushed = true;
+    }
+
+    /// Move the frames of the next mini-GOP from the input queue to the
+    /// coding queue. Returns false if more frames or a flush are needed.
+    fn plan_mini_gop(&mut self) -> bool {
+        let first = self.frame_count;
+        let max_len = if first % self.key_frame_interval == 0 {
+            // keyframes are coded on their own
+            1
+        } else {
+            // mini-GOPs stop short of the next keyframe
+            let to_key = self.key_frame_interval - first % self.key_frame_interval;
+            std::cmp::min(self.mini_gop_len as u64, to_key) as usize
+        };
+        if self.frame_q.len() < max_len && !self.flushed {
+            return false;
+        }
+        let len = std::cmp::min(max_len, self.frame_q.len());
+        if len == 0 {
+            return false;
+        }
+
+        let (pts, mut inputs): (Vec<u64>, Vec<Option<Frame>>) =
+            self.frame_q.drain(..len).map(|(pts, f)| (pts, Some(f))).unzip();
+        for plan in plan_mini_gop(len) {
+            self.gop_q.push_back(PlannedFrame {
+                plan,
+                number: first + plan.offset as u64 - 1,
+                pts: pts[plan.offset - 1],
+                input: inputs[plan.offset - 1].take()
+            });
+        }
+        self.frame_count += len as u64;
+        true
+    }
+
+    /// Code a frame of the current mini-GOP and store it in the reference
+    /// slots. Returns its OBUs, and its reconstruction if it is shown.
+    fn encode_planned_frame(&mut self, frame: PlannedFrame)
+                            -> Result<(Vec<u8>, Option<Frame>), EncoderError> {
+        let fi = &mut self.fi;
+        fi.number = frame.pts;
+        fi.order_hint = (frame.number % (1 << ORDER_HINT_BITS)) as u32;
+        fi.show_frame = frame.plan.show_frame;
+        fi.show_existing_frame = frame.plan.show_existing_frame;
+        fi.frame_type = if frame.number % self.key_frame_interval == 0 {
+            FrameType::KEY
+        } else {
+            FrameType::INTER
+        };
+        fi.intra_only = fi.frame_type == FrameType::KEY ||
+                        fi.frame_type == FrameType::INTRA_ONLY;
+        fi.use_prev_frame_mvs = !(fi.intra_only || fi.error_resilient);
+
+        if fi.show_existing_frame {
+            fi.frame_to_show_map_idx = find_slot(&self.refs, frame.number)
+                .ok_or(EncoderError::Internal("no frame to show"))?;
+            fi.refresh_frame_flags = 0;
+        } else if fi.frame_type == FrameType::KEY {
+            fi.refresh_frame_flags = ALL_REF_FRAMES_MASK;
+            fi.ref_frames = Default::default();
+            fi.reference_mode = ReferenceMode::SINGLE;
+        } else {
+            fi.ref_frame_idx = select_references(&self.refs, frame.number);
+            // Compound prediction pairs a past reference with a future one
+            let has_future = fi.ref_frame_idx[BWDREF_FRAME - LAST_FRAME] != fi.ref_frame_idx[0];
+            fi.reference_mode = if has_future { ReferenceMode::SELECT } else { ReferenceMode::SINGLE };
+            fi.refresh_frame_flags = 1 << refresh_slot(&self.refs, self.last_shown);
+            for (r, &i) in fi.ref_frames.iter_mut().zip(fi.ref_frame_idx.iter()) {
+                *r = self.refs.frames[i].clone();
+            }
+        }
+        for (order_hint, slot) in fi.ref_order_hint.iter_mut().zip(self.refs.frames.iter()) {
+            *order_hint = slot.as_ref().map_or(0, |r| r.order_hint);
+        }
+        fi.motion_field = if fi.use_prev_frame_mvs && !fi.show_existing_frame {
+            project_motion_field(fi)
+        } else {
+            Vec::new()
+        };
+
+        let mut fs = FrameState::new(fi);
+        if let Some(input) = frame.input {
+            fs.input = input;
+        }
+
+        // Global motion is estimated once for each distinct reference slot
+        fi.globalmv_transformation_type = [GlobalMVMode::IDENTITY; ALTREF_FRAME + 1];
+        fi.gm_params = [DEFAULT_GM_PARAMS; ALTREF_FRAME + 1];
+        if fi.frame_type == FrameType::INTER && !fi.show_existing_frame {
+            for i in LAST_FRAME..ALTREF_FRAME+1 {
+                let slot = fi.ref_frame_idx[i - LAST_FRAME];
+                let same_slot = (LAST_FRAME..i).find(|&j| fi.ref_frame_idx[j - LAST_FRAME] == slot);
+                let (gm_type, params) = match same_slot {
+                    Some(j) => (fi.globalmv_transformation_type[j], fi.gm_params[j]),
+                    None => {
+                        let rec = fi.ref_frames[i - LAST_FRAME].as_ref()
+                            .ok_or(EncoderError::Internal("empty reference slot"))?;
+                        estimate_global_motion(fi, &fs.input.planes[0], &rec.frame.planes[0])
                     }
+                };
+                fi.globalmv_transformation_type[i] = gm_type;
+                fi.gm_params[i] = params;
+            }
+        }
+        let data = encode_frame(&self.sequence, fi, &mut fs, &self.refs)?;
+
+        let rec = if self.output_rec && fi.show_frame { Some(fs.rec.clone()) } else { None };
+        if !fi.show_existing_frame {
+            let mut ref_order_hints = [0; REFS_PER_FRAME];
+            for (order_hint, r) in ref_order_hints.iter_mut().zip(LAST_FRAME..) {
+                *order_hint = fi.ref_frame_order_hint(r);
+            }
+            let reference = Rc::new(ReferenceFrame {
+                frame: fs.rec,
+                cdfs: fs.cdfs,
+                frame_type: fi.frame_type,
+                number: frame.number,
+                order_hint: fi.order_hint,
+                ref_order_hints,
+                frame_mvs: fs.frame_mvs
+            });
+            self.refs.refresh(fi.refresh_frame_flags, &reference);
+        }
+        Ok((data, rec))
+    }
+
+    /// Encode the frames up to the next shown one into a packet, or return
+    /// None if more frames or a flush are needed first. A mini-GOP is coded
+    /// once all its frames are queued.
+    pub fn receive_packet(&mut self) -> Result<Option<Packet>, EncoderError> {
+        if self.gop_q.is_empty() && !self.plan_mini_gop() {
+            return Ok(None);
+        }
+
+        // Hidden frames share the temporal unit of the next shown frame
+        let mut data = Vec::new();
+        write_obu(&mut data, OBU_Type::OBU_TEMPORAL_DELIMITER, &[])?;
+        loop {
+            let frame = match self.gop_q.pop_front() {
+                Some(f) => f,
+                None => return Err(EncoderError::Internal("mini-GOP ends with a hidden frame"))
+            };
+            let (number, pts) = (frame.number, frame.pts);
+            let (frame_data, rec) = self.encode_planned_frame(frame)?;
+            data.extend(frame_data);
+
+            if self.fi.show_frame {
+                self.last_shown = number;
+                let frame_type = if self.fi.show_existing_frame {
+                    self.refs.frames[self.fi.frame_to_show_map_idx].as_ref()
+                        .ok_or(EncoderError::Internal("no frame to show"))?.frame_type
+                } else {
+                    self.fi.frame_type
+                };
+                return Ok(Some(Packet {
+                    data,
+                    rec,
+                    pts,
+                    frame_type
+                }));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Bit `i` of `data`, most significant first
+    fn bit(data: &[u8], i: usize) -> u32 {
+        (data[i / 8] >> (7 - i % 8)) as u32 & 1
+    }
+
+    fn bits(data: &[u8], start: usize, n: usize) -> u32 {
+        (start..start + n).fold(0, |v, i| v << 1 | bit(data, i))
+    }
+
+    /// Bytes written by `mux` to a scratch file, for the muxer tests.
+    pub fn muxed<F>(name: &str, mux: F) -> Vec<u8>
+        where F: FnOnce(Output) -> Result<(), EncoderError> {
+        let path = std::env::temp_dir().join(format!("rav1e-{}-{}", std::process::id(), name));
+        mux(Output::File(File::create(&path).unwrap())).unwrap();
+        let data = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        data
+    }
+
+    /// Reference frame of display order `number`, with an 8x8 picture, for
+    /// the reference slot tests.
+    pub fn reference_frame(number: u64) -> Rc<ReferenceFrame> {
+        Rc::new(ReferenceFrame {
+            frame: Frame::new(8, 8, ChromaSampling::CS400),
+            cdfs: CDFContext::new(0),
+            frame_type: FrameType::INTER,
+            number,
+            order_hint: 0,
+            ref_order_hints: [0; REFS_PER_FRAME],
+            frame_mvs: Vec::new()
+        })
+    }
+
+    #[test]
+    fn refresh_reference_slots() {
+        let mut refs = ReferenceFramesSet::default();
+        let numbers = |refs: &ReferenceFramesSet| -> Vec<Option<u64>> {
+            refs.frames.iter().map(|f| f.as_ref().map(|f| f.number)).collect()
+        };
+
+        refs.refresh(0, &reference_frame(0));
+        assert_eq!(numbers(&refs), [None; NUM_REF_FRAMES]);
+
+        let key = reference_frame(0);
+        refs.refresh(0xff, &key);
+        assert!(refs.frames.iter().all(|f| Rc::ptr_eq(f.as_ref().unwrap(), &key)));
+
+        refs.refresh(1 << 2, &reference_frame(1));
+        refs.refresh(0b1010_0001, &reference_frame(4));
+        assert_eq!(numbers(&refs),
+                   [Some(4), Some(0), Some(1), Some(0), Some(0), Some(4), Some(0), Some(4)]);
+        assert!(Rc::ptr_eq(refs.frames[5].as_ref().unwrap(), refs.frames[7].as_ref().unwrap()));
+
+        refs.refresh(0, &reference_frame(5));
+        assert_eq!(numbers(&refs),
+                   [Some(4), Some(0), Some(1), Some(0), Some(0), Some(4), Some(0), Some(4)]);
+    }
+
+    #[test]
+    fn order_hint_distances_wrap() {
+        assert_eq!(relative_dist(5, 3), 2);
+        assert_eq!(relative_dist(3, 5), -2);
+        assert_eq!(relative_dist(1, 127), 2);
+        assert_eq!(relative_dist(127, 1), -2);
+    }
+
+    #[test]
+    fn reject_invalid_sequence() {
+        let config = EncoderConfig::default();
+        let mut seq = Sequence::new(64, 64);
+        seq.timing_info = Some(TimingInfo { num_ticks_per_picture: Some(0), ..TimingInfo::new(30, 1) });
+        assert!(Encoder::new(&config, seq).is_err());
+        let mut seq = Sequence::new(64, 64);
+        seq.matrix_coefficients = MatrixCoefficients::IDENTITY;
+        assert!(Encoder::new(&config, seq).is_err());
+        let mut seq = Sequence::new(64, 64);
+        seq.num_bits_width = 5;
+        assert!(Encoder::new(&config, seq).is_err());
+        assert!(Encoder::new(&config, Sequence::new(64, 64)).is_ok());
+    }
+
+    #[test]
+    fn reject_invalid_quantizer() {
+        for &quantizer in &[0, 256] {
+            let config = EncoderConfig { quantizer, ..Default::default() };
+            assert_eq!(config.validate(), Err(ConfigError::InvalidQuantizer(quantizer)));
+        }
+        for &quantizer in &[1, 255] {
+            assert!(EncoderConfig { quantizer, ..Default::default() }.validate().is_ok());
+        }
+    }
+
+    #[test]
+    fn mini_gop_len_bounds_packet_lag() {
+        for &mini_gop_len in &[0, MAX_MINI_GOP_LEN + 1] {
+            let config = EncoderConfig { mini_gop_len, ..Default::default() };
+            assert_eq!(config.validate(), Err(ConfigError::InvalidMiniGopLen(mini_gop_len)));
+        }
+        for &mini_gop_len in &[1, 3, MAX_MINI_GOP_LEN] {
+            let config = EncoderConfig { mini_gop_len, speed: 10, ..Default::default() };
+            let mut enc = Encoder::new(&config, Sequence::new(16, 16)).unwrap();
+            let frame = enc.new_frame();
+            let mut received = 0;
+            for sent in 1..24 {
+                enc.send_frame(&frame, sent).unwrap();
+                while enc.receive_packet().unwrap().is_some() {
+                    received += 1;
                 }
-                for (y, line) in rec_v.chunks_mut(width/2).enumerate() {
-                    for (x, pixel) in line.iter_mut().enumerate() {
-                        let stride = fs.rec.planes[2].cfg.stride;
-                        *pixel = fs.rec.planes[2].data[y*stride+x] as u8;
-                    }
+                assert!(sent - received < mini_gop_len as u64);
+            }
+        }
+    }
+
+    #[test]
+    fn still_picture_frame_header() {
+        let config = EncoderConfig {
+            quantizer: 100,
+            speed: 10,
+            still_picture: true,
+            ..Default::default()
+        };
+        let mut enc = Encoder::new(&config, Sequence::new(16, 16)).unwrap();
+        let frame = enc.new_frame();
+        enc.send_frame(frame, 0).unwrap();
+        enc.flush();
+        let packet = enc.receive_packet().unwrap().unwrap();
+
+        // Temporal delimiter, then the sequence header, each with one byte
+        // of OBU header and one of size
+        let data = &packet.data[2..];
+        assert_eq!(data[0] >> 3, OBU_Type::OBU_SEQUENCE_HEADER as u8);
+        let data = &data[2 + data[1] as usize..];
+        assert_eq!(data[0] >> 3, OBU_Type::OBU_FRAME_HEADER as u8);
+        let size = data[1] as usize;
+        let header = &data[2..2 + size];
+
+        // Everything up to the frame size is implied by the reduced still
+        // picture header, and so is disable_frame_end_update_cdf
+        assert_eq!(bits(header, 0, 3), 0); // cdf update, screen content, render size
+        assert_eq!(bit(header, 3), 1); // uniform tile spacing, a single tile
+        assert_eq!(bits(header, 4, 8), 100); // base_q_idx
+        // Delta q and quantizer matrix flags, segmentation, delta_q_present,
+        // loop filter, tx_mode_select and reduced_tx_set, then the trailing
+        // bit
+        let trailing = 4 + 8 + 4 + 1 + 1 + 16 + 1 + 1;
+        assert_eq!(bit(header, trailing), 1);
+        assert_eq!(header.len(), (trailing + 8) / 8);
+        assert_eq!(bits(header, trailing + 1, 8 * size - trailing - 1), 0);
+    }
+
+    #[test]
+    fn tiles_fit_the_frame_size() {
+        // 4x2 superblocks
+        let config = EncoderConfig { tile_cols_log2: 2, tile_rows_log2: 1, ..Default::default() };
+        let enc = Encoder::new(&config, Sequence::new(256, 128)).unwrap();
+        assert_eq!(enc.fi.tile_size_sb(), (1, 1));
+        for &(tile_cols_log2, tile_rows_log2) in &[(3, 0), (0, 2), (7, 0)] {
+            let config = EncoderConfig { tile_cols_log2, tile_rows_log2, ..Default::default() };
+            match Encoder::new(&config, Sequence::new(256, 128)) {
+                Err(EncoderError::InvalidConfig(e)) =>
+                    assert_eq!(e, ConfigError::InvalidTiles(tile_cols_log2, tile_rows_log2)),
+                _ => panic!("{}x{} tiles accepted", 1 << tile_cols_log2, 1 << tile_rows_log2)
+            }
+        }
+
+        // Tiles are at most 4096 pixels wide
+        let enc = Encoder::new(&EncoderConfig::default(), Sequence::new(8192, 64)).unwrap();
+        assert_eq!(enc.fi.tile_cols_log2, 1);
+        assert_eq!(enc.fi.tile_rows_log2, 0);
+    }
+
+    #[test]
+    fn tile_group_sizes() {
+        let config = EncoderConfig {
+            speed: 10,
+            tile_cols_log2: 1,
+            tile_rows_log2: 1,
+            ..Default::default()
+        };
+        let mut enc = Encoder::new(&config, Sequence::new(192, 128)).unwrap();
+        let frame = enc.new_frame();
+        enc.send_frame(frame, 0).unwrap();
+        enc.flush();
+        let packet = enc.receive_packet().unwrap().unwrap();
+
+        // OBU type and payload, skipping the leb128 size
+        fn obu(data: &[u8]) -> (u8, &[u8], &[u8]) {
+            let (mut size, mut i) = (0, 1);
+            loop {
+                size |= (data[i] as usize & 0x7f) << (7 * (i - 1));
+                i += 1;
+                if data[i - 1] & 0x80 == 0 {
+                    break;
                 }
-                let rec_frame = y4m::Frame::new([&rec_y, &rec_u, &rec_v], None);
-                y4m_enc.write_frame(&rec_frame).unwrap();
             }
-            *last_rec = Some(fs.rec);
-            true
-        },
-        _ => false
+            (data[0] >> 3, &data[i..i + size], &data[i + size..])
+        }
+        let (_, _, data) = obu(&packet.data); // temporal delimiter
+        let (_, _, data) = obu(data); // sequence header
+        let (obu_type, _, data) = obu(data);
+        assert_eq!(obu_type, OBU_Type::OBU_FRAME_HEADER as u8);
+        let (obu_type, mut tiles, data) = obu(data);
+        assert_eq!(obu_type, OBU_Type::OBU_TILE_GROUP as u8);
+        assert!(data.is_empty());
+
+        // 3x2 superblocks split into 2x2 tiles, the last one without its
+        // size, after tile_start_and_end_present_flag
+        assert_eq!(tiles[0], 0);
+        tiles = &tiles[1..];
+        for _ in 0..3 {
+            let size = 1 + tiles[..4].iter().rev().fold(0, |v, &b| v << 8 | b as usize);
+            assert!(size + 4 < tiles.len());
+            tiles = &tiles[4 + size..];
+        }
     }
 }
@@ -0,0 +1,135 @@
+// Copyright (c) 2017-2018, The rav1e contributors. All rights reserved
+//
+// This source code is subject to the terms of the BSD 2 Clause License and
+// the Alliance for Open Media Patent License 1.0. If the BSD 2 Clause License
+// was not distributed with this source code in the LICENSE file, you can
+// obtain it at www.aomedia.org/license/software. If the Alliance for Open
+// Media Patent License 1.0 was not distributed with this source code in the
+// PATENTS file, you can obtain it at www.aomedia.org/license/patent.
+
+#![cfg_attr(feature = "cargo-clippy", allow(cast_lossless))]
+
+use context::*;
+use partition::*;
+use plane::*;
+use std::cmp;
+use FrameInvariants;
+use FrameState;
+use LAST_FRAME;
+use NONE_FRAME;
+
+// Full-pel search range around the co-located block, in samples
+const SEARCH_RANGE: isize = 16;
+
+// Sum of Absolute Differences for a wxh block
+pub fn get_sad(
+  plane_org: &PlaneSlice, plane_ref: &PlaneSlice, w: usize, h: usize
+) -> u32 {
+  let mut sum: u32 = 0;
+  for j in 0..h {
+    for i in 0..w {
+      let dist = plane_org.p(i, j) as i32 - plane_ref.p(i, j) as i32;
+      sum += dist.abs() as u32;
+    }
+  }
+  sum
+}
+
+// SAD of the block at po against its motion compensated prediction
+fn get_mc_sad(
+  fi: &FrameInvariants, fs: &FrameState, tmp: &mut Plane, po: &PlaneOffset,
+  bsize: BlockSize, ref_frame: usize, mv: &MotionVector
+) -> u32 {
+  let tmp_po = PlaneOffset {
+    x: 0,
+    y: 0
+  };
+  let (w, h) = (bsize.width(), bsize.height());
+  PredictionMode::NEWMV.predict_inter(
+    fi,
+    0,
+    po,
+    &mut tmp.mut_slice(&tmp_po),
+    w,
+    h,
+    bsize,
+    &[ref_frame, NONE_FRAME],
+    &[*mv; 2]
+  );
+  get_sad(&fs.input.planes[0].slice(po), &tmp.slice(&tmp_po), w, h)
+}
+
+// Luma motion search of a block against ref_frame: exhaustive full-pel
+// search, then refinement down to the finest allowed sub-pel precision
+pub fn motion_estimation(
+  fi: &FrameInvariants, fs: &FrameState, bsize: BlockSize, bo: &BlockOffset,
+  ref_frame: usize
+) -> MotionVector {
+  let rec = fi.ref_frames[ref_frame - LAST_FRAME].as_ref().unwrap();
+  let po = bo.plane_offset(&fs.input.planes[0].cfg);
+  let (blk_w, blk_h) = (bsize.width(), bsize.height());
+
+  let x_lo = cmp::max(0, po.x as isize - SEARCH_RANGE);
+  let x_hi =
+    cmp::min((fi.padded_w - blk_w) as isize, po.x as isize + SEARCH_RANGE);
+  let y_lo = cmp::max(0, po.y as isize - SEARCH_RANGE);
+  let y_hi =
+    cmp::min((fi.padded_h - blk_h) as isize, po.y as isize + SEARCH_RANGE);
+
+  let mut lowest_sad = u32::max_value();
+  let mut best_mv = MotionVector::default();
+
+  for y in y_lo..y_hi + 1 {
+    for x in x_lo..x_hi + 1 {
+      let ref_po = PlaneOffset {
+        x: x as usize,
+        y: y as usize
+      };
+      let sad = get_sad(
+        &fs.input.planes[0].slice(&po),
+        &rec.frame.planes[0].slice(&ref_po),
+        blk_w,
+        blk_h
+      );
+
+      if sad < lowest_sad {
+        lowest_sad = sad;
+        best_mv = MotionVector {
+          row: 8 * (y - po.y as isize) as i16,
+          col: 8 * (x - po.x as isize) as i16
+        };
+      }
+    }
+  }
+
+  // The full-pel search reads the padding of the reference as is, while
+  // the prediction replicates the frame edges instead
+  let mut tmp = Plane::new(blk_w, blk_h, 0, 0);
+  lowest_sad = get_mc_sad(fi, fs, &mut tmp, &po, bsize, ref_frame, &best_mv);
+
+  let steps: &[i16] = if fi.allow_high_precision_mv {
+    &[4, 2, 1]
+  } else {
+    &[4, 2]
+  };
+
+  for &step in steps {
+    let center = best_mv;
+    for &(i, j) in
+      &[(-1, -1), (-1, 0), (-1, 1), (0, -1), (0, 1), (1, -1), (1, 0), (1, 1)]
+    {
+      let cand_mv = MotionVector {
+        row: center.row + step * i,
+        col: center.col + step * j
+      };
+      let sad = get_mc_sad(fi, fs, &mut tmp, &po, bsize, ref_frame, &cand_mv);
+
+      if sad < lowest_sad {
+        lowest_sad = sad;
+        best_mv = cand_mv;
+      }
+    }
+  }
+
+  best_mv
+}
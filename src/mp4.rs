@@ -0,0 +1,459 @@
+// Copyright (c) 2018, The rav1e contributors. All rights reserved
+//
+// This source code is subject to the terms of the BSD 2 Clause License and
+// the Alliance for Open Media Patent License 1.0. If the BSD 2 Clause License
+// was not distributed with this source code in the LICENSE file, you can
+// obtain it at www.aomedia.org/license/software. If the Alliance for Open
+// Media Patent License 1.0 was not distributed with this source code in the
+// PATENTS file, you can obtain it at www.aomedia.org/license/patent.
+
+//! Minimal ISOBMFF/MP4 muxer for a single AV1 video track.
+//!
+//! Each sample is written as it comes in its own `mdat` box, so that the
+//! output needs no seeking, and `finish` writes the `moov` box indexing them
+//! at the end.
+
+use super::*;
+
+/// Unity transformation matrix of the `mvhd` and `tkhd` boxes
+const MATRIX: [u32; 9] =
+  [0x00010000, 0, 0, 0, 0x00010000, 0, 0, 0, 0x40000000];
+
+pub fn write_box(buf: &mut Vec<u8>, box_type: &[u8; 4], payload: &[u8]) {
+  let mut bw = BitWriter::<BE>::new(buf);
+  bw.write(32, (payload.len() + 8) as u32).unwrap();
+  bw.write_bytes(box_type).unwrap();
+  bw.write_bytes(payload).unwrap();
+}
+
+/// Header of an `mdat` box holding `size` bytes, with a 64-bit largesize
+/// when the box does not fit in 4 GiB.
+fn mdat_header(size: u64) -> Vec<u8> {
+  let mut header = Vec::new();
+  {
+    let mut bw = BitWriter::<BE>::new(&mut header);
+    if size + 8 <= u32::max_value() as u64 {
+      bw.write(32, size as u32 + 8).unwrap();
+      bw.write_bytes(b"mdat").unwrap();
+    } else {
+      bw.write(32, 1).unwrap(); // size in largesize
+      bw.write_bytes(b"mdat").unwrap();
+      bw.write(64, size + 16).unwrap();
+    }
+  }
+  header
+}
+
+pub fn write_full_box(
+  buf: &mut Vec<u8>, box_type: &[u8; 4], version: u8, flags: u32,
+  payload: &[u8]
+) {
+  let mut full = Vec::with_capacity(payload.len() + 4);
+  {
+    let mut bw = BitWriter::<BE>::new(&mut full);
+    bw.write(8, version).unwrap();
+    bw.write(24, flags).unwrap();
+    bw.write_bytes(payload).unwrap();
+  }
+  write_box(buf, box_type, &full);
+}
+
+struct Sample {
+  size: u32,
+  sync: bool,
+  /// Offset of the sample in the output
+  offset: u64
+}
+
+pub struct Mp4Muxer {
+  output: Output,
+  width: usize,
+  height: usize,
+  timescale: u32,
+  sample_duration: u32,
+  av1c: Vec<u8>,
+  samples: Vec<Sample>,
+  /// Size of the output so far
+  position: u64
+}
+
+impl Mp4Muxer {
+  pub fn new(
+    output: Output, sequence: &Sequence, num: usize, den: usize
+  ) -> Result<Mp4Muxer, EncoderError> {
+    let mut muxer = Mp4Muxer {
+      output,
+      width: sequence.max_frame_width as usize,
+      height: sequence.max_frame_height as usize,
+      timescale: num as u32,
+      sample_duration: den as u32,
+      av1c: av1c_record(sequence)?,
+      samples: Vec::new(),
+      position: 0
+    };
+    let mut ftyp = Vec::new();
+    muxer.ftyp(&mut ftyp);
+    muxer.output.write_all(&ftyp)?;
+    muxer.position = ftyp.len() as u64;
+    Ok(muxer)
+  }
+
+  fn duration(&self) -> u32 {
+    self.samples.len() as u32 * self.sample_duration
+  }
+
+  fn ftyp(&self, buf: &mut Vec<u8>) {
+    let mut ftyp = Vec::new();
+    ftyp.extend_from_slice(b"isom"); // major_brand
+    ftyp.extend_from_slice(&[0, 0, 2, 0]); // minor_version
+    for brand in &[b"isom", b"iso2", b"av01", b"mp41"] {
+      ftyp.extend_from_slice(*brand);
+    }
+    write_box(buf, b"ftyp", &ftyp);
+  }
+
+  fn mvhd(&self, buf: &mut Vec<u8>) {
+    let mut mvhd = Vec::new();
+    {
+      let mut bw = BitWriter::<BE>::new(&mut mvhd);
+      bw.write(32, 0).unwrap(); // creation_time
+      bw.write(32, 0).unwrap(); // modification_time
+      bw.write(32, self.timescale).unwrap();
+      bw.write(32, self.duration()).unwrap();
+      bw.write(32, 0x00010000).unwrap(); // rate 1.0
+      bw.write(16, 0x0100).unwrap(); // volume 1.0
+      bw.write(16, 0).unwrap(); // reserved
+      bw.write(32, 0).unwrap(); // reserved
+      bw.write(32, 0).unwrap(); // reserved
+      for &m in MATRIX.iter() {
+        bw.write(32, m).unwrap();
+      }
+      for _ in 0..6 {
+        bw.write(32, 0).unwrap(); // pre_defined
+      }
+      bw.write(32, 2).unwrap(); // next_track_ID
+    }
+    write_full_box(buf, b"mvhd", 0, 0, &mvhd);
+  }
+
+  fn tkhd(&self, buf: &mut Vec<u8>) {
+    let mut tkhd = Vec::new();
+    {
+      let mut bw = BitWriter::<BE>::new(&mut tkhd);
+      bw.write(32, 0).unwrap(); // creation_time
+      bw.write(32, 0).unwrap(); // modification_time
+      bw.write(32, 1).unwrap(); // track_ID
+      bw.write(32, 0).unwrap(); // reserved
+      bw.write(32, self.duration()).unwrap();
+      bw.write(32, 0).unwrap(); // reserved
+      bw.write(32, 0).unwrap(); // reserved
+      bw.write(16, 0).unwrap(); // layer
+      bw.write(16, 0).unwrap(); // alternate_group
+      bw.write(16, 0).unwrap(); // volume
+      bw.write(16, 0).unwrap(); // reserved
+      for &m in MATRIX.iter() {
+        bw.write(32, m).unwrap();
+      }
+      bw.write(32, (self.width as u32) << 16).unwrap(); // 16.16 fixed point
+      bw.write(32, (self.height as u32) << 16).unwrap();
+    }
+    // track_enabled | track_in_movie
+    write_full_box(buf, b"tkhd", 0, 0x3, &tkhd);
+  }
+
+  fn mdia(&self, buf: &mut Vec<u8>) {
+    let mut mdhd = Vec::new();
+    {
+      let mut bw = BitWriter::<BE>::new(&mut mdhd);
+      bw.write(32, 0).unwrap(); // creation_time
+      bw.write(32, 0).unwrap(); // modification_time
+      bw.write(32, self.timescale).unwrap();
+      bw.write(32, self.duration()).unwrap();
+      bw.write(16, 0x55C4).unwrap(); // language "und"
+      bw.write(16, 0).unwrap(); // pre_defined
+    }
+
+    let mut hdlr = Vec::new();
+    {
+      let mut bw = BitWriter::<BE>::new(&mut hdlr);
+      bw.write(32, 0).unwrap(); // pre_defined
+      bw.write_bytes(b"vide").unwrap(); // handler_type
+      for _ in 0..3 {
+        bw.write(32, 0).unwrap(); // reserved
+      }
+      bw.write_bytes(b"VideoHandler\0").unwrap(); // name
+    }
+
+    let mut mdia = Vec::new();
+    write_full_box(&mut mdia, b"mdhd", 0, 0, &mdhd);
+    write_full_box(&mut mdia, b"hdlr", 0, 0, &hdlr);
+    self.minf(&mut mdia);
+    write_box(buf, b"mdia", &mdia);
+  }
+
+  fn minf(&self, buf: &mut Vec<u8>) {
+    let mut minf = Vec::new();
+    // graphicsmode and opcolor are all zero
+    write_full_box(&mut minf, b"vmhd", 0, 1, &[0; 8]);
+
+    let mut dref = vec![0, 0, 0, 1]; // entry_count
+    // self-contained, the media data is in this file
+    write_full_box(&mut dref, b"url ", 0, 1, &[]);
+    let mut dinf = Vec::new();
+    write_full_box(&mut dinf, b"dref", 0, 0, &dref);
+    write_box(&mut minf, b"dinf", &dinf);
+
+    self.stbl(&mut minf);
+    write_box(buf, b"minf", &minf);
+  }
+
+  fn av01(&self, buf: &mut Vec<u8>) {
+    let mut av01 = Vec::new();
+    {
+      let mut bw = BitWriter::<BE>::new(&mut av01);
+      bw.write_bytes(&[0; 6]).unwrap(); // reserved
+      bw.write(16, 1).unwrap(); // data_reference_index
+      bw.write(16, 0).unwrap(); // pre_defined
+      bw.write(16, 0).unwrap(); // reserved
+      for _ in 0..3 {
+        bw.write(32, 0).unwrap(); // pre_defined
+      }
+      bw.write(16, self.width as u16).unwrap();
+      bw.write(16, self.height as u16).unwrap();
+      bw.write(32, 0x00480000).unwrap(); // horizresolution 72 dpi
+      bw.write(32, 0x00480000).unwrap(); // vertresolution 72 dpi
+      bw.write(32, 0).unwrap(); // reserved
+      bw.write(16, 1).unwrap(); // frame_count
+      bw.write_bytes(&[0; 32]).unwrap(); // compressorname
+      bw.write(16, 0x0018).unwrap(); // depth
+      bw.write(16, 0xFFFF).unwrap(); // pre_defined
+    }
+    write_box(&mut av01, b"av1C", &self.av1c);
+    write_box(buf, b"av01", &av01);
+  }
+
+  fn stbl(&self, buf: &mut Vec<u8>) {
+    let sample_count = self.samples.len() as u32;
+
+    let mut stsd = vec![0, 0, 0, 1]; // entry_count
+    self.av01(&mut stsd);
+
+    // Every sample lasts one frame
+    let mut stts = Vec::new();
+    {
+      let mut bw = BitWriter::<BE>::new(&mut stts);
+      bw.write(32, 1).unwrap(); // entry_count
+      bw.write(32, sample_count).unwrap();
+      bw.write(32, self.sample_duration).unwrap();
+    }
+
+    let mut stss = Vec::new();
+    {
+      let sync_samples: Vec<u32> = self.samples.iter().enumerate()
+        .filter(|&(_, sample)| sample.sync)
+        .map(|(i, _)| i as u32 + 1)
+        .collect();
+      let mut bw = BitWriter::<BE>::new(&mut stss);
+      bw.write(32, sync_samples.len() as u32).unwrap();
+      for sample_number in sync_samples {
+        bw.write(32, sample_number).unwrap();
+      }
+    }
+
+    // Every sample is a chunk of its own
+    let mut stsc = Vec::new();
+    {
+      let mut bw = BitWriter::<BE>::new(&mut stsc);
+      bw.write(32, 1).unwrap(); // entry_count
+      bw.write(32, 1).unwrap(); // first_chunk
+      bw.write(32, 1).unwrap(); // samples_per_chunk
+      bw.write(32, 1).unwrap(); // sample_description_index
+    }
+
+    let mut stsz = Vec::new();
+    {
+      let mut bw = BitWriter::<BE>::new(&mut stsz);
+      bw.write(32, 0).unwrap(); // sample_size varies
+      bw.write(32, sample_count).unwrap();
+      for sample in &self.samples {
+        bw.write(32, sample.size).unwrap();
+      }
+    }
+
+    // 64-bit chunk offsets are only used past 4 GiB
+    let large_offsets = self.samples.last()
+      .map_or(false, |sample| sample.offset > u32::max_value() as u64);
+    let mut stco = Vec::new();
+    {
+      let mut bw = BitWriter::<BE>::new(&mut stco);
+      bw.write(32, sample_count).unwrap(); // entry_count
+      for sample in &self.samples {
+        if large_offsets {
+          bw.write(64, sample.offset).unwrap();
+        } else {
+          bw.write(32, sample.offset as u32).unwrap();
+        }
+      }
+    }
+
+    let mut stbl = Vec::new();
+    write_full_box(&mut stbl, b"stsd", 0, 0, &stsd);
+    write_full_box(&mut stbl, b"stts", 0, 0, &stts);
+    write_full_box(&mut stbl, b"stss", 0, 0, &stss);
+    write_full_box(&mut stbl, b"stsc", 0, 0, &stsc);
+    write_full_box(&mut stbl, b"stsz", 0, 0, &stsz);
+    let stco_type = if large_offsets { b"co64" } else { b"stco" };
+    write_full_box(&mut stbl, stco_type, 0, 0, &stco);
+    write_box(buf, b"stbl", &stbl);
+  }
+
+  fn moov(&self) -> Vec<u8> {
+    let mut trak = Vec::new();
+    self.tkhd(&mut trak);
+    self.mdia(&mut trak);
+
+    let mut moov = Vec::new();
+    self.mvhd(&mut moov);
+    write_box(&mut moov, b"trak", &trak);
+    let mut buf = Vec::new();
+    write_box(&mut buf, b"moov", &moov);
+    buf
+  }
+}
+
+impl Muxer for Mp4Muxer {
+  fn write_frame(
+    &mut self, _pts: u64, data: &[u8], frame_type: FrameType
+  ) -> Result<(), EncoderError> {
+    let data = strip_temporal_delimiter(data);
+    if data.len() > u32::max_value() as usize {
+      return Err(EncoderError::UnsupportedInput(
+        "frames are limited to 4 GiB in MP4".to_string()
+      ));
+    }
+    let header = mdat_header(data.len() as u64);
+    self.output.write_all(&header)?;
+    self.output.write_all(data)?;
+    self.samples.push(Sample {
+      size: data.len() as u32,
+      sync: frame_type == FrameType::KEY,
+      offset: self.position + header.len() as u64
+    });
+    self.position += (header.len() + data.len()) as u64;
+    Ok(())
+  }
+
+  fn finish(&mut self) -> Result<(), EncoderError> {
+    let moov = self.moov();
+    self.output.write_all(&moov)?;
+    self.output.flush()?;
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  // Boxes of `data` by type, with the payload following a largesize
+  fn boxes(mut data: &[u8]) -> Vec<([u8; 4], &[u8])> {
+    let be = |b: &[u8]| b.iter().fold(0u64, |v, &b| v << 8 | b as u64);
+    let mut boxes = Vec::new();
+    while !data.is_empty() {
+      let mut box_type = [0; 4];
+      box_type.copy_from_slice(&data[4..8]);
+      let (size, header) = match be(&data[..4]) {
+        1 => (be(&data[8..16]) as usize, 16),
+        size => (size as usize, 8)
+      };
+      boxes.push((box_type, &data[header..size]));
+      data = &data[size..];
+    }
+    boxes
+  }
+
+  fn find<'a>(data: &'a [u8], box_type: &[u8; 4]) -> &'a [u8] {
+    boxes(data).into_iter().find(|b| &b.0 == box_type).unwrap().1
+  }
+
+  fn be32(data: &[u8], i: usize) -> u32 {
+    data[4 * i..4 * i + 4].iter().fold(0, |v, &b| v << 8 | b as u32)
+  }
+
+  #[test]
+  fn mdat_largesize() {
+    assert_eq!(mdat_header(100), [0, 0, 0, 108, b'm', b'd', b'a', b't']);
+    let header = mdat_header(1 << 32);
+    assert_eq!(&header[..8], [0, 0, 0, 1, b'm', b'd', b'a', b't']);
+    assert_eq!(&header[8..], [0, 0, 0, 1, 0, 0, 0, 16]);
+  }
+
+  #[test]
+  fn large_chunk_offsets() {
+    let seq = Sequence::new(64, 64);
+    let data = ::test::muxed("large_chunk_offsets.mp4", |output| {
+      let mut muxer = Mp4Muxer::new(output, &seq, 30, 1)?;
+      muxer.samples = [100, 1 << 32]
+        .iter()
+        .map(|&offset| Sample { size: 1, sync: true, offset })
+        .collect();
+      let mut stbl = Vec::new();
+      muxer.stbl(&mut stbl);
+      muxer.output.write_all(&stbl)?;
+      Ok(())
+    });
+    let stbl = find(&data, b"stbl");
+    let co64 = find(stbl, b"co64");
+    // version and flags, entry_count, then the offsets
+    assert_eq!(be32(co64, 1), 2);
+    assert_eq!(&co64[8..], [0, 0, 0, 0, 0, 0, 0, 100, 0, 0, 0, 1, 0, 0, 0, 0]);
+  }
+
+  #[test]
+  fn box_layout() {
+    let seq = Sequence::new(64, 64);
+    let frames: [(&[u8], FrameType); 3] = [
+      (&[0x12, 0, 0xA, 0xB], FrameType::KEY),
+      (&[0xC, 0xD, 0xE], FrameType::INTER),
+      (&[0xF], FrameType::KEY)
+    ];
+    let data = ::test::muxed("box_layout.mp4", |output| {
+      let mut muxer = Mp4Muxer::new(output, &seq, 30, 1)?;
+      for (pts, &(frame, frame_type)) in frames.iter().enumerate() {
+        muxer.write_frame(pts as u64, frame, frame_type)?;
+      }
+      muxer.finish()
+    });
+
+    let top: Vec<[u8; 4]> = boxes(&data).iter().map(|b| b.0).collect();
+    assert_eq!(top, [*b"ftyp", *b"mdat", *b"mdat", *b"mdat", *b"moov"]);
+    let ftyp = find(&data, b"ftyp");
+    assert_eq!(&ftyp[..4], b"isom");
+    assert!(ftyp[8..].chunks(4).any(|brand| brand == b"av01"));
+
+    let stbl = find(
+      find(find(find(find(&data, b"moov"), b"trak"), b"mdia"), b"minf"),
+      b"stbl"
+    );
+    // Full boxes start with their version and flags
+    let stsz = find(stbl, b"stsz");
+    assert_eq!(
+      (1..6).map(|i| be32(stsz, i)).collect::<Vec<_>>(),
+      [0, 3, 2, 3, 1]
+    );
+    let stss = find(stbl, b"stss");
+    assert_eq!((1..4).map(|i| be32(stss, i)).collect::<Vec<_>>(), [2, 1, 3]);
+    let stco = find(stbl, b"stco");
+    assert_eq!(be32(stco, 1), 3);
+    for (i, &(frame, _)) in frames.iter().enumerate() {
+      let frame = strip_temporal_delimiter(frame);
+      let chunk_offset = be32(stco, i + 2) as usize;
+      assert_eq!(&data[chunk_offset..chunk_offset + frame.len()], frame);
+    }
+
+    // The sample entry precedes its boxes by 78 bytes, after the
+    // entry_count of stsd
+    let av01 = find(&find(stbl, b"stsd")[8..], b"av01");
+    assert_eq!(find(&av01[78..], b"av1C"), &av1c_record(&seq).unwrap()[..]);
+  }
+}
@@ -19,6 +19,12 @@ pub enum PartitionType {
   PARTITION_HORZ,
   PARTITION_VERT,
   PARTITION_SPLIT,
+  PARTITION_HORZ_A,
+  PARTITION_HORZ_B,
+  PARTITION_VERT_A,
+  PARTITION_VERT_B,
+  PARTITION_HORZ_4,
+  PARTITION_VERT_4,
   PARTITION_INVALID
 }
 
@@ -115,7 +121,7 @@ pub enum TxSize {
 
 impl TxSize {
   /// Number of square transform sizes
-  pub const TX_SIZES: usize = 4;
+  pub const TX_SIZES: usize = 5;
 
   /// Number of transform sizes (including non-square sizes)
   pub const TX_SIZES_ALL: usize = 14 + 5;
@@ -313,44 +319,64 @@ pub enum GlobalMVMode {
 }
 
 use context::*;
+use gm::*;
 use plane::*;
 use predict::*;
+use std::cmp;
+use FrameInvariants;
+use LAST_FRAME;
+use NONE_FRAME;
 
 impl PredictionMode {
-  pub fn predict<'a>(self, dst: &'a mut PlaneMutSlice<'a>, tx_size: TxSize) {
+  /// Predict the intra block at dst from the edges of its neighbours to the
+  /// left and above, when they are available in the tile.
+  pub fn predict<'a>(
+    self, dst: &'a mut PlaneMutSlice<'a>, tx_size: TxSize, bit_depth: usize,
+    have_left: bool, have_above: bool
+  ) {
+    let edges = (have_left, have_above);
     match tx_size {
-      TxSize::TX_4X4 => self.predict_inner::<Block4x4>(dst),
-      TxSize::TX_8X8 => self.predict_inner::<Block8x8>(dst),
-      TxSize::TX_16X16 => self.predict_inner::<Block16x16>(dst),
-      TxSize::TX_32X32 => self.predict_inner::<Block32x32>(dst),
+      TxSize::TX_4X4 => self.predict_inner::<Block4x4>(dst, bit_depth, edges),
+      TxSize::TX_8X8 => self.predict_inner::<Block8x8>(dst, bit_depth, edges),
+      TxSize::TX_16X16 =>
+        self.predict_inner::<Block16x16>(dst, bit_depth, edges),
+      TxSize::TX_32X32 =>
+        self.predict_inner::<Block32x32>(dst, bit_depth, edges),
+      TxSize::TX_4X8 => self.predict_inner::<Block4x8>(dst, bit_depth, edges),
+      TxSize::TX_8X16 =>
+        self.predict_inner::<Block8x16>(dst, bit_depth, edges),
+      TxSize::TX_16X32 =>
+        self.predict_inner::<Block16x32>(dst, bit_depth, edges),
       _ => unimplemented!()
     }
   }
 
   #[inline(always)]
-  fn predict_inner<'a, B: Intra>(self, dst: &'a mut PlaneMutSlice<'a>) {
+  fn predict_inner<'a, B: Intra>(
+    self, dst: &'a mut PlaneMutSlice<'a>, bit_depth: usize,
+    (have_left, have_above): (bool, bool)
+  ) {
     // above and left arrays include above-left sample
     // above array includes above-right samples
     // left array includes below-left samples
-    let above = &mut [127u16; 2 * MAX_TX_SIZE + 1][..B::W + B::H + 1];
-    let left = &mut [129u16; 2 * MAX_TX_SIZE + 1][..B::H + B::W + 1];
+    let base = 1u16 << (bit_depth - 1);
+    let above = &mut [base - 1; 2 * MAX_TX_SIZE + 1][..B::W + B::H + 1];
+    let left = &mut [base + 1; 2 * MAX_TX_SIZE + 1][..B::H + B::W + 1];
 
     let stride = dst.plane.cfg.stride;
-    let x = dst.x;
-    let y = dst.y;
 
-    if self != PredictionMode::H_PRED && y != 0 {
+    if self != PredictionMode::H_PRED && have_above {
       above[1..B::W + 1].copy_from_slice(&dst.go_up(1).as_slice()[..B::W]);
     }
 
-    if self != PredictionMode::V_PRED && x != 0 {
+    if self != PredictionMode::V_PRED && have_left {
       let left_slice = dst.go_left(1);
       for i in 0..B::H {
         left[i + 1] = left_slice.p(0, i);
       }
     }
 
-    if self == PredictionMode::PAETH_PRED && x != 0 && y != 0 {
+    if self == PredictionMode::PAETH_PRED && have_left && have_above {
       above[0] = dst.go_up(1).go_left(1).p(0, 0);
       left[0] = above[0];
     }
@@ -360,26 +386,138 @@ impl PredictionMode {
     let left_slice = &left[1..B::H + 1];
 
     match self {
-      PredictionMode::DC_PRED => match (x, y) {
-        (0, 0) => B::pred_dc_128(slice, stride),
-        (_, 0) => B::pred_dc_left(slice, stride, above_slice, left_slice),
-        (0, _) => B::pred_dc_top(slice, stride, above_slice, left_slice),
-        _ => B::pred_dc(slice, stride, above_slice, left_slice)
+      PredictionMode::DC_PRED => match (have_left, have_above) {
+        (false, false) => B::pred_dc_128(slice, stride, bit_depth),
+        (true, false) => B::pred_dc_left(slice, stride, left_slice),
+        (false, true) => B::pred_dc_top(slice, stride, above_slice),
+        (true, true) => B::pred_dc(slice, stride, above_slice, left_slice)
       },
       PredictionMode::H_PRED => B::pred_h(slice, stride, left_slice),
       PredictionMode::V_PRED => B::pred_v(slice, stride, above_slice),
       PredictionMode::PAETH_PRED =>
         B::pred_paeth(slice, stride, above_slice, left_slice, above[0]),
-      PredictionMode::SMOOTH_PRED =>
-        B::pred_smooth(slice, stride, above_slice, left_slice, 8),
-      PredictionMode::SMOOTH_H_PRED =>
-        B::pred_smooth_h(slice, stride, above_slice, left_slice, 8),
-      PredictionMode::SMOOTH_V_PRED =>
-        B::pred_smooth_v(slice, stride, above_slice, left_slice, 8),
+      PredictionMode::SMOOTH_PRED => B::pred_smooth(
+        slice,
+        stride,
+        above_slice,
+        left_slice,
+        bit_depth as u8
+      ),
+      PredictionMode::SMOOTH_H_PRED => B::pred_smooth_h(
+        slice,
+        stride,
+        above_slice,
+        left_slice,
+        bit_depth as u8
+      ),
+      PredictionMode::SMOOTH_V_PRED => B::pred_smooth_v(
+        slice,
+        stride,
+        above_slice,
+        left_slice,
+        bit_depth as u8
+      ),
       _ => unimplemented!()
     }
   }
 
+  /// Predict a `width`x`height` block of plane `p` at `po` from
+  /// `ref_frame`, displaced by `mv`.
+  pub fn predict_inter<'a>(
+    self, fi: &FrameInvariants, p: usize, po: &PlaneOffset,
+    dst: &'a mut PlaneMutSlice<'a>, width: usize, height: usize,
+    bsize: BlockSize, ref_frames: &[usize; 2], mvs: &[MotionVector; 2]
+  ) {
+    assert!(!self.is_intra());
+
+    let is_compound = ref_frames[1] != NONE_FRAME;
+    let round0 = if fi.bit_depth == 12 {
+      5
+    } else {
+      3
+    };
+    let round1 = if is_compound {
+      7
+    } else if fi.bit_depth == 12 {
+      9
+    } else {
+      11
+    };
+    let max_sample_val = (1 << fi.bit_depth) - 1;
+
+    // GLOBALMV blocks of at least 8x8 are warped by the global motion of
+    // their references when it is more than a translation
+    let is_global = (self == PredictionMode::ZEROMV
+      || self == PredictionMode::ZERO_ZEROMV)
+      && cmp::min(bsize.width(), bsize.height()) >= 8;
+    let predict = |i: usize, pred: &mut [i32]| {
+      let ref_frame = ref_frames[i];
+      let shear = if is_global
+        && fi.globalmv_transformation_type[ref_frame]
+          > GlobalMVMode::TRANSLATION
+      {
+        setup_shear(&fi.gm_params[ref_frame])
+      } else {
+        None
+      };
+      match shear {
+        Some(shear) => warp_block(
+          fi, p, po, width, height, ref_frame, &shear, round0, round1, pred
+        ),
+        None => mc_block(
+          fi, p, po, width, height, ref_frame, &mvs[i], round0, round1, pred
+        )
+      }
+    };
+
+    let stride = dst.plane.cfg.stride;
+    let slice = dst.as_mut_slice();
+    if !is_compound {
+      let mut pred = [0i32; 64 * 64];
+      predict(0, &mut pred);
+      for r in 0..height {
+        for c in 0..width {
+          let v = pred[r * width + c];
+          slice[r * stride + c] = clamp(v, 0, max_sample_val) as u16;
+        }
+      }
+    } else {
+      // Average of the two predictions kept at a higher precision
+      let mut preds = [[0i32; 64 * 64]; 2];
+      for (i, pred) in preds.iter_mut().enumerate() {
+        predict(i, pred);
+      }
+      let shift = 1 + 2 * 7 - round0 - round1;
+      for r in 0..height {
+        for c in 0..width {
+          let sum = preds[0][r * width + c] + preds[1][r * width + c];
+          let v = (sum + (1 << (shift - 1))) >> shift;
+          slice[r * stride + c] = clamp(v, 0, max_sample_val) as u16;
+        }
+      }
+    }
+  }
+
+  pub fn is_intra(self) -> bool {
+    self < PredictionMode::NEARESTMV
+  }
+
+  pub fn has_nearmv(self) -> bool {
+    self == PredictionMode::NEARMV
+      || self == PredictionMode::NEAR_NEARMV
+      || self == PredictionMode::NEAR_NEWMV
+      || self == PredictionMode::NEW_NEARMV
+  }
+
+  pub fn has_newmv(self) -> bool {
+    self == PredictionMode::NEWMV
+      || self == PredictionMode::NEW_NEWMV
+      || self == PredictionMode::NEAREST_NEWMV
+      || self == PredictionMode::NEW_NEARESTMV
+      || self == PredictionMode::NEAR_NEWMV
+      || self == PredictionMode::NEW_NEARMV
+  }
+
   pub fn is_directional(self) -> bool {
     self >= PredictionMode::V_PRED && self <= PredictionMode::D63_PRED
   }
@@ -409,6 +547,137 @@ pub enum TxSetType {
   EXT_TX_SET_ALL16
 }
 
+// Motion compensated prediction of a block of plane p from ref_frame,
+// rounded by round0 after the horizontal filter and round1 after the
+// vertical one
+fn mc_block(
+  fi: &FrameInvariants, p: usize, po: &PlaneOffset, width: usize,
+  height: usize, ref_frame: usize, mv: &MotionVector, round0: i32,
+  round1: i32, pred: &mut [i32]
+) {
+  assert!(ref_frame >= LAST_FRAME);
+
+  let rec = fi.ref_frames[ref_frame - LAST_FRAME].as_ref().unwrap();
+  let ref_plane = &rec.frame.planes[p];
+  let PlaneConfig {
+    xdec,
+    ydec,
+    ..
+  } = ref_plane.cfg;
+  let max_x = ((fi.width + xdec) >> xdec) as i32 - 1;
+  let max_y = ((fi.height + ydec) >> ydec) as i32 - 1;
+
+  // Position in 1/16 sample units
+  let x = ((po.x as i32) << 4) + ((2 * mv.col as i32) >> xdec);
+  let y = ((po.y as i32) << 4) + ((2 * mv.row as i32) >> ydec);
+  let filter_x = &subpel_filters[(width <= 4) as usize][(x & 15) as usize];
+  let filter_y = &subpel_filters[(height <= 4) as usize][(y & 15) as usize];
+  let (x0, y0) = (x >> 4, y >> 4);
+
+  let mut intermediate = [0i32; (64 + 7) * 64];
+  for r in 0..height + 7 {
+    let ry = clamp(y0 + r as i32 - 3, 0, max_y) as usize;
+    for c in 0..width {
+      let mut sum = 0;
+      for (t, f) in filter_x.iter().enumerate() {
+        let rx = clamp(x0 + (c + t) as i32 - 3, 0, max_x) as usize;
+        sum += f * ref_plane.p(rx, ry) as i32;
+      }
+      intermediate[r * width + c] = (sum + (1 << (round0 - 1))) >> round0;
+    }
+  }
+
+  for r in 0..height {
+    for c in 0..width {
+      let mut sum = 0;
+      for (t, f) in filter_y.iter().enumerate() {
+        sum += f * intermediate[(r + t) * width + c];
+      }
+      pred[r * width + c] = (sum + (1 << (round1 - 1))) >> round1;
+    }
+  }
+}
+
+const WARPEDDIFF_PREC_BITS: usize = 10;
+
+// Prediction of a block of plane p from ref_frame warped by its global
+// motion, with the shears of setup_shear, in 8x8 blocks rounded as in
+// mc_block
+fn warp_block(
+  fi: &FrameInvariants, p: usize, po: &PlaneOffset, width: usize,
+  height: usize, ref_frame: usize, shear: &[i32; 4], round0: i32,
+  round1: i32, pred: &mut [i32]
+) {
+  assert!(ref_frame >= LAST_FRAME);
+
+  let rec = fi.ref_frames[ref_frame - LAST_FRAME].as_ref().unwrap();
+  let ref_plane = &rec.frame.planes[p];
+  let PlaneConfig {
+    xdec,
+    ydec,
+    ..
+  } = ref_plane.cfg;
+  let max_x = ((fi.width + xdec) >> xdec) as i32 - 1;
+  let max_y = ((fi.height + ydec) >> ydec) as i32 - 1;
+  let params = &fi.gm_params[ref_frame];
+  let (alpha, beta, gamma, delta) = (shear[0], shear[1], shear[2], shear[3]);
+  let filter = |pos: i32| {
+    let offs = (pos + (1 << (WARPEDDIFF_PREC_BITS - 1)))
+      >> WARPEDDIFF_PREC_BITS;
+    &warped_filter[(offs + WARPEDPIXEL_PREC_SHIFTS as i32) as usize]
+  };
+
+  for i8 in 0..(height + 7) / 8 {
+    for j8 in 0..(width + 7) / 8 {
+      // Position in the reference of the center of the 8x8 block
+      let src_x = ((po.x + j8 * 8 + 4) << xdec) as i64;
+      let src_y = ((po.y + i8 * 8 + 4) << ydec) as i64;
+      let dst_x = params[2] as i64 * src_x
+        + params[3] as i64 * src_y
+        + params[0] as i64;
+      let dst_y = params[4] as i64 * src_x
+        + params[5] as i64 * src_y
+        + params[1] as i64;
+      let (x4, y4) = (dst_x >> xdec, dst_y >> ydec);
+      let ix4 = (x4 >> WARPEDMODEL_PREC_BITS) as i32;
+      let sx4 = (x4 & ((1 << WARPEDMODEL_PREC_BITS) - 1)) as i32;
+      let iy4 = (y4 >> WARPEDMODEL_PREC_BITS) as i32;
+      let sy4 = (y4 & ((1 << WARPEDMODEL_PREC_BITS) - 1)) as i32;
+
+      let mut intermediate = [[0i32; 8]; 15];
+      for i1 in -7..8 {
+        let ry = clamp(iy4 + i1, 0, max_y) as usize;
+        for i2 in -4..4 {
+          let taps = filter(sx4 + alpha * i2 + beta * i1);
+          let mut sum = 0;
+          for (t, &f) in taps.iter().enumerate() {
+            let rx = clamp(ix4 + i2 - 3 + t as i32, 0, max_x) as usize;
+            sum += f as i32 * ref_plane.p(rx, ry) as i32;
+          }
+          intermediate[(i1 + 7) as usize][(i2 + 4) as usize] =
+            (sum + (1 << (round0 - 1))) >> round0;
+        }
+      }
+
+      let rows = cmp::min(8, height - i8 * 8) as i32;
+      let cols = cmp::min(8, width - j8 * 8) as i32;
+      for i1 in -4..rows - 4 {
+        for i2 in -4..cols - 4 {
+          let taps = filter(sy4 + gamma * i2 + delta * i1);
+          let mut sum = 0;
+          for (t, &f) in taps.iter().enumerate() {
+            sum += f as i32 * intermediate[(i1 + t as i32 + 4) as usize]
+              [(i2 + 4) as usize];
+          }
+          let r = i8 * 8 + (i1 + 4) as usize;
+          let c = j8 * 8 + (i2 + 4) as usize;
+          pred[r * width + c] = (sum + (1 << (round1 - 1))) >> round1;
+        }
+      }
+    }
+  }
+}
+
 pub fn get_subsize(bsize: BlockSize, partition: PartitionType) -> BlockSize {
   subsize_lookup[partition as usize][bsize as usize]
 }
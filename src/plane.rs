@@ -10,6 +10,7 @@
 #![cfg_attr(feature = "cargo-clippy", allow(cast_lossless))]
 
 /// Plane-specific configuration.
+#[derive(Clone)]
 pub struct PlaneConfig {
   pub stride: usize,
   pub xdec: usize,
@@ -22,6 +23,7 @@ pub struct PlaneOffset {
   pub y: usize
 }
 
+#[derive(Clone)]
 pub struct Plane {
   pub data: Vec<u16>,
   pub cfg: PlaneConfig
@@ -59,6 +61,23 @@ impl Plane {
     self.data[y * self.cfg.stride + x]
   }
 
+  /// Replicate the right column and bottom row of the visible `w`x`h`
+  /// area into the rest of the plane.
+  pub fn pad(&mut self, w: usize, h: usize) {
+    let stride = self.cfg.stride;
+    for row in self.data.chunks_mut(stride).take(h) {
+      let edge = row[w - 1];
+      for v in &mut row[w..] {
+        *v = edge;
+      }
+    }
+    let (visible, padding) = self.data.split_at_mut(h * stride);
+    let last_row = &visible[(h - 1) * stride..];
+    for row in padding.chunks_mut(stride) {
+      row.copy_from_slice(last_row);
+    }
+  }
+
   pub fn copy_from_raw_u8(
     &mut self, source: &[u8], source_stride: usize, source_bytewidth: usize
   ) {
@@ -72,8 +91,10 @@ impl Plane {
         {
           *self_pixel = *source_pixel as u16;
         },
-        2 => for (self_pixel, bytes) in
-          self_row.iter_mut().zip(source_row.chunks(2))
+        // A last byte without its pair is not a sample
+        2 => for (self_pixel, bytes) in self_row
+          .iter_mut()
+          .zip(source_row.chunks(2).filter(|bytes| bytes.len() == 2))
         {
           *self_pixel = (bytes[1] as u16) << 8 | (bytes[0] as u16);
         },
@@ -82,6 +103,31 @@ impl Plane {
       }
     }
   }
+
+  pub fn copy_to_raw_u8(
+    &self, dest: &mut [u8], dest_stride: usize, dest_bytewidth: usize
+  ) {
+    let stride = self.cfg.stride;
+    for (self_row, dest_row) in
+      self.data.chunks(stride).zip(dest.chunks_mut(dest_stride))
+    {
+      match dest_bytewidth {
+        1 => for (self_pixel, dest_pixel) in
+          self_row.iter().zip(dest_row.iter_mut())
+        {
+          *dest_pixel = *self_pixel as u8;
+        },
+        2 => for (self_pixel, bytes) in
+          self_row.iter().zip(dest_row.chunks_mut(2))
+        {
+          bytes[0] = *self_pixel as u8;
+          bytes[1] = (*self_pixel >> 8) as u8;
+        },
+
+        _ => {}
+      }
+    }
+  }
 }
 
 pub struct PlaneSlice<'a> {
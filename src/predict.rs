@@ -11,8 +11,6 @@
 #![cfg_attr(feature = "cargo-clippy", allow(cast_lossless))]
 #![cfg_attr(feature = "cargo-clippy", allow(needless_range_loop))]
 
-use libc;
-
 use context::MAX_TX_SIZE;
 use partition::*;
 use std::mem::*;
@@ -27,6 +25,20 @@ pub static RAV1E_INTRA_MODES: &'static [PredictionMode] = &[
   PredictionMode::PAETH_PRED
 ];
 
+pub static RAV1E_INTER_MODES: &'static [PredictionMode] = &[
+  PredictionMode::ZEROMV,
+  PredictionMode::NEARESTMV,
+  PredictionMode::NEARMV,
+  PredictionMode::NEWMV
+];
+
+pub static RAV1E_INTER_COMPOUND_MODES: &'static [PredictionMode] = &[
+  PredictionMode::ZERO_ZEROMV,
+  PredictionMode::NEAREST_NEARESTMV,
+  PredictionMode::NEAR_NEARMV,
+  PredictionMode::NEW_NEWMV
+];
+
 // Intra prediction modes tested at high speed levels
 #[cfg_attr(rustfmt, rustfmt_skip)]
 pub static RAV1E_INTRA_MODES_MINIMAL: &'static [PredictionMode] = &[
@@ -62,59 +74,141 @@ static sm_weight_arrays: [u8; 2 * MAX_TX_SIZE] = [
     13, 12, 10, 9, 8, 7, 6, 6, 5, 5, 4, 4, 4,*/
 ];
 
-extern {
-  #[cfg(test)]
-  fn highbd_dc_predictor(
-    dst: *mut u16, stride: libc::ptrdiff_t, bw: libc::c_int, bh: libc::c_int,
-    above: *const u16, left: *const u16, bd: libc::c_int
-  );
-
-  fn highbd_dc_left_predictor(
-    dst: *mut u16, stride: libc::ptrdiff_t, bw: libc::c_int, bh: libc::c_int,
-    above: *const u16, left: *const u16, bd: libc::c_int
-  );
-
-  fn highbd_dc_top_predictor(
-    dst: *mut u16, stride: libc::ptrdiff_t, bw: libc::c_int, bh: libc::c_int,
-    above: *const u16, left: *const u16, bd: libc::c_int
-  );
-
-  #[cfg(test)]
-  fn highbd_h_predictor(
-    dst: *mut u16, stride: libc::ptrdiff_t, bw: libc::c_int, bh: libc::c_int,
-    above: *const u16, left: *const u16, bd: libc::c_int
-  );
-
-  #[cfg(test)]
-  fn highbd_v_predictor(
-    dst: *mut u16, stride: libc::ptrdiff_t, bw: libc::c_int, bh: libc::c_int,
-    above: *const u16, left: *const u16, bd: libc::c_int
-  );
-
-  #[cfg(test)]
-  fn highbd_paeth_predictor(
-    dst: *mut u16, stride: libc::ptrdiff_t, bw: libc::c_int, bh: libc::c_int,
-    above: *const u16, left: *const u16, bd: libc::c_int
-  );
-
-  #[cfg(test)]
-  fn highbd_smooth_predictor(
-    dst: *mut u16, stride: libc::ptrdiff_t, bw: libc::c_int, bh: libc::c_int,
-    above: *const u16, left: *const u16, bd: libc::c_int
-  );
-
-  #[cfg(test)]
-  fn highbd_smooth_h_predictor(
-    dst: *mut u16, stride: libc::ptrdiff_t, bw: libc::c_int, bh: libc::c_int,
-    above: *const u16, left: *const u16, bd: libc::c_int
-  );
-
-  #[cfg(test)]
-  fn highbd_smooth_v_predictor(
-    dst: *mut u16, stride: libc::ptrdiff_t, bw: libc::c_int, bh: libc::c_int,
-    above: *const u16, left: *const u16, bd: libc::c_int
-  );
-}
+// Sub-pixel interpolation filters of EIGHTTAP_REGULAR, indexed by the
+// 1/16 sample phase. The second set replaces the first for blocks with a
+// dimension of 4 or less.
+#[cfg_attr(rustfmt, rustfmt_skip)]
+pub static subpel_filters: [[[i32; 8]; 16]; 2] = [
+    [
+        [0, 0, 0, 128, 0, 0, 0, 0], [0, 2, -6, 126, 8, -2, 0, 0],
+        [0, 2, -10, 122, 18, -4, 0, 0], [0, 2, -12, 116, 28, -8, 2, 0],
+        [0, 2, -14, 110, 38, -10, 2, 0], [0, 2, -14, 102, 48, -12, 2, 0],
+        [0, 2, -16, 94, 58, -12, 2, 0], [0, 2, -14, 84, 66, -12, 2, 0],
+        [0, 2, -14, 76, 76, -14, 2, 0], [0, 2, -12, 66, 84, -14, 2, 0],
+        [0, 2, -12, 58, 94, -16, 2, 0], [0, 2, -12, 48, 102, -14, 2, 0],
+        [0, 2, -10, 38, 110, -14, 2, 0], [0, 2, -8, 28, 116, -12, 2, 0],
+        [0, 0, -4, 18, 122, -10, 2, 0], [0, 0, -2, 8, 126, -6, 2, 0]
+    ],
+    [
+        [0, 0, 0, 128, 0, 0, 0, 0], [0, 0, -4, 126, 8, -2, 0, 0],
+        [0, 0, -8, 122, 18, -4, 0, 0], [0, 0, -10, 116, 28, -6, 0, 0],
+        [0, 0, -12, 110, 38, -8, 0, 0], [0, 0, -12, 102, 48, -10, 0, 0],
+        [0, 0, -14, 94, 58, -10, 0, 0], [0, 0, -12, 84, 66, -10, 0, 0],
+        [0, 0, -12, 76, 76, -12, 0, 0], [0, 0, -10, 66, 84, -12, 0, 0],
+        [0, 0, -10, 58, 94, -14, 0, 0], [0, 0, -10, 48, 102, -12, 0, 0],
+        [0, 0, -8, 38, 110, -12, 0, 0], [0, 0, -6, 28, 116, -10, 0, 0],
+        [0, 0, -4, 18, 122, -8, 0, 0], [0, 0, -2, 8, 126, -4, 0, 0]
+    ]
+];
+
+pub const WARPEDPIXEL_PREC_SHIFTS: usize = 64;
+
+// Filters of the warped motion prediction at 1/64 sample positions over
+// [-1, 2), followed by a dummy filter for the rounding of the last position
+#[cfg_attr(rustfmt, rustfmt_skip)]
+pub static warped_filter: [[i16; 8]; 3 * WARPEDPIXEL_PREC_SHIFTS + 1] = [
+    // [-1, 0)
+    [0, 0, 127, 1, 0, 0, 0, 0], [0, -1, 127, 2, 0, 0, 0, 0],
+    [1, -3, 127, 4, -1, 0, 0, 0], [1, -4, 126, 6, -2, 1, 0, 0],
+    [1, -5, 126, 8, -3, 1, 0, 0], [1, -6, 125, 11, -4, 1, 0, 0],
+    [1, -7, 124, 13, -4, 1, 0, 0], [2, -8, 123, 15, -5, 1, 0, 0],
+    [2, -9, 122, 18, -6, 1, 0, 0], [2, -10, 121, 20, -6, 1, 0, 0],
+    [2, -11, 120, 22, -7, 2, 0, 0], [2, -12, 119, 25, -8, 2, 0, 0],
+    [3, -13, 117, 27, -8, 2, 0, 0], [3, -13, 116, 29, -9, 2, 0, 0],
+    [3, -14, 114, 32, -10, 3, 0, 0], [3, -15, 113, 35, -10, 2, 0, 0],
+    [3, -15, 111, 37, -11, 3, 0, 0], [3, -16, 109, 40, -11, 3, 0, 0],
+    [3, -16, 108, 42, -12, 3, 0, 0], [4, -17, 106, 45, -13, 3, 0, 0],
+    [4, -17, 104, 47, -13, 3, 0, 0], [4, -17, 102, 50, -14, 3, 0, 0],
+    [4, -17, 100, 52, -14, 3, 0, 0], [4, -18, 98, 55, -15, 4, 0, 0],
+    [4, -18, 96, 58, -15, 3, 0, 0], [4, -18, 94, 60, -16, 4, 0, 0],
+    [4, -18, 91, 63, -16, 4, 0, 0], [4, -18, 89, 65, -16, 4, 0, 0],
+    [4, -18, 87, 68, -17, 4, 0, 0], [4, -18, 85, 70, -17, 4, 0, 0],
+    [4, -18, 82, 73, -17, 4, 0, 0], [4, -18, 80, 75, -17, 4, 0, 0],
+    [4, -18, 78, 78, -18, 4, 0, 0], [4, -17, 75, 80, -18, 4, 0, 0],
+    [4, -17, 73, 82, -18, 4, 0, 0], [4, -17, 70, 85, -18, 4, 0, 0],
+    [4, -17, 68, 87, -18, 4, 0, 0], [4, -16, 65, 89, -18, 4, 0, 0],
+    [4, -16, 63, 91, -18, 4, 0, 0], [4, -16, 60, 94, -18, 4, 0, 0],
+    [3, -15, 58, 96, -18, 4, 0, 0], [4, -15, 55, 98, -18, 4, 0, 0],
+    [3, -14, 52, 100, -17, 4, 0, 0], [3, -14, 50, 102, -17, 4, 0, 0],
+    [3, -13, 47, 104, -17, 4, 0, 0], [3, -13, 45, 106, -17, 4, 0, 0],
+    [3, -12, 42, 108, -16, 3, 0, 0], [3, -11, 40, 109, -16, 3, 0, 0],
+    [3, -11, 37, 111, -15, 3, 0, 0], [2, -10, 35, 113, -15, 3, 0, 0],
+    [3, -10, 32, 114, -14, 3, 0, 0], [2, -9, 29, 116, -13, 3, 0, 0],
+    [2, -8, 27, 117, -13, 3, 0, 0], [2, -8, 25, 119, -12, 2, 0, 0],
+    [2, -7, 22, 120, -11, 2, 0, 0], [1, -6, 20, 121, -10, 2, 0, 0],
+    [1, -6, 18, 122, -9, 2, 0, 0], [1, -5, 15, 123, -8, 2, 0, 0],
+    [1, -4, 13, 124, -7, 1, 0, 0], [1, -4, 11, 125, -6, 1, 0, 0],
+    [1, -3, 8, 126, -5, 1, 0, 0], [1, -2, 6, 126, -4, 1, 0, 0],
+    [0, -1, 4, 127, -3, 1, 0, 0], [0, 0, 2, 127, -1, 0, 0, 0],
+    // [0, 1)
+    [0, 0, 0, 127, 1, 0, 0, 0], [0, 0, -1, 127, 2, 0, 0, 0],
+    [0, 1, -3, 127, 4, -2, 1, 0], [0, 1, -5, 127, 6, -2, 1, 0],
+    [0, 2, -6, 126, 8, -3, 1, 0], [-1, 2, -7, 126, 11, -4, 2, -1],
+    [-1, 3, -8, 125, 13, -5, 2, -1], [-1, 3, -10, 124, 16, -6, 3, -1],
+    [-1, 4, -11, 123, 18, -7, 3, -1], [-1, 4, -12, 122, 20, -7, 3, -1],
+    [-1, 4, -13, 121, 23, -8, 3, -1], [-2, 5, -14, 120, 25, -9, 4, -1],
+    [-1, 5, -15, 119, 27, -10, 4, -1], [-1, 5, -16, 118, 30, -11, 4, -1],
+    [-2, 6, -17, 116, 33, -12, 5, -1], [-2, 6, -17, 114, 35, -12, 5, -1],
+    [-2, 6, -18, 113, 38, -13, 5, -1], [-2, 7, -19, 111, 41, -14, 6, -2],
+    [-2, 7, -19, 110, 43, -15, 6, -2], [-2, 7, -20, 108, 46, -15, 6, -2],
+    [-2, 7, -20, 106, 49, -16, 6, -2], [-2, 7, -21, 104, 51, -16, 7, -2],
+    [-2, 7, -21, 102, 54, -17, 7, -2], [-2, 8, -21, 100, 56, -18, 7, -2],
+    [-2, 8, -22, 98, 59, -18, 7, -2], [-2, 8, -22, 96, 62, -19, 7, -2],
+    [-2, 8, -22, 94, 64, -19, 7, -2], [-2, 8, -22, 91, 67, -20, 8, -2],
+    [-2, 8, -22, 89, 69, -20, 8, -2], [-2, 8, -22, 87, 72, -21, 8, -2],
+    [-2, 8, -21, 84, 74, -21, 8, -2], [-2, 8, -22, 82, 77, -21, 8, -2],
+    [-2, 8, -21, 79, 79, -21, 8, -2], [-2, 8, -21, 77, 82, -22, 8, -2],
+    [-2, 8, -21, 74, 84, -21, 8, -2], [-2, 8, -21, 72, 87, -22, 8, -2],
+    [-2, 8, -20, 69, 89, -22, 8, -2], [-2, 8, -20, 67, 91, -22, 8, -2],
+    [-2, 7, -19, 64, 94, -22, 8, -2], [-2, 7, -19, 62, 96, -22, 8, -2],
+    [-2, 7, -18, 59, 98, -22, 8, -2], [-2, 7, -18, 56, 100, -21, 8, -2],
+    [-2, 7, -17, 54, 102, -21, 7, -2], [-2, 7, -16, 51, 104, -21, 7, -2],
+    [-2, 6, -16, 49, 106, -20, 7, -2], [-2, 6, -15, 46, 108, -20, 7, -2],
+    [-2, 6, -15, 43, 110, -19, 7, -2], [-2, 6, -14, 41, 111, -19, 7, -2],
+    [-1, 5, -13, 38, 113, -18, 6, -2], [-1, 5, -12, 35, 114, -17, 6, -2],
+    [-1, 5, -12, 33, 116, -17, 6, -2], [-1, 4, -11, 30, 118, -16, 5, -1],
+    [-1, 4, -10, 27, 119, -15, 5, -1], [-1, 4, -9, 25, 120, -14, 5, -2],
+    [-1, 3, -8, 23, 121, -13, 4, -1], [-1, 3, -7, 20, 122, -12, 4, -1],
+    [-1, 3, -7, 18, 123, -11, 4, -1], [-1, 3, -6, 16, 124, -10, 3, -1],
+    [-1, 2, -5, 13, 125, -8, 3, -1], [-1, 2, -4, 11, 126, -7, 2, -1],
+    [0, 1, -3, 8, 126, -6, 2, 0], [0, 1, -2, 6, 127, -5, 1, 0],
+    [0, 1, -2, 4, 127, -3, 1, 0], [0, 0, 0, 2, 127, -1, 0, 0],
+    // [1, 2)
+    [0, 0, 0, 1, 127, 0, 0, 0], [0, 0, 0, -1, 127, 2, 0, 0],
+    [0, 0, 1, -3, 127, 4, -1, 0], [0, 0, 1, -4, 126, 6, -2, 1],
+    [0, 0, 1, -5, 126, 8, -3, 1], [0, 0, 1, -6, 125, 11, -4, 1],
+    [0, 0, 1, -7, 124, 13, -4, 1], [0, 0, 2, -8, 123, 15, -5, 1],
+    [0, 0, 2, -9, 122, 18, -6, 1], [0, 0, 2, -10, 121, 20, -6, 1],
+    [0, 0, 2, -11, 120, 22, -7, 2], [0, 0, 2, -12, 119, 25, -8, 2],
+    [0, 0, 3, -13, 117, 27, -8, 2], [0, 0, 3, -13, 116, 29, -9, 2],
+    [0, 0, 3, -14, 114, 32, -10, 3], [0, 0, 3, -15, 113, 35, -10, 2],
+    [0, 0, 3, -15, 111, 37, -11, 3], [0, 0, 3, -16, 109, 40, -11, 3],
+    [0, 0, 3, -16, 108, 42, -12, 3], [0, 0, 4, -17, 106, 45, -13, 3],
+    [0, 0, 4, -17, 104, 47, -13, 3], [0, 0, 4, -17, 102, 50, -14, 3],
+    [0, 0, 4, -17, 100, 52, -14, 3], [0, 0, 4, -18, 98, 55, -15, 4],
+    [0, 0, 4, -18, 96, 58, -15, 3], [0, 0, 4, -18, 94, 60, -16, 4],
+    [0, 0, 4, -18, 91, 63, -16, 4], [0, 0, 4, -18, 89, 65, -16, 4],
+    [0, 0, 4, -18, 87, 68, -17, 4], [0, 0, 4, -18, 85, 70, -17, 4],
+    [0, 0, 4, -18, 82, 73, -17, 4], [0, 0, 4, -18, 80, 75, -17, 4],
+    [0, 0, 4, -18, 78, 78, -18, 4], [0, 0, 4, -17, 75, 80, -18, 4],
+    [0, 0, 4, -17, 73, 82, -18, 4], [0, 0, 4, -17, 70, 85, -18, 4],
+    [0, 0, 4, -17, 68, 87, -18, 4], [0, 0, 4, -16, 65, 89, -18, 4],
+    [0, 0, 4, -16, 63, 91, -18, 4], [0, 0, 4, -16, 60, 94, -18, 4],
+    [0, 0, 3, -15, 58, 96, -18, 4], [0, 0, 4, -15, 55, 98, -18, 4],
+    [0, 0, 3, -14, 52, 100, -17, 4], [0, 0, 3, -14, 50, 102, -17, 4],
+    [0, 0, 3, -13, 47, 104, -17, 4], [0, 0, 3, -13, 45, 106, -17, 4],
+    [0, 0, 3, -12, 42, 108, -16, 3], [0, 0, 3, -11, 40, 109, -16, 3],
+    [0, 0, 3, -11, 37, 111, -15, 3], [0, 0, 2, -10, 35, 113, -15, 3],
+    [0, 0, 3, -10, 32, 114, -14, 3], [0, 0, 2, -9, 29, 116, -13, 3],
+    [0, 0, 2, -8, 27, 117, -13, 3], [0, 0, 2, -8, 25, 119, -12, 2],
+    [0, 0, 2, -7, 22, 120, -11, 2], [0, 0, 1, -6, 20, 121, -10, 2],
+    [0, 0, 1, -6, 18, 122, -9, 2], [0, 0, 1, -5, 15, 123, -8, 2],
+    [0, 0, 1, -4, 13, 124, -7, 1], [0, 0, 1, -4, 11, 125, -6, 1],
+    [0, 0, 1, -3, 8, 126, -5, 1], [0, 0, 1, -2, 6, 126, -4, 1],
+    [0, 0, 0, -1, 4, 127, -3, 1], [0, 0, 0, 0, 2, 127, -1, 0],
+    // dummy (unused)
+    [0, 0, 0, 0, 2, 127, -1, 0]
+];
 
 pub trait Dim {
   const W: usize;
@@ -149,6 +243,27 @@ impl Dim for Block32x32 {
   const H: usize = 32;
 }
 
+pub struct Block4x8;
+
+impl Dim for Block4x8 {
+  const W: usize = 4;
+  const H: usize = 8;
+}
+
+pub struct Block8x16;
+
+impl Dim for Block8x16 {
+  const W: usize = 8;
+  const H: usize = 16;
+}
+
+pub struct Block16x32;
+
+impl Dim for Block16x32 {
+  const W: usize = 16;
+  const H: usize = 32;
+}
+
 pub trait Intra: Dim {
   fn pred_dc(output: &mut [u16], stride: usize, above: &[u16], left: &[u16]) {
     let edges = left[..Self::H].iter().chain(above[..Self::W].iter());
@@ -163,43 +278,37 @@ pub trait Intra: Dim {
     }
   }
 
-  fn pred_dc_128(output: &mut [u16], stride: usize) {
+  fn pred_dc_128(output: &mut [u16], stride: usize, bit_depth: usize) {
     for y in 0..Self::H {
       for x in 0..Self::W {
-        output[y * stride + x] = 128;
+        output[y * stride + x] = 1 << (bit_depth - 1);
       }
     }
   }
 
-  fn pred_dc_left(
-    output: &mut [u16], stride: usize, above: &[u16], left: &[u16]
-  ) {
-    unsafe {
-      highbd_dc_left_predictor(
-        output.as_mut_ptr(),
-        stride as libc::ptrdiff_t,
-        Self::W as libc::c_int,
-        Self::H as libc::c_int,
-        above.as_ptr(),
-        left.as_ptr(),
-        8
-      );
+  fn pred_dc_left(output: &mut [u16], stride: usize, left: &[u16]) {
+    let len = Self::H as u32;
+    let avg = ((left[..Self::H].iter().fold(0, |acc, &v| acc + v as u32)
+      + (len >> 1))
+      / len) as u16;
+
+    for line in output.chunks_mut(stride).take(Self::H) {
+      for v in &mut line[..Self::W] {
+        *v = avg;
+      }
     }
   }
 
-  fn pred_dc_top(
-    output: &mut [u16], stride: usize, above: &[u16], left: &[u16]
-  ) {
-    unsafe {
-      highbd_dc_top_predictor(
-        output.as_mut_ptr(),
-        stride as libc::ptrdiff_t,
-        Self::W as libc::c_int,
-        Self::H as libc::c_int,
-        above.as_ptr(),
-        left.as_ptr(),
-        8
-      );
+  fn pred_dc_top(output: &mut [u16], stride: usize, above: &[u16]) {
+    let len = Self::W as u32;
+    let avg = ((above[..Self::W].iter().fold(0, |acc, &v| acc + v as u32)
+      + (len >> 1))
+      / len) as u16;
+
+    for line in output.chunks_mut(stride).take(Self::H) {
+      for v in &mut line[..Self::W] {
+        *v = avg;
+      }
     }
   }
 
@@ -374,11 +483,67 @@ impl Intra for Block4x4 {}
 impl Intra for Block8x8 {}
 impl Intra for Block16x16 {}
 impl Intra for Block32x32 {}
+impl Intra for Block4x8 {}
+impl Intra for Block8x16 {}
+impl Intra for Block16x32 {}
 
 #[cfg(test)]
 pub mod test {
   use super::*;
+  use context::{CDFContext, MotionVector};
+  use libc;
+  use plane::*;
   use rand::{ChaChaRng, Rng, SeedableRng};
+  use std::rc::Rc;
+  use {ChromaSampling, Frame, FrameInvariants, FrameType, ReferenceFrame};
+  use {ALTREF_FRAME, LAST_FRAME, NONE_FRAME, REFS_PER_FRAME};
+
+  extern {
+    fn highbd_dc_predictor(
+      dst: *mut u16, stride: libc::ptrdiff_t, bw: libc::c_int,
+      bh: libc::c_int, above: *const u16, left: *const u16, bd: libc::c_int
+    );
+
+    fn highbd_dc_left_predictor(
+      dst: *mut u16, stride: libc::ptrdiff_t, bw: libc::c_int,
+      bh: libc::c_int, above: *const u16, left: *const u16, bd: libc::c_int
+    );
+
+    fn highbd_dc_top_predictor(
+      dst: *mut u16, stride: libc::ptrdiff_t, bw: libc::c_int,
+      bh: libc::c_int, above: *const u16, left: *const u16, bd: libc::c_int
+    );
+
+    fn highbd_h_predictor(
+      dst: *mut u16, stride: libc::ptrdiff_t, bw: libc::c_int,
+      bh: libc::c_int, above: *const u16, left: *const u16, bd: libc::c_int
+    );
+
+    fn highbd_v_predictor(
+      dst: *mut u16, stride: libc::ptrdiff_t, bw: libc::c_int,
+      bh: libc::c_int, above: *const u16, left: *const u16, bd: libc::c_int
+    );
+
+    fn highbd_paeth_predictor(
+      dst: *mut u16, stride: libc::ptrdiff_t, bw: libc::c_int,
+      bh: libc::c_int, above: *const u16, left: *const u16, bd: libc::c_int
+    );
+
+    fn highbd_smooth_predictor(
+      dst: *mut u16, stride: libc::ptrdiff_t, bw: libc::c_int,
+      bh: libc::c_int, above: *const u16, left: *const u16, bd: libc::c_int
+    );
+
+    fn highbd_smooth_h_predictor(
+      dst: *mut u16, stride: libc::ptrdiff_t, bw: libc::c_int,
+      bh: libc::c_int, above: *const u16, left: *const u16, bd: libc::c_int
+    );
+
+    fn highbd_smooth_v_predictor(
+      dst: *mut u16, stride: libc::ptrdiff_t, bw: libc::c_int,
+      bh: libc::c_int, above: *const u16, left: *const u16, bd: libc::c_int
+    );
+  }
 
   const MAX_ITER: usize = 50000;
 
@@ -411,6 +576,38 @@ pub mod test {
     }
   }
 
+  fn pred_dc_left_4x4(
+    output: &mut [u16], stride: usize, above: &[u16], left: &[u16]
+  ) {
+    unsafe {
+      highbd_dc_left_predictor(
+        output.as_mut_ptr(),
+        stride as libc::ptrdiff_t,
+        4,
+        4,
+        above.as_ptr(),
+        left.as_ptr(),
+        8
+      );
+    }
+  }
+
+  fn pred_dc_top_4x4(
+    output: &mut [u16], stride: usize, above: &[u16], left: &[u16]
+  ) {
+    unsafe {
+      highbd_dc_top_predictor(
+        output.as_mut_ptr(),
+        stride as libc::ptrdiff_t,
+        4,
+        4,
+        above.as_ptr(),
+        left.as_ptr(),
+        8
+      );
+    }
+  }
+
   pub fn pred_h_4x4(
     output: &mut [u16], stride: usize, above: &[u16], left: &[u16]
   ) {
@@ -516,6 +713,24 @@ pub mod test {
     (o1, o2)
   }
 
+  fn do_dc_left_pred(ra: &mut ChaChaRng) -> (Vec<u16>, Vec<u16>) {
+    let (above, left, mut o1, mut o2) = setup_pred(ra);
+
+    pred_dc_left_4x4(&mut o1, 32, &above[..4], &left[..4]);
+    Block4x4::pred_dc_left(&mut o2, 32, &left[..4]);
+
+    (o1, o2)
+  }
+
+  fn do_dc_top_pred(ra: &mut ChaChaRng) -> (Vec<u16>, Vec<u16>) {
+    let (above, left, mut o1, mut o2) = setup_pred(ra);
+
+    pred_dc_top_4x4(&mut o1, 32, &above[..4], &left[..4]);
+    Block4x4::pred_dc_top(&mut o2, 32, &above[..4]);
+
+    (o1, o2)
+  }
+
   fn do_h_pred(ra: &mut ChaChaRng) -> (Vec<u16>, Vec<u16>) {
     let (above, left, mut o1, mut o2) = setup_pred(ra);
 
@@ -586,6 +801,12 @@ pub mod test {
       let (o1, o2) = do_dc_pred(&mut ra);
       assert_eq!(o1, o2);
 
+      let (o1, o2) = do_dc_left_pred(&mut ra);
+      assert_eq!(o1, o2);
+
+      let (o1, o2) = do_dc_top_pred(&mut ra);
+      assert_eq!(o1, o2);
+
       let (o1, o2) = do_h_pred(&mut ra);
       assert_eq!(o1, o2);
 
@@ -682,4 +903,170 @@ pub mod test {
       }
     }
   }
+
+  #[test]
+  fn pred_inter_full_pel() {
+    let mut fi = FrameInvariants::new(64, 64, 100, 3);
+    let mut rec = Frame::new(64, 64, ChromaSampling::CS420);
+    for (i, v) in rec.planes[0].data.iter_mut().enumerate() {
+      *v = (i * 7 % 251) as u16;
+    }
+    fi.ref_frames[0] = Some(Rc::new(ReferenceFrame {
+      frame: rec.clone(),
+      cdfs: CDFContext::new(100),
+      frame_type: FrameType::KEY,
+      number: 0,
+      order_hint: 0,
+      ref_order_hints: [0; REFS_PER_FRAME],
+      frame_mvs: Vec::new()
+    }));
+
+    let po = PlaneOffset {
+      x: 16,
+      y: 16
+    };
+    let mut dst = Plane::new(64, 64, 0, 0);
+    for &(row, col) in &[(0, 0), (-24, 40), (64, -8)] {
+      let mv = MotionVector {
+        row,
+        col
+      };
+      PredictionMode::NEWMV.predict_inter(
+        &fi,
+        0,
+        &po,
+        &mut dst.mut_slice(&po),
+        16,
+        16,
+        BlockSize::BLOCK_16X16,
+        &[LAST_FRAME, NONE_FRAME],
+        &[mv; 2]
+      );
+      for y in 0..16 {
+        for x in 0..16 {
+          let rx = (po.x as isize + x + (col / 8) as isize) as usize;
+          let ry = (po.y as isize + y + (row / 8) as isize) as usize;
+          assert_eq!(
+            dst.p(po.x + x as usize, po.y + y as usize),
+            rec.planes[0].p(rx, ry)
+          );
+        }
+      }
+    }
+  }
+
+  #[test]
+  fn pred_inter_flat() {
+    let mut fi = FrameInvariants::new(64, 64, 100, 3);
+    fi.bit_depth = 12;
+    let mut rec = Frame::new(64, 64, ChromaSampling::CS420);
+    for v in rec.planes[0].data.iter_mut() {
+      *v = 4095;
+    }
+    fi.ref_frames[0] = Some(Rc::new(ReferenceFrame {
+      frame: rec,
+      cdfs: CDFContext::new(100),
+      frame_type: FrameType::KEY,
+      number: 0,
+      order_hint: 0,
+      ref_order_hints: [0; REFS_PER_FRAME],
+      frame_mvs: Vec::new()
+    }));
+
+    // Sub-pel positions, and positions clamped to the frame edges
+    let po = PlaneOffset {
+      x: 0,
+      y: 56
+    };
+    let mut dst = Plane::new(64, 64, 0, 0);
+    for &(row, col) in &[(3, 5), (-1, 7), (20, -13)] {
+      let mv = MotionVector {
+        row,
+        col
+      };
+      PredictionMode::NEWMV.predict_inter(
+        &fi,
+        0,
+        &po,
+        &mut dst.mut_slice(&po),
+        8,
+        4,
+        BlockSize::BLOCK_8X4,
+        &[LAST_FRAME, NONE_FRAME],
+        &[mv; 2]
+      );
+      for l in dst.data.chunks(64).skip(56).take(4) {
+        for v in l[..8].iter() {
+          assert_eq!(*v, 4095);
+        }
+      }
+    }
+  }
+
+  #[test]
+  fn pred_inter_compound_average() {
+    let mut fi = FrameInvariants::new(64, 64, 100, 3);
+    for &(i, v) in &[(0, 100), (6, 201)] {
+      let mut rec = Frame::new(64, 64, ChromaSampling::CS420);
+      for s in rec.planes[0].data.iter_mut() {
+        *s = v;
+      }
+      fi.ref_frames[i] = Some(Rc::new(ReferenceFrame {
+        frame: rec,
+        cdfs: CDFContext::new(100),
+        frame_type: FrameType::INTER,
+        number: i as u64,
+        order_hint: 0,
+        ref_order_hints: [0; REFS_PER_FRAME],
+        frame_mvs: Vec::new()
+      }));
+    }
+
+    let po = PlaneOffset {
+      x: 8,
+      y: 8
+    };
+    let mut dst = Plane::new(64, 64, 0, 0);
+    let mvs = [
+      MotionVector {
+        row: 3,
+        col: -5
+      },
+      MotionVector {
+        row: 16,
+        col: 2
+      }
+    ];
+    PredictionMode::NEW_NEWMV.predict_inter(
+      &fi,
+      0,
+      &po,
+      &mut dst.mut_slice(&po),
+      16,
+      8,
+      BlockSize::BLOCK_16X8,
+      &[LAST_FRAME, ALTREF_FRAME],
+      &mvs
+    );
+    for l in dst.data.chunks(64).skip(8).take(8) {
+      for v in l[8..24].iter() {
+        assert_eq!(*v, 151);
+      }
+    }
+  }
+
+  #[test]
+  fn warped_filter_normalized() {
+    for filter in warped_filter.iter() {
+      assert_eq!(filter.iter().map(|&t| t as i32).sum::<i32>(), 128);
+    }
+    // The filters over [1, 2) are those over [-1, 0), two samples later
+    for i in 1..WARPEDPIXEL_PREC_SHIFTS {
+      let (a, b) = (
+        &warped_filter[i],
+        &warped_filter[2 * WARPEDPIXEL_PREC_SHIFTS + i]
+      );
+      assert_eq!(&a[..6], &b[2..]);
+    }
+  }
 }
@@ -14,17 +14,28 @@
 use context::*;
 use ec::OD_BITRES;
 use encode_block;
+use me::*;
 use partition::*;
 use plane::*;
-use predict::{RAV1E_INTRA_MODES, RAV1E_INTRA_MODES_MINIMAL};
+use predict::{
+  RAV1E_INTER_COMPOUND_MODES, RAV1E_INTER_MODES, RAV1E_INTRA_MODES,
+  RAV1E_INTRA_MODES_MINIMAL
+};
 use quantize::dc_q;
 use std;
 use std::vec::Vec;
 use write_tx_blocks;
 use BlockSize;
+use ChromaSampling;
 use FrameInvariants;
 use FrameState;
 use FrameType;
+use ReferenceMode;
+use ALTREF_FRAME;
+use BWDREF_FRAME;
+use INTRA_FRAME;
+use LAST_FRAME;
+use NONE_FRAME;
 
 #[derive(Clone)]
 pub struct RDOOutput {
@@ -39,6 +50,8 @@ pub struct RDOPartitionOutput {
   pub bo: BlockOffset,
   pub pred_mode_luma: PredictionMode,
   pub pred_mode_chroma: PredictionMode,
+  pub pred_ref_frames: [usize; 2],
+  pub pred_mvs: [MotionVector; 2],
   pub skip: bool
 }
 
@@ -54,13 +67,28 @@ fn sse_wxh(src1: &PlaneSlice, src2: &PlaneSlice, w: usize, h: usize) -> u64 {
   sse
 }
 
+// Motion vectors of an inter mode, from the reference MV stack of the block
+// or from motion estimation for NEWMV modes. GLOBALMV modes use the global
+// motion of the references.
+fn inter_mode_mvs(
+  mode: PredictionMode, mv_stack: &RefMVStack, new_mvs: [MotionVector; 2]
+) -> [MotionVector; 2] {
+  match mode {
+    PredictionMode::NEARESTMV | PredictionMode::NEAREST_NEARESTMV =>
+      mv_stack.mvs[0],
+    PredictionMode::NEARMV | PredictionMode::NEAR_NEARMV => mv_stack.mvs[1],
+    PredictionMode::NEWMV | PredictionMode::NEW_NEWMV => new_mvs,
+    _ => mv_stack.global_mvs
+  }
+}
+
 // Compute the rate-distortion cost for an encode
 fn compute_rd_cost(
   fi: &FrameInvariants, fs: &FrameState, w_y: usize, h_y: usize, w_uv: usize,
   h_uv: usize, partition_start_x: usize, partition_start_y: usize,
   bo: &BlockOffset, bit_cost: u32
 ) -> f64 {
-  let q = dc_q(fi.qindex) as f64;
+  let q = dc_q(fi.qindex, fi.bit_depth) as f64;
 
   // Convert q into Q0 precision, given that libaom quantizers are Q3
   let q0 = q / 8.0_f64;
@@ -109,6 +137,8 @@ pub fn rdo_mode_decision(
 ) -> RDOOutput {
   let mut best_mode_luma = PredictionMode::DC_PRED;
   let mut best_mode_chroma = PredictionMode::DC_PRED;
+  let mut best_ref_frames = [INTRA_FRAME, NONE_FRAME];
+  let mut best_mvs = [MotionVector::default(); 2];
   let mut best_skip = false;
   let mut best_rd = std::f64::MAX;
   let tell = cw.w.tell_frac();
@@ -117,25 +147,79 @@ pub fn rdo_mode_decision(
   let w = bsize.width();
   let h = bsize.height();
 
-  let PlaneConfig {
-    xdec,
-    ydec,
-    ..
-  } = fs.input.planes[1].cfg;
-
-  let mut w_uv = w >> xdec;
-  let mut h_uv = h >> ydec;
+  let (xdec, ydec) = fi.chroma_sampling.get_decimation();
 
-  let is_chroma_block = has_chroma(bo, bsize, xdec, ydec);
+  let is_chroma_block = fi.chroma_sampling != ChromaSampling::CS400
+    && has_chroma(bo, bsize, xdec, ydec);
 
-  if (w_uv == 0 || h_uv == 0) && is_chroma_block {
-    w_uv = 4;
-    h_uv = 4;
-  }
+  let (w_uv, h_uv) = if is_chroma_block {
+    let plane_bsize = get_plane_block_size(bsize, xdec, ydec);
+    (plane_bsize.width(), plane_bsize.height())
+  } else {
+    (0, 0)
+  };
 
   let partition_start_x = (bo.x & LOCAL_BLOCK_MASK) >> xdec << MI_SIZE_LOG2;
   let partition_start_y = (bo.y & LOCAL_BLOCK_MASK) >> ydec << MI_SIZE_LOG2;
 
+  // Candidates as (mode, references, motion vectors)
+  let mut mode_set: Vec<(PredictionMode, [usize; 2], [MotionVector; 2])> =
+    Vec::new();
+  // Inter prediction of sub-8x8 chroma blocks is not supported
+  if fi.frame_type == FrameType::INTER && bsize >= BlockSize::BLOCK_8X8 {
+    // Search the past and future references, once per distinct slot
+    let mut searched: Vec<(usize, MotionVector)> = Vec::new();
+    for &ref_frame in &[LAST_FRAME, BWDREF_FRAME, ALTREF_FRAME] {
+      let slot = fi.ref_frame_idx[ref_frame - LAST_FRAME];
+      if searched
+        .iter()
+        .any(|&(r, _)| fi.ref_frame_idx[r - LAST_FRAME] == slot)
+      {
+        continue;
+      }
+
+      let new_mv = motion_estimation(fi, fs, bsize, bo, ref_frame);
+      searched.push((ref_frame, new_mv));
+      let ref_frames = [ref_frame, NONE_FRAME];
+      let mv_stack = cw.bc.find_mv_stack(fi, bo, bsize, ref_frames);
+      for &mode in RAV1E_INTER_MODES {
+        let new_mvs = [new_mv, MotionVector::default()];
+        let mvs = inter_mode_mvs(mode, &mv_stack, new_mvs);
+        mode_set.push((mode, ref_frames, mvs));
+      }
+    }
+
+    // Pair each past reference with each future one, reusing their motion
+    // vectors
+    if fi.reference_mode == ReferenceMode::SELECT {
+      let (fwd_refs, bwd_refs): (Vec<_>, Vec<_>) =
+        searched.iter().partition(|r| r.0 < BWDREF_FRAME);
+      for &&(fwd, fwd_mv) in &fwd_refs {
+        for &&(bwd, bwd_mv) in &bwd_refs {
+          let ref_frames = [fwd, bwd];
+          let mv_stack = cw.bc.find_mv_stack(fi, bo, bsize, ref_frames);
+          for &mode in RAV1E_INTER_COMPOUND_MODES {
+            let mvs = inter_mode_mvs(mode, &mv_stack, [fwd_mv, bwd_mv]);
+            mode_set.push((mode, ref_frames, mvs));
+          }
+        }
+      }
+    }
+  }
+  // Exclude complex prediction modes at higher speed levels
+  let intra_modes = if fi.speed <= 3 {
+    RAV1E_INTRA_MODES
+  } else {
+    RAV1E_INTRA_MODES_MINIMAL
+  };
+  for &mode in intra_modes {
+    mode_set.push((
+      mode,
+      [INTRA_FRAME, NONE_FRAME],
+      [MotionVector::default(); 2]
+    ));
+  }
+
   for &skip in &[false, true] {
     // Don't test skipped blocks at higher speed levels
     if fi.speed > 1 && skip {
@@ -144,24 +228,22 @@ pub fn rdo_mode_decision(
 
     let checkpoint = cw.checkpoint();
 
-    // Exclude complex prediction modes at higher speed levels
-    let mode_set = if fi.speed <= 3 {
-      RAV1E_INTRA_MODES
-    } else {
-      RAV1E_INTRA_MODES_MINIMAL
-    };
-
-    for &luma_mode in mode_set {
-      if fi.frame_type == FrameType::KEY
-        && luma_mode >= PredictionMode::NEARESTMV
-      {
-        break;
-      }
-
-      if is_chroma_block && fi.speed <= 3 {
+    for &(luma_mode, ref_frames, mvs) in &mode_set {
+      if luma_mode.is_intra() && is_chroma_block && fi.speed <= 3 {
         // Find the best chroma prediction mode for the current luma prediction mode
         for &chroma_mode in RAV1E_INTRA_MODES {
-          encode_block(fi, fs, cw, luma_mode, chroma_mode, bsize, bo, skip);
+          encode_block(
+            fi,
+            fs,
+            cw,
+            luma_mode,
+            chroma_mode,
+            ref_frames,
+            mvs,
+            bsize,
+            bo,
+            skip
+          );
 
           let cost = cw.w.tell_frac() - tell;
           let rd = compute_rd_cost(
@@ -181,13 +263,17 @@ pub fn rdo_mode_decision(
             best_rd = rd;
             best_mode_luma = luma_mode;
             best_mode_chroma = chroma_mode;
+            best_ref_frames = ref_frames;
+            best_mvs = mvs;
             best_skip = skip;
           }
 
           cw.rollback(&checkpoint);
         }
       } else {
-        encode_block(fi, fs, cw, luma_mode, luma_mode, bsize, bo, skip);
+        encode_block(
+          fi, fs, cw, luma_mode, luma_mode, ref_frames, mvs, bsize, bo, skip
+        );
 
         let cost = cw.w.tell_frac() - tell;
         let rd = compute_rd_cost(
@@ -207,6 +293,8 @@ pub fn rdo_mode_decision(
           best_rd = rd;
           best_mode_luma = luma_mode;
           best_mode_chroma = luma_mode;
+          best_ref_frames = ref_frames;
+          best_mvs = mvs;
           best_skip = skip;
         }
 
@@ -224,6 +312,8 @@ pub fn rdo_mode_decision(
       bo: bo.clone(),
       pred_mode_luma: best_mode_luma,
       pred_mode_chroma: best_mode_chroma,
+      pred_ref_frames: best_ref_frames,
+      pred_mvs: best_mvs,
       rd_cost: best_rd,
       skip: best_skip
     }]
@@ -233,8 +323,8 @@ pub fn rdo_mode_decision(
 // RDO-based intra frame transform type decision
 pub fn rdo_tx_type_decision(
   fi: &FrameInvariants, fs: &mut FrameState, cw: &mut ContextWriter,
-  mode: PredictionMode, bsize: BlockSize, bo: &BlockOffset, tx_size: TxSize,
-  tx_set_type: TxSetType
+  mode: PredictionMode, ref_frames: [usize; 2], mvs: [MotionVector; 2],
+  bsize: BlockSize, bo: &BlockOffset, tx_size: TxSize, tx_set_type: TxSetType
 ) -> TxType {
   let mut best_type = TxType::DCT_DCT;
   let mut best_rd = std::f64::MAX;
@@ -244,19 +334,17 @@ pub fn rdo_tx_type_decision(
   let w = bsize.width();
   let h = bsize.height();
 
-  let PlaneConfig {
-    xdec,
-    ydec,
-    ..
-  } = fs.input.planes[1].cfg;
+  let (xdec, ydec) = fi.chroma_sampling.get_decimation();
 
-  let mut w_uv = w >> xdec;
-  let mut h_uv = h >> ydec;
+  let is_chroma_block = fi.chroma_sampling != ChromaSampling::CS400
+    && has_chroma(bo, bsize, xdec, ydec);
 
-  if (w_uv == 0 || h_uv == 0) && has_chroma(bo, bsize, xdec, ydec) {
-    w_uv = 4;
-    h_uv = 4;
-  }
+  let (w_uv, h_uv) = if is_chroma_block {
+    let plane_bsize = get_plane_block_size(bsize, xdec, ydec);
+    (plane_bsize.width(), plane_bsize.height())
+  } else {
+    (0, 0)
+  };
 
   let partition_start_x = (bo.x & LOCAL_BLOCK_MASK) >> xdec << MI_SIZE_LOG2;
   let partition_start_y = (bo.y & LOCAL_BLOCK_MASK) >> ydec << MI_SIZE_LOG2;
@@ -270,7 +358,8 @@ pub fn rdo_tx_type_decision(
     }
 
     write_tx_blocks(
-      fi, fs, cw, mode, mode, bo, bsize, tx_size, tx_type, false,
+      fi, fs, cw, mode, mode, ref_frames, mvs, bo, bsize, tx_size, tx_type,
+      false
     );
 
     let cost = cw.w.tell_frac() - tell;
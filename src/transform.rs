@@ -7,236 +7,1128 @@
 // Media Patent License 1.0 was not distributed with this source code in the
 // PATENTS file, you can obtain it at www.aomedia.org/license/patent.
 
-extern crate libc;
+#![cfg_attr(feature = "cargo-clippy", allow(cast_lossless))]
 
 use partition::TxSize;
 use partition::TxType;
 
-// In libaom, functions that have more than one specialization use function
-// pointers, so we need to declare them as static fields and call them
-// indirectly. Otherwise, we call SSE or C variants directly. To fully
-// understand what's going on here you should first understand the Perl code
-// in libaom that generates these function bindings.
-
-#[cfg(target_feature = "sse2")]
-extern {
-  fn av1_fht4x4_sse2(
-    input: *const i16, output: *mut i32, stride: libc::c_int,
-    tx_type: *const libc::c_int
-  );
-  fn av1_fht8x8_sse2(
-    input: *const i16, output: *mut i32, stride: libc::c_int,
-    tx_type: *const libc::c_int
-  );
+// The inverse transforms follow the AV1 specification and match libaom
+// bit-exactly. The forward transforms are the transposes of the same
+// butterfly networks, scaled so that the inverse transforms undo them, and
+// use the per-size precision of libaom's so that they match it too.
+
+// cos(k * pi / 128) in 10- to 13-bit fixed point, for k = 0..64
+const COSPI: [[i32; 65]; 4] = [
+  [
+    1024, 1024, 1023, 1021, 1019, 1016, 1013, 1009, 1004, 999, 993, 987, 980,
+    972, 964, 955, 946, 936, 926, 915, 903, 891, 878, 865, 851, 837, 822, 807,
+    792, 775, 759, 742, 724, 706, 688, 669, 650, 630, 610, 590, 569, 548, 526,
+    505, 483, 460, 438, 415, 392, 369, 345, 321, 297, 273, 249, 224, 200, 175,
+    150, 125, 100, 75, 50, 25, 0
+  ],
+  [
+    2048, 2047, 2046, 2042, 2038, 2033, 2026, 2018, 2009, 1998, 1987, 1974,
+    1960, 1945, 1928, 1911, 1892, 1872, 1851, 1829, 1806, 1782, 1757, 1730,
+    1703, 1674, 1645, 1615, 1583, 1551, 1517, 1483, 1448, 1412, 1375, 1338,
+    1299, 1260, 1220, 1179, 1138, 1096, 1053, 1009, 965, 921, 876, 830, 784,
+    737, 690, 642, 595, 546, 498, 449, 400, 350, 301, 251, 201, 151, 100, 50, 0
+  ],
+  [
+    4096, 4095, 4091, 4085, 4076, 4065, 4052, 4036, 4017, 3996, 3973, 3948,
+    3920, 3889, 3857, 3822, 3784, 3745, 3703, 3659, 3612, 3564, 3513, 3461,
+    3406, 3349, 3290, 3229, 3166, 3102, 3035, 2967, 2896, 2824, 2751, 2675,
+    2598, 2520, 2440, 2359, 2276, 2191, 2106, 2019, 1931, 1842, 1751, 1660,
+    1567, 1474, 1380, 1285, 1189, 1092, 995, 897, 799, 700, 601, 501, 401, 301,
+    201, 101, 0
+  ],
+  [
+    8192, 8190, 8182, 8170, 8153, 8130, 8103, 8071, 8035, 7993, 7946, 7895,
+    7839, 7779, 7713, 7643, 7568, 7489, 7405, 7317, 7225, 7128, 7027, 6921,
+    6811, 6698, 6580, 6458, 6333, 6203, 6070, 5933, 5793, 5649, 5501, 5351,
+    5197, 5040, 4880, 4717, 4551, 4383, 4212, 4038, 3862, 3683, 3503, 3320,
+    3135, 2948, 2760, 2570, 2378, 2185, 1990, 1795, 1598, 1401, 1202, 1003,
+    803, 603, 402, 201, 0
+  ]
+];
+
+// (2 * sqrt(2) / 3) * sin(k * pi / 9) in 12- and 13-bit fixed point, for
+// k = 0..4, adjusted so that SINPI[1] + SINPI[2] == SINPI[4]
+const SINPI: [[i64; 5]; 2] =
+  [[0, 1321, 2482, 3344, 3803], [0, 2642, 4964, 6689, 7606]];
+
+// Precision of the inverse transforms
+const COS_BIT: usize = 12;
+
+// sqrt(2) and 1/sqrt(2) in 12-bit fixed point
+const SQRT2: i64 = 5793;
+const INV_SQRT2: i64 = 2896;
+
+// Rounding shift after the inverse row transforms, in TxSize order. The
+// column transforms are always followed by a shift of 4.
+const INV_ROW_SHIFT: [usize; TxSize::TX_SIZES_ALL] =
+  [0, 1, 2, 2, 2, 0, 0, 1, 1, 1, 1, 1, 1, 1, 1, 2, 2, 2, 2];
+const INV_COL_SHIFT: usize = 4;
+
+// Shifts of the forward transforms, in TxSize order: left shift of the
+// residual, then rounding shifts after the column and the row transforms
+const FWD_SHIFT: [(usize, usize, usize); TxSize::TX_SIZES_ALL] = [
+  (2, 0, 0), // TX_4X4
+  (2, 1, 0), // TX_8X8
+  (2, 2, 0), // TX_16X16
+  (2, 4, 0), // TX_32X32
+  (0, 2, 2), // TX_64X64
+  (2, 1, 0), // TX_4X8
+  (2, 1, 0), // TX_8X4
+  (2, 2, 0), // TX_8X16
+  (2, 2, 0), // TX_16X8
+  (2, 4, 0), // TX_16X32
+  (2, 4, 0), // TX_32X16
+  (0, 2, 2), // TX_32X64
+  (2, 4, 2), // TX_64X32
+  (2, 1, 0), // TX_4X16
+  (2, 1, 0), // TX_16X4
+  (2, 2, 0), // TX_8X32
+  (2, 2, 0), // TX_32X8
+  (0, 2, 0), // TX_16X64
+  (2, 4, 0)  // TX_64X16
+];
+
+// Precision of the forward column and row transforms, in TxSize order
+const FWD_COS_BIT: [(usize, usize); TxSize::TX_SIZES_ALL] = [
+  (13, 13), // TX_4X4
+  (13, 13), // TX_8X8
+  (13, 12), // TX_16X16
+  (12, 12), // TX_32X32
+  (13, 10), // TX_64X64
+  (13, 13), // TX_4X8
+  (13, 13), // TX_8X4
+  (13, 13), // TX_8X16
+  (13, 13), // TX_16X8
+  (12, 13), // TX_16X32
+  (13, 13), // TX_32X16
+  (13, 11), // TX_32X64
+  (12, 11), // TX_64X32
+  (13, 12), // TX_4X16
+  (13, 13), // TX_16X4
+  (12, 12), // TX_8X32
+  (13, 12), // TX_32X8
+  (13, 12), // TX_16X64
+  (13, 12)  // TX_64X16
+];
+
+fn cos128(angle: usize, cos_bit: usize) -> i64 {
+  let cospi = &COSPI[cos_bit - 10];
+  let angle = angle & 255;
+  let c = if angle <= 64 {
+    cospi[angle]
+  } else if angle <= 128 {
+    -cospi[128 - angle]
+  } else if angle <= 192 {
+    -cospi[angle - 128]
+  } else {
+    cospi[256 - angle]
+  };
+  c as i64
 }
 
-#[cfg(target_feature = "sse2")]
-use self::av1_fht4x4_sse2 as av1_fht4x4;
-#[cfg(target_feature = "sse2")]
-use self::av1_fht8x8_sse2 as av1_fht8x8;
+fn sin128(angle: usize, cos_bit: usize) -> i64 {
+  cos128(angle.wrapping_sub(64), cos_bit)
+}
 
-#[cfg(not(target_feature = "sse2"))]
-extern {
-  fn av1_fht4x4_c(
-    input: *const i16, output: *mut i32, stride: libc::c_int,
-    tx_type: *const libc::c_int
-  );
-  fn av1_fht8x8_c(
-    input: *const i16, output: *mut i32, stride: libc::c_int,
-    tx_type: *const libc::c_int
-  );
+fn round_shift(value: i64, bit: usize) -> i32 {
+  if bit == 0 {
+    value as i32
+  } else {
+    ((value + (1 << (bit - 1))) >> bit) as i32
+  }
 }
 
-#[cfg(not(target_feature = "sse2"))]
-use self::av1_fht4x4_c as av1_fht4x4;
-#[cfg(not(target_feature = "sse2"))]
-use self::av1_fht8x8_c as av1_fht8x8;
+fn clamp_value(value: i32, bits: usize) -> i32 {
+  let max = (1 << (bits - 1)) - 1;
+  let min = -(1 << (bits - 1));
+  if value < min {
+    min
+  } else if value > max {
+    max
+  } else {
+    value
+  }
+}
 
-extern {
-  static av1_inv_txfm2d_add_4x4: extern fn(
-    input: *const i32,
-    output: *mut u16,
-    stride: libc::c_int,
-    tx_type: libc::c_int,
-    bd: libc::c_int
-  );
+fn brev(n: usize, x: usize) -> usize {
+  (0..n).fold(0, |r, i| r | ((x >> i) & 1) << (n - 1 - i))
+}
 
-  static av1_inv_txfm2d_add_8x8: extern fn(
-    input: *const i32,
-    output: *mut u16,
-    stride: libc::c_int,
-    tx_type: libc::c_int,
-    bd: libc::c_int
-  ) -> ();
-  static av1_fht16x16: extern fn(
-    input: *const i16,
-    output: *mut i32,
-    stride: libc::c_int,
-    tx_type: *const libc::c_int
-  );
-  static av1_inv_txfm2d_add_16x16: extern fn(
-    input: *const i32,
-    output: *mut u16,
-    stride: libc::c_int,
-    tx_type: libc::c_int,
-    bd: libc::c_int
-  );
-  fn av1_inv_txfm2d_add_16x16_c(
-    input: *const i32, output: *mut u16, stride: libc::c_int,
-    tx_type: libc::c_int, bd: libc::c_int
-  );
-  static av1_fht32x32: extern fn(
-    input: *const i16,
-    output: *mut i32,
-    stride: libc::c_int,
-    tx_type: *const libc::c_int
-  );
-  static av1_inv_txfm2d_add_32x32: extern fn(
-    input: *const i32,
-    output: *mut u16,
-    stride: libc::c_int,
-    tx_type: libc::c_int,
-    bd: libc::c_int
-  );
+/// Index of the input of the inverse ADST permutation for output `i`
+fn adst_in_index(n: usize, i: usize) -> usize {
+  if i & 1 == 1 {
+    i - 1
+  } else {
+    (1 << n) - i - 1
+  }
 }
 
-pub fn forward_transform(
-  input: &[i16], output: &mut [i32], stride: usize, tx_size: TxSize,
-  tx_type: TxType
-) {
-  match tx_size {
-    TxSize::TX_4X4 => fht4x4(input, output, stride, tx_type),
-    TxSize::TX_8X8 => fht8x8(input, output, stride, tx_type),
-    TxSize::TX_16X16 => fht16x16(input, output, stride, tx_type),
-    TxSize::TX_32X32 => fht32x32(input, output, stride, tx_type),
-    _ => panic!("unimplemented tx size")
+/// Index of the input of the inverse ADST output permutation for output `i`,
+/// which is negated for odd `i`
+fn adst_out_index(n: usize, i: usize) -> usize {
+  let a = (i >> 3) & 1;
+  let b = ((i >> 2) & 1) ^ ((i >> 3) & 1);
+  let c = ((i >> 1) & 1) ^ ((i >> 2) & 1);
+  let d = (i & 1) ^ ((i >> 1) & 1);
+  ((d << 3) | (c << 2) | (b << 1) | a) >> (4 - n)
+}
+
+#[derive(Clone, Copy)]
+enum Butterfly {
+  /// Rotate (T[a], T[b]) by angle * pi / 128, then swap them if flip.
+  Rotation(usize, usize, usize, bool),
+  /// Replace (T[a], T[b]) by their sum and difference, with T[a] and T[b]
+  /// swapped beforehand if flip.
+  Hadamard(usize, usize, bool)
+}
+
+use self::Butterfly::*;
+
+/// Butterflies of the inverse DCT of length 2^n, after the bit reversal
+/// permutation of its input
+fn dct_butterflies(n: usize) -> Vec<Butterfly> {
+  let mut bf = Vec::new();
+  if n == 6 {
+    for i in 0..16 {
+      bf.push(Rotation(32 + i, 63 - i, 63 - 4 * brev(4, i), false));
+    }
+  }
+  if n >= 5 {
+    for i in 0..8 {
+      bf.push(Rotation(16 + i, 31 - i, 6 + (brev(3, 7 - i) << 3), false));
+    }
+  }
+  if n == 6 {
+    for i in 0..16 {
+      bf.push(Hadamard(32 + i * 2, 33 + i * 2, i & 1 == 1));
+    }
+  }
+  if n >= 4 {
+    for i in 0..4 {
+      bf.push(Rotation(8 + i, 15 - i, 12 + (brev(2, 3 - i) << 4), false));
+    }
+  }
+  if n >= 5 {
+    for i in 0..8 {
+      bf.push(Hadamard(16 + 2 * i, 17 + 2 * i, i & 1 == 1));
+    }
+  }
+  if n == 6 {
+    for i in 0..4 {
+      for j in 0..2 {
+        bf.push(Rotation(
+          62 - i * 4 - j,
+          33 + i * 4 + j,
+          60 - 16 * brev(2, i) + 64 * j,
+          true
+        ));
+      }
+    }
+  }
+  if n >= 3 {
+    for i in 0..2 {
+      bf.push(Rotation(4 + i, 7 - i, 56 - 32 * i, false));
+    }
+  }
+  if n >= 4 {
+    for i in 0..4 {
+      bf.push(Hadamard(8 + 2 * i, 9 + 2 * i, i & 1 == 1));
+    }
+  }
+  if n >= 5 {
+    for i in 0..2 {
+      for j in 0..2 {
+        bf.push(Rotation(
+          30 - 4 * i - j,
+          17 + 4 * i + j,
+          24 + (j << 6) + ((1 - i) << 5),
+          true
+        ));
+      }
+    }
+  }
+  if n == 6 {
+    for i in 0..8 {
+      for j in 0..2 {
+        bf.push(Hadamard(32 + i * 4 + j, 35 + i * 4 - j, i & 1 == 1));
+      }
+    }
   }
+  for i in 0..2 {
+    bf.push(Rotation(2 * i, 2 * i + 1, 32 + 16 * i, i == 0));
+  }
+  if n >= 3 {
+    for i in 0..2 {
+      bf.push(Hadamard(4 + 2 * i, 5 + 2 * i, i == 1));
+    }
+  }
+  if n >= 4 {
+    for i in 0..2 {
+      bf.push(Rotation(14 - i, 9 + i, 48 + 64 * i, true));
+    }
+  }
+  if n >= 5 {
+    for i in 0..4 {
+      for j in 0..2 {
+        bf.push(Hadamard(16 + 4 * i + j, 19 + 4 * i - j, i & 1 == 1));
+      }
+    }
+  }
+  if n == 6 {
+    for i in 0..2 {
+      for j in 0..4 {
+        bf.push(Rotation(
+          61 - i * 8 - j,
+          34 + i * 8 + j,
+          56 - i * 32 + (j >> 1) * 64,
+          true
+        ));
+      }
+    }
+  }
+  for i in 0..2 {
+    bf.push(Hadamard(i, 3 - i, false));
+  }
+  if n >= 3 {
+    bf.push(Rotation(6, 5, 32, true));
+  }
+  if n >= 4 {
+    for i in 0..2 {
+      for j in 0..2 {
+        bf.push(Hadamard(8 + 4 * i + j, 11 + 4 * i - j, i == 1));
+      }
+    }
+  }
+  if n >= 5 {
+    for i in 0..4 {
+      bf.push(Rotation(29 - i, 18 + i, 48 + (i >> 1) * 64, true));
+    }
+  }
+  if n == 6 {
+    for i in 0..4 {
+      for j in 0..4 {
+        bf.push(Hadamard(32 + 8 * i + j, 39 + 8 * i - j, i & 1 == 1));
+      }
+    }
+  }
+  if n >= 3 {
+    for i in 0..4 {
+      bf.push(Hadamard(i, 7 - i, false));
+    }
+  }
+  if n >= 4 {
+    for i in 0..2 {
+      bf.push(Rotation(13 - i, 10 + i, 32, true));
+    }
+  }
+  if n >= 5 {
+    for i in 0..2 {
+      for j in 0..4 {
+        bf.push(Hadamard(16 + i * 8 + j, 23 + i * 8 - j, i == 1));
+      }
+    }
+  }
+  if n == 6 {
+    for i in 0..8 {
+      bf.push(Rotation(
+        59 - i,
+        36 + i,
+        if i < 4 {
+          48
+        } else {
+          112
+        },
+        true
+      ));
+    }
+  }
+  if n >= 4 {
+    for i in 0..8 {
+      bf.push(Hadamard(i, 15 - i, false));
+    }
+  }
+  if n >= 5 {
+    for i in 0..4 {
+      bf.push(Rotation(27 - i, 20 + i, 32, true));
+    }
+  }
+  if n == 6 {
+    for i in 0..8 {
+      bf.push(Hadamard(32 + i, 47 - i, false));
+    }
+    for i in 0..8 {
+      bf.push(Hadamard(48 + i, 63 - i, true));
+    }
+  }
+  if n >= 5 {
+    for i in 0..16 {
+      bf.push(Hadamard(i, 31 - i, false));
+    }
+  }
+  if n == 6 {
+    for i in 0..8 {
+      bf.push(Rotation(55 - i, 40 + i, 32, true));
+    }
+    for i in 0..32 {
+      bf.push(Hadamard(i, 63 - i, false));
+    }
+  }
+  bf
 }
 
-pub fn inverse_transform_add(
-  input: &[i32], output: &mut [u16], stride: usize, tx_size: TxSize,
-  tx_type: TxType
-) {
-  match tx_size {
-    TxSize::TX_4X4 => iht4x4_add(input, output, stride, tx_type),
-    TxSize::TX_8X8 => iht8x8_add(input, output, stride, tx_type),
-    TxSize::TX_16X16 => iht16x16_add(input, output, stride, tx_type),
-    TxSize::TX_32X32 => iht32x32_add(input, output, stride, tx_type),
-    _ => panic!("unimplemented tx size")
+/// Butterflies of the inverse ADST of length 8 or 16, between its input and
+/// output permutations
+fn adst_butterflies(n: usize) -> Vec<Butterfly> {
+  let mut bf = Vec::new();
+  if n == 3 {
+    for i in 0..4 {
+      bf.push(Rotation(2 * i, 2 * i + 1, 60 - 16 * i, true));
+    }
+    for i in 0..4 {
+      bf.push(Hadamard(i, 4 + i, false));
+    }
+    for i in 0..2 {
+      bf.push(Rotation(4 + 3 * i, 5 + i, 48 - 32 * i, true));
+    }
+    for i in 0..2 {
+      for j in 0..2 {
+        bf.push(Hadamard(4 * j + i, 2 + 4 * j + i, false));
+      }
+    }
+    for i in 0..2 {
+      bf.push(Rotation(2 + 4 * i, 3 + 4 * i, 32, true));
+    }
+  } else {
+    for i in 0..8 {
+      bf.push(Rotation(2 * i, 2 * i + 1, 62 - 8 * i, true));
+    }
+    for i in 0..8 {
+      bf.push(Hadamard(i, 8 + i, false));
+    }
+    for i in 0..2 {
+      bf.push(Rotation(8 + 2 * i, 9 + 2 * i, 56 - 32 * i, true));
+      bf.push(Rotation(13 + 2 * i, 12 + 2 * i, 8 + 32 * i, true));
+    }
+    for i in 0..4 {
+      for j in 0..2 {
+        bf.push(Hadamard(8 * j + i, 4 + 8 * j + i, false));
+      }
+    }
+    for i in 0..2 {
+      for j in 0..2 {
+        bf.push(Rotation(4 + 8 * j + 3 * i, 5 + 8 * j + i, 48 - 32 * i, true));
+      }
+    }
+    for i in 0..2 {
+      for j in 0..4 {
+        bf.push(Hadamard(4 * j + i, 2 + 4 * j + i, false));
+      }
+    }
+    for i in 0..4 {
+      bf.push(Rotation(2 + 4 * i, 3 + 4 * i, 32, true));
+    }
   }
+  bf
 }
 
-fn fht4x4(input: &[i16], output: &mut [i32], stride: usize, tx_type: TxType) {
-  unsafe {
-    av1_fht4x4(
-      input.as_ptr(),
-      output.as_mut_ptr(),
-      stride as libc::c_int,
-      &(tx_type as i32) as *const libc::c_int
-    );
+/// Apply `butterflies` in order, clamping the Hadamard outputs to `range`
+/// bits.
+fn inv_butterflies(butterflies: &[Butterfly], t: &mut [i32], range: usize) {
+  for &bf in butterflies {
+    match bf {
+      Rotation(a, b, angle, flip) => {
+        let (x, y) = (t[a] as i64, t[b] as i64);
+        let (c, s) = (cos128(angle, COS_BIT), sin128(angle, COS_BIT));
+        let u = round_shift(x * c - y * s, COS_BIT);
+        let v = round_shift(x * s + y * c, COS_BIT);
+        if flip {
+          t[a] = v;
+          t[b] = u;
+        } else {
+          t[a] = u;
+          t[b] = v;
+        }
+      }
+      Hadamard(a, b, flip) => {
+        let (a, b) = if flip {
+          (b, a)
+        } else {
+          (a, b)
+        };
+        let (x, y) = (t[a], t[b]);
+        t[a] = clamp_value(x + y, range);
+        t[b] = clamp_value(x - y, range);
+      }
+    }
   }
 }
 
-fn iht4x4_add(
-  input: &[i32], output: &mut [u16], stride: usize, tx_type: TxType
-) {
-  unsafe {
-    av1_inv_txfm2d_add_4x4(
-      input.as_ptr(),
-      output.as_mut_ptr(),
-      stride as libc::c_int,
-      tx_type as libc::c_int,
-      8
-    );
+/// Apply the transpose of `butterflies`: the transposed steps in reverse
+/// order, with `cos_bit` bits of precision.
+fn fwd_butterflies(butterflies: &[Butterfly], t: &mut [i32], cos_bit: usize) {
+  for &bf in butterflies.iter().rev() {
+    match bf {
+      Rotation(a, b, angle, flip) => {
+        let (x, y) = if flip {
+          (t[b], t[a])
+        } else {
+          (t[a], t[b])
+        };
+        let (x, y) = (x as i64, y as i64);
+        let (c, s) = (cos128(angle, cos_bit), sin128(angle, cos_bit));
+        t[a] = round_shift(x * c + y * s, cos_bit);
+        t[b] = round_shift(y * c - x * s, cos_bit);
+      }
+      Hadamard(a, b, flip) => {
+        let (a, b) = if flip {
+          (b, a)
+        } else {
+          (a, b)
+        };
+        let (x, y) = (t[a], t[b]);
+        t[a] = x + y;
+        t[b] = x - y;
+      }
+    }
   }
 }
 
-fn fht8x8(input: &[i16], output: &mut [i32], stride: usize, tx_type: TxType) {
-  unsafe {
-    av1_fht8x8(
-      input.as_ptr(),
-      output.as_mut_ptr(),
-      stride as libc::c_int,
-      &(tx_type as i32) as *const libc::c_int
-    );
+fn iadst4(t: &mut [i32]) {
+  let sinpi = &SINPI[COS_BIT - 12];
+  let (x0, x1, x2, x3) = (t[0] as i64, t[1] as i64, t[2] as i64, t[3] as i64);
+  let s0 = sinpi[1] * x0 + sinpi[4] * x2 + sinpi[2] * x3;
+  let s1 = sinpi[2] * x0 - sinpi[1] * x2 - sinpi[4] * x3;
+  let s2 = sinpi[3] * (x0 - x2 + x3);
+  let s3 = sinpi[3] * x1;
+  t[0] = round_shift(s0 + s3, COS_BIT);
+  t[1] = round_shift(s1 + s3, COS_BIT);
+  t[2] = round_shift(s2, COS_BIT);
+  t[3] = round_shift(s0 + s1 - s3, COS_BIT);
+}
+
+fn fadst4(t: &mut [i32], cos_bit: usize) {
+  let sinpi = &SINPI[cos_bit - 12];
+  let (x0, x1, x2, x3) = (t[0] as i64, t[1] as i64, t[2] as i64, t[3] as i64);
+  let s0 = sinpi[1] * x0 + sinpi[2] * x1 + sinpi[4] * x3;
+  let s1 = sinpi[3] * (x0 + x1 - x3);
+  let s2 = sinpi[4] * x0 - sinpi[1] * x1 + sinpi[2] * x3;
+  let s3 = sinpi[3] * x2;
+  t[0] = round_shift(s0 + s3, cos_bit);
+  t[1] = round_shift(s1, cos_bit);
+  t[2] = round_shift(s2 - s3, cos_bit);
+  t[3] = round_shift(s2 - s0 + s3, cos_bit);
+}
+
+/// The identity transforms scale by sqrt(2^n / 2), like the DCT and ADST.
+fn identity(t: &mut [i32], n: usize) {
+  for v in t.iter_mut() {
+    *v = match n {
+      2 => round_shift(*v as i64 * SQRT2, 12),
+      3 => *v * 2,
+      4 => round_shift(*v as i64 * 2 * SQRT2, 12),
+      _ => *v * 4
+    };
   }
 }
 
-fn iht8x8_add(
-  input: &[i32], output: &mut [u16], stride: usize, tx_type: TxType
-) {
-  unsafe {
-    av1_inv_txfm2d_add_8x8(
-      input.as_ptr(),
-      output.as_mut_ptr(),
-      stride as libc::c_int,
-      tx_type as libc::c_int,
-      8
-    );
+#[derive(Clone, Copy, PartialEq)]
+enum TxfmType {
+  DCT,
+  ADST,
+  IDENTITY
+}
+
+/// 1-D transform of length 2^n
+struct Txfm1D {
+  txfm_type: TxfmType,
+  n: usize,
+  butterflies: Vec<Butterfly>
+}
+
+impl Txfm1D {
+  fn new(txfm_type: TxfmType, n: usize) -> Txfm1D {
+    let butterflies = match txfm_type {
+      TxfmType::DCT => dct_butterflies(n),
+      TxfmType::ADST if n > 2 => adst_butterflies(n),
+      _ => Vec::new()
+    };
+    Txfm1D {
+      txfm_type,
+      n,
+      butterflies
+    }
+  }
+
+  fn inverse(&self, t: &mut [i32], range: usize) {
+    let n = self.n;
+    let mut c = [0; 64];
+    c[..1 << n].copy_from_slice(t);
+    match self.txfm_type {
+      TxfmType::DCT => {
+        for (i, v) in t.iter_mut().enumerate() {
+          *v = c[brev(n, i)];
+        }
+        inv_butterflies(&self.butterflies, t, range);
+      }
+      TxfmType::ADST if n == 2 => iadst4(t),
+      TxfmType::ADST => {
+        for (i, v) in t.iter_mut().enumerate() {
+          *v = c[adst_in_index(n, i)];
+        }
+        inv_butterflies(&self.butterflies, t, range);
+        c[..1 << n].copy_from_slice(t);
+        for (i, v) in t.iter_mut().enumerate() {
+          let x = c[adst_out_index(n, i)];
+          *v = if i & 1 == 1 {
+            -x
+          } else {
+            x
+          };
+        }
+      }
+      TxfmType::IDENTITY => identity(t, n)
+    }
+  }
+
+  fn forward(&self, t: &mut [i32], cos_bit: usize) {
+    let n = self.n;
+    let mut c = [0; 64];
+    match self.txfm_type {
+      TxfmType::DCT => {
+        fwd_butterflies(&self.butterflies, t, cos_bit);
+        c[..1 << n].copy_from_slice(t);
+        for (i, v) in t.iter_mut().enumerate() {
+          *v = c[brev(n, i)];
+        }
+      }
+      TxfmType::ADST if n == 2 => fadst4(t, cos_bit),
+      TxfmType::ADST => {
+        for (i, &x) in t.iter().enumerate() {
+          c[adst_out_index(n, i)] = if i & 1 == 1 {
+            -x
+          } else {
+            x
+          };
+        }
+        t.copy_from_slice(&c[..1 << n]);
+        fwd_butterflies(&self.butterflies, t, cos_bit);
+        for (i, &x) in t.iter().enumerate() {
+          c[adst_in_index(n, i)] = x;
+        }
+        t.copy_from_slice(&c[..1 << n]);
+      }
+      TxfmType::IDENTITY => identity(t, n)
+    }
   }
 }
 
-fn fht16x16(
-  input: &[i16], output: &mut [i32], stride: usize, tx_type: TxType
-) {
-  unsafe {
-    av1_fht16x16(
-      input.as_ptr(),
-      output.as_mut_ptr(),
-      stride as libc::c_int,
-      &(tx_type as i32) as *const libc::c_int
-    );
+/// Vertical and horizontal 1-D transforms of `tx_type`, followed by whether
+/// they are flipped
+fn txfm_types(tx_type: TxType) -> (TxfmType, TxfmType, bool, bool) {
+  use self::TxfmType::*;
+  match tx_type {
+    TxType::DCT_DCT => (DCT, DCT, false, false),
+    TxType::ADST_DCT => (ADST, DCT, false, false),
+    TxType::DCT_ADST => (DCT, ADST, false, false),
+    TxType::ADST_ADST => (ADST, ADST, false, false),
+    TxType::FLIPADST_DCT => (ADST, DCT, true, false),
+    TxType::DCT_FLIPADST => (DCT, ADST, false, true),
+    TxType::FLIPADST_FLIPADST => (ADST, ADST, true, true),
+    TxType::ADST_FLIPADST => (ADST, ADST, false, true),
+    TxType::FLIPADST_ADST => (ADST, ADST, true, false),
+    TxType::IDTX => (IDENTITY, IDENTITY, false, false),
+    TxType::V_DCT => (DCT, IDENTITY, false, false),
+    TxType::H_DCT => (IDENTITY, DCT, false, false),
+    TxType::V_ADST => (ADST, IDENTITY, false, false),
+    TxType::H_ADST => (IDENTITY, ADST, false, false),
+    TxType::V_FLIPADST => (ADST, IDENTITY, true, false),
+    TxType::H_FLIPADST => (IDENTITY, ADST, false, true)
   }
 }
 
-fn iht16x16_add(
-  input: &[i32], output: &mut [u16], stride: usize, tx_type: TxType
+/// Coefficients are stored row by row. Only the top-left 32x32 of the
+/// coefficients of 64-point transforms are kept, in rows of at most 32.
+pub fn forward_transform(
+  input: &[i16], output: &mut [i32], stride: usize, tx_size: TxSize,
+  tx_type: TxType
 ) {
-  unsafe {
-    if tx_type < TxType::IDTX {
-      // SSE C code asserts for transform types beyond TxType::IDTX.
-      av1_inv_txfm2d_add_16x16(
-        input.as_ptr(),
-        output.as_mut_ptr(),
-        stride as libc::c_int,
-        tx_type as libc::c_int,
-        8
-      );
+  let (w, h) = (tx_size.width(), tx_size.height());
+  let (coded_w, coded_h) = (w.min(32), h.min(32));
+  let (col_type, row_type, ud_flip, lr_flip) = txfm_types(tx_type);
+  let col_txfm = Txfm1D::new(col_type, tx_size.height_log2());
+  let row_txfm = Txfm1D::new(row_type, tx_size.width_log2());
+  let (shift0, shift1, shift2) = FWD_SHIFT[tx_size as usize];
+  let (col_bit, row_bit) = FWD_COS_BIT[tx_size as usize];
+  let rect = tx_size.width_log2() + 1 == tx_size.height_log2()
+    || tx_size.height_log2() + 1 == tx_size.width_log2();
+
+  let mut buf = [0i32; 64 * 64];
+  let mut col = [0i32; 64];
+  for c in 0..w {
+    for (r, v) in col[..h].iter_mut().enumerate() {
+      let r = if ud_flip {
+        h - 1 - r
+      } else {
+        r
+      };
+      *v = (input[r * stride + c] as i32) << shift0;
+    }
+    col_txfm.forward(&mut col[..h], col_bit);
+    let c = if lr_flip {
+      w - 1 - c
     } else {
-      av1_inv_txfm2d_add_16x16_c(
-        input.as_ptr(),
-        output.as_mut_ptr(),
-        stride as libc::c_int,
-        tx_type as libc::c_int,
-        8
-      );
+      c
+    };
+    for (r, &v) in col[..h].iter().enumerate() {
+      buf[r * w + c] = round_shift(v as i64, shift1);
+    }
+  }
+
+  for r in 0..coded_h {
+    let row = &mut buf[r * w..(r + 1) * w];
+    row_txfm.forward(row, row_bit);
+    for c in 0..coded_w {
+      let v = round_shift(row[c] as i64, shift2);
+      output[r * coded_w + c] = if rect {
+        round_shift(v as i64 * SQRT2, 12)
+      } else {
+        v
+      };
     }
   }
 }
 
-fn fht32x32(
-  input: &[i16], output: &mut [i32], stride: usize, tx_type: TxType
+pub fn inverse_transform_add(
+  input: &[i32], output: &mut [u16], stride: usize, tx_size: TxSize,
+  tx_type: TxType, bit_depth: usize
 ) {
-  unsafe {
-    av1_fht32x32(
-      input.as_ptr(),
-      output.as_mut_ptr(),
-      stride as libc::c_int,
-      &(tx_type as i32) as *const libc::c_int
-    );
+  let (w, h) = (tx_size.width(), tx_size.height());
+  let (coded_w, coded_h) = (w.min(32), h.min(32));
+  let (col_type, row_type, ud_flip, lr_flip) = txfm_types(tx_type);
+  let col_txfm = Txfm1D::new(col_type, tx_size.height_log2());
+  let row_txfm = Txfm1D::new(row_type, tx_size.width_log2());
+  let row_shift = INV_ROW_SHIFT[tx_size as usize];
+  let rect = tx_size.width_log2() + 1 == tx_size.height_log2()
+    || tx_size.height_log2() + 1 == tx_size.width_log2();
+  let row_range = bit_depth + 8;
+  let col_range = (bit_depth + 6).max(16);
+
+  let mut buf = [0i32; 64 * 64];
+  for r in 0..coded_h {
+    let row = &mut buf[r * w..(r + 1) * w];
+    for c in 0..coded_w {
+      let v = input[r * coded_w + c];
+      let v = if rect {
+        round_shift(v as i64 * INV_SQRT2, 12)
+      } else {
+        v
+      };
+      row[c] = clamp_value(v, row_range);
+    }
+    row_txfm.inverse(row, row_range);
+    for v in row.iter_mut() {
+      *v = round_shift(*v as i64, row_shift);
+    }
+  }
+
+  let max_value = (1 << bit_depth) - 1;
+  let mut col = [0i32; 64];
+  for c in 0..w {
+    let c_in = if lr_flip {
+      w - 1 - c
+    } else {
+      c
+    };
+    for (r, v) in col[..h].iter_mut().enumerate() {
+      *v = clamp_value(buf[r * w + c_in], col_range);
+    }
+    col_txfm.inverse(&mut col[..h], col_range);
+    for (r, &v) in col[..h].iter().enumerate() {
+      let r = if ud_flip {
+        h - 1 - r
+      } else {
+        r
+      };
+      let p = &mut output[r * stride + c];
+      let x = *p as i32 + round_shift(v as i64, INV_COL_SHIFT);
+      *p = x.max(0).min(max_value) as u16;
+    }
   }
 }
 
-fn iht32x32_add(
-  input: &[i32], output: &mut [u16], stride: usize, tx_type: TxType
-) {
-  unsafe {
-    av1_inv_txfm2d_add_32x32(
-      input.as_ptr(),
-      output.as_mut_ptr(),
-      stride as libc::c_int,
-      tx_type as libc::c_int,
-      8
+#[cfg(test)]
+mod test {
+  use super::*;
+  use libc;
+  use rand::{ChaChaRng, Rng, SeedableRng};
+
+  extern {
+    fn av1_inv_txfm2d_add_4x4_c(
+      input: *const i32, output: *mut u16, stride: libc::c_int,
+      tx_type: libc::c_int, bd: libc::c_int
+    );
+    fn av1_inv_txfm2d_add_8x8_c(
+      input: *const i32, output: *mut u16, stride: libc::c_int,
+      tx_type: libc::c_int, bd: libc::c_int
+    );
+    fn av1_inv_txfm2d_add_16x16_c(
+      input: *const i32, output: *mut u16, stride: libc::c_int,
+      tx_type: libc::c_int, bd: libc::c_int
+    );
+    fn av1_inv_txfm2d_add_32x32_c(
+      input: *const i32, output: *mut u16, stride: libc::c_int,
+      tx_type: libc::c_int, bd: libc::c_int
     );
+    fn av1_inv_txfm2d_add_64x64_c(
+      input: *const i32, output: *mut u16, stride: libc::c_int,
+      tx_type: libc::c_int, bd: libc::c_int
+    );
+    fn av1_inv_txfm2d_add_4x8_c(
+      input: *const i32, output: *mut u16, stride: libc::c_int,
+      tx_type: libc::c_int, bd: libc::c_int
+    );
+    fn av1_inv_txfm2d_add_8x4_c(
+      input: *const i32, output: *mut u16, stride: libc::c_int,
+      tx_type: libc::c_int, bd: libc::c_int
+    );
+    fn av1_inv_txfm2d_add_8x16_c(
+      input: *const i32, output: *mut u16, stride: libc::c_int,
+      tx_type: libc::c_int, bd: libc::c_int
+    );
+    fn av1_inv_txfm2d_add_16x8_c(
+      input: *const i32, output: *mut u16, stride: libc::c_int,
+      tx_type: libc::c_int, bd: libc::c_int
+    );
+    fn av1_inv_txfm2d_add_16x32_c(
+      input: *const i32, output: *mut u16, stride: libc::c_int,
+      tx_type: libc::c_int, bd: libc::c_int
+    );
+    fn av1_inv_txfm2d_add_32x16_c(
+      input: *const i32, output: *mut u16, stride: libc::c_int,
+      tx_type: libc::c_int, bd: libc::c_int
+    );
+    fn av1_inv_txfm2d_add_32x64_c(
+      input: *const i32, output: *mut u16, stride: libc::c_int,
+      tx_type: libc::c_int, bd: libc::c_int
+    );
+    fn av1_inv_txfm2d_add_64x32_c(
+      input: *const i32, output: *mut u16, stride: libc::c_int,
+      tx_type: libc::c_int, bd: libc::c_int
+    );
+    fn av1_inv_txfm2d_add_4x16_c(
+      input: *const i32, output: *mut u16, stride: libc::c_int,
+      tx_type: libc::c_int, bd: libc::c_int
+    );
+    fn av1_inv_txfm2d_add_16x4_c(
+      input: *const i32, output: *mut u16, stride: libc::c_int,
+      tx_type: libc::c_int, bd: libc::c_int
+    );
+    fn av1_inv_txfm2d_add_8x32_c(
+      input: *const i32, output: *mut u16, stride: libc::c_int,
+      tx_type: libc::c_int, bd: libc::c_int
+    );
+    fn av1_inv_txfm2d_add_32x8_c(
+      input: *const i32, output: *mut u16, stride: libc::c_int,
+      tx_type: libc::c_int, bd: libc::c_int
+    );
+    fn av1_inv_txfm2d_add_16x64_c(
+      input: *const i32, output: *mut u16, stride: libc::c_int,
+      tx_type: libc::c_int, bd: libc::c_int
+    );
+    fn av1_inv_txfm2d_add_64x16_c(
+      input: *const i32, output: *mut u16, stride: libc::c_int,
+      tx_type: libc::c_int, bd: libc::c_int
+    );
+    fn av1_fwd_txfm2d_4x4_c(
+      input: *const i16, output: *mut i32, stride: libc::c_int,
+      tx_type: libc::c_int, bd: libc::c_int
+    );
+    fn av1_fwd_txfm2d_8x8_c(
+      input: *const i16, output: *mut i32, stride: libc::c_int,
+      tx_type: libc::c_int, bd: libc::c_int
+    );
+    fn av1_fwd_txfm2d_16x16_c(
+      input: *const i16, output: *mut i32, stride: libc::c_int,
+      tx_type: libc::c_int, bd: libc::c_int
+    );
+    fn av1_fwd_txfm2d_32x32_c(
+      input: *const i16, output: *mut i32, stride: libc::c_int,
+      tx_type: libc::c_int, bd: libc::c_int
+    );
+    fn av1_fwd_txfm2d_64x64_c(
+      input: *const i16, output: *mut i32, stride: libc::c_int,
+      tx_type: libc::c_int, bd: libc::c_int
+    );
+    fn av1_fwd_txfm2d_4x8_c(
+      input: *const i16, output: *mut i32, stride: libc::c_int,
+      tx_type: libc::c_int, bd: libc::c_int
+    );
+    fn av1_fwd_txfm2d_8x4_c(
+      input: *const i16, output: *mut i32, stride: libc::c_int,
+      tx_type: libc::c_int, bd: libc::c_int
+    );
+    fn av1_fwd_txfm2d_8x16_c(
+      input: *const i16, output: *mut i32, stride: libc::c_int,
+      tx_type: libc::c_int, bd: libc::c_int
+    );
+    fn av1_fwd_txfm2d_16x8_c(
+      input: *const i16, output: *mut i32, stride: libc::c_int,
+      tx_type: libc::c_int, bd: libc::c_int
+    );
+    fn av1_fwd_txfm2d_16x32_c(
+      input: *const i16, output: *mut i32, stride: libc::c_int,
+      tx_type: libc::c_int, bd: libc::c_int
+    );
+    fn av1_fwd_txfm2d_32x16_c(
+      input: *const i16, output: *mut i32, stride: libc::c_int,
+      tx_type: libc::c_int, bd: libc::c_int
+    );
+    fn av1_fwd_txfm2d_32x64_c(
+      input: *const i16, output: *mut i32, stride: libc::c_int,
+      tx_type: libc::c_int, bd: libc::c_int
+    );
+    fn av1_fwd_txfm2d_64x32_c(
+      input: *const i16, output: *mut i32, stride: libc::c_int,
+      tx_type: libc::c_int, bd: libc::c_int
+    );
+    fn av1_fwd_txfm2d_4x16_c(
+      input: *const i16, output: *mut i32, stride: libc::c_int,
+      tx_type: libc::c_int, bd: libc::c_int
+    );
+    fn av1_fwd_txfm2d_16x4_c(
+      input: *const i16, output: *mut i32, stride: libc::c_int,
+      tx_type: libc::c_int, bd: libc::c_int
+    );
+    fn av1_fwd_txfm2d_8x32_c(
+      input: *const i16, output: *mut i32, stride: libc::c_int,
+      tx_type: libc::c_int, bd: libc::c_int
+    );
+    fn av1_fwd_txfm2d_32x8_c(
+      input: *const i16, output: *mut i32, stride: libc::c_int,
+      tx_type: libc::c_int, bd: libc::c_int
+    );
+    fn av1_fwd_txfm2d_16x64_c(
+      input: *const i16, output: *mut i32, stride: libc::c_int,
+      tx_type: libc::c_int, bd: libc::c_int
+    );
+    fn av1_fwd_txfm2d_64x16_c(
+      input: *const i16, output: *mut i32, stride: libc::c_int,
+      tx_type: libc::c_int, bd: libc::c_int
+    );
+  }
+
+  type InvTxfmFn = unsafe extern fn(
+    *const i32,
+    *mut u16,
+    libc::c_int,
+    libc::c_int,
+    libc::c_int
+  );
+  type FwdTxfmFn = unsafe extern fn(
+    *const i16,
+    *mut i32,
+    libc::c_int,
+    libc::c_int,
+    libc::c_int
+  );
+
+  const MAX_ITER: usize = 100;
+
+  const TX_TYPES: [TxType; 16] = [
+    TxType::DCT_DCT,
+    TxType::ADST_DCT,
+    TxType::DCT_ADST,
+    TxType::ADST_ADST,
+    TxType::FLIPADST_DCT,
+    TxType::DCT_FLIPADST,
+    TxType::FLIPADST_FLIPADST,
+    TxType::ADST_FLIPADST,
+    TxType::FLIPADST_ADST,
+    TxType::IDTX,
+    TxType::V_DCT,
+    TxType::H_DCT,
+    TxType::V_ADST,
+    TxType::H_ADST,
+    TxType::V_FLIPADST,
+    TxType::H_FLIPADST
+  ];
+
+  /// Transform types allowed for `tx_size` by the AV1 transform sets
+  fn tx_types(tx_size: TxSize) -> &'static [TxType] {
+    match tx_size.sqr_up() {
+      TxSize::TX_64X64 => &TX_TYPES[..1],
+      TxSize::TX_32X32 => &[TxType::DCT_DCT, TxType::IDTX],
+      _ => &TX_TYPES
+    }
+  }
+
+  #[test]
+  fn inverse_matches_libaom() {
+    let mut ra = ChaChaRng::from_seed([0; 32]);
+    let inv_txfms: [(TxSize, InvTxfmFn); TxSize::TX_SIZES_ALL] = [
+      (TxSize::TX_4X4, av1_inv_txfm2d_add_4x4_c),
+      (TxSize::TX_8X8, av1_inv_txfm2d_add_8x8_c),
+      (TxSize::TX_16X16, av1_inv_txfm2d_add_16x16_c),
+      (TxSize::TX_32X32, av1_inv_txfm2d_add_32x32_c),
+      (TxSize::TX_64X64, av1_inv_txfm2d_add_64x64_c),
+      (TxSize::TX_4X8, av1_inv_txfm2d_add_4x8_c),
+      (TxSize::TX_8X4, av1_inv_txfm2d_add_8x4_c),
+      (TxSize::TX_8X16, av1_inv_txfm2d_add_8x16_c),
+      (TxSize::TX_16X8, av1_inv_txfm2d_add_16x8_c),
+      (TxSize::TX_16X32, av1_inv_txfm2d_add_16x32_c),
+      (TxSize::TX_32X16, av1_inv_txfm2d_add_32x16_c),
+      (TxSize::TX_32X64, av1_inv_txfm2d_add_32x64_c),
+      (TxSize::TX_64X32, av1_inv_txfm2d_add_64x32_c),
+      (TxSize::TX_4X16, av1_inv_txfm2d_add_4x16_c),
+      (TxSize::TX_16X4, av1_inv_txfm2d_add_16x4_c),
+      (TxSize::TX_8X32, av1_inv_txfm2d_add_8x32_c),
+      (TxSize::TX_32X8, av1_inv_txfm2d_add_32x8_c),
+      (TxSize::TX_16X64, av1_inv_txfm2d_add_16x64_c),
+      (TxSize::TX_64X16, av1_inv_txfm2d_add_64x16_c)
+    ];
+    for &(tx_size, inv_txfm) in inv_txfms.iter() {
+      let area = tx_size.area();
+      for &tx_type in tx_types(tx_size) {
+        for &bit_depth in [8, 10, 12].iter() {
+          for _ in 0..MAX_ITER {
+            // Mostly small coefficients, with some at the limits
+            let max_coeff = 1 << (bit_depth + 7);
+            let input: Vec<i32> = (0..area)
+              .map(|_| match ra.gen_range(0, 16) {
+                0 => ra.gen_range(-max_coeff, max_coeff),
+                1..=4 => ra.gen_range(-256, 256),
+                _ => 0
+              })
+              .collect();
+            let pixels: Vec<u16> =
+              (0..area).map(|_| ra.gen_range(0, 1 << bit_depth)).collect();
+            let mut o1 = pixels.clone();
+            let mut o2 = pixels;
+            unsafe {
+              inv_txfm(
+                input.as_ptr(),
+                o1.as_mut_ptr(),
+                tx_size.width() as libc::c_int,
+                tx_type as libc::c_int,
+                bit_depth as libc::c_int
+              );
+            }
+            inverse_transform_add(
+              &input,
+              &mut o2,
+              tx_size.width(),
+              tx_size,
+              tx_type,
+              bit_depth
+            );
+            assert!(o1 == o2);
+          }
+        }
+      }
+    }
+  }
+
+  #[test]
+  fn forward_matches_libaom() {
+    let mut ra = ChaChaRng::from_seed([0; 32]);
+    let fwd_txfms: [(TxSize, FwdTxfmFn); TxSize::TX_SIZES_ALL] = [
+      (TxSize::TX_4X4, av1_fwd_txfm2d_4x4_c),
+      (TxSize::TX_8X8, av1_fwd_txfm2d_8x8_c),
+      (TxSize::TX_16X16, av1_fwd_txfm2d_16x16_c),
+      (TxSize::TX_32X32, av1_fwd_txfm2d_32x32_c),
+      (TxSize::TX_64X64, av1_fwd_txfm2d_64x64_c),
+      (TxSize::TX_4X8, av1_fwd_txfm2d_4x8_c),
+      (TxSize::TX_8X4, av1_fwd_txfm2d_8x4_c),
+      (TxSize::TX_8X16, av1_fwd_txfm2d_8x16_c),
+      (TxSize::TX_16X8, av1_fwd_txfm2d_16x8_c),
+      (TxSize::TX_16X32, av1_fwd_txfm2d_16x32_c),
+      (TxSize::TX_32X16, av1_fwd_txfm2d_32x16_c),
+      (TxSize::TX_32X64, av1_fwd_txfm2d_32x64_c),
+      (TxSize::TX_64X32, av1_fwd_txfm2d_64x32_c),
+      (TxSize::TX_4X16, av1_fwd_txfm2d_4x16_c),
+      (TxSize::TX_16X4, av1_fwd_txfm2d_16x4_c),
+      (TxSize::TX_8X32, av1_fwd_txfm2d_8x32_c),
+      (TxSize::TX_32X8, av1_fwd_txfm2d_32x8_c),
+      (TxSize::TX_16X64, av1_fwd_txfm2d_16x64_c),
+      (TxSize::TX_64X16, av1_fwd_txfm2d_64x16_c)
+    ];
+    for &(tx_size, fwd_txfm) in fwd_txfms.iter() {
+      let area = tx_size.area();
+      let coded_area = tx_size.width().min(32) * tx_size.height().min(32);
+      for &tx_type in tx_types(tx_size) {
+        for &bit_depth in [8, 10, 12].iter() {
+          for _ in 0..MAX_ITER {
+            let max_residual = 1 << bit_depth;
+            let input: Vec<i16> = (0..area)
+              .map(|_| ra.gen_range(-max_residual + 1, max_residual))
+              .collect();
+            // libaom writes the whole block before packing the coefficients
+            // that are kept at the start of the output
+            let mut o1 = vec![0; area];
+            let mut o2 = vec![0; area];
+            unsafe {
+              fwd_txfm(
+                input.as_ptr(),
+                o1.as_mut_ptr(),
+                tx_size.width() as libc::c_int,
+                tx_type as libc::c_int,
+                bit_depth as libc::c_int
+              );
+            }
+            forward_transform(
+              &input,
+              &mut o2,
+              tx_size.width(),
+              tx_size,
+              tx_type
+            );
+            assert!(o1[..coded_area] == o2[..coded_area]);
+          }
+        }
+      }
+    }
+  }
+
+  #[test]
+  fn roundtrip() {
+    use partition::TxSize::*;
+    let mut ra = ChaChaRng::from_seed([0; 32]);
+    let tx_sizes = [
+      TX_4X4, TX_8X8, TX_16X16, TX_32X32, TX_64X64, TX_4X8, TX_8X4, TX_8X16,
+      TX_16X8, TX_16X32, TX_32X16, TX_32X64, TX_64X32, TX_4X16, TX_16X4,
+      TX_8X32, TX_32X8, TX_16X64, TX_64X16
+    ];
+    for &tx_size in tx_sizes.iter() {
+      let (w, h) = (tx_size.width(), tx_size.height());
+      for &tx_type in tx_types(tx_size) {
+        for _ in 0..MAX_ITER {
+          // Only the lower half of the frequencies of 64-point transforms
+          // are kept, so use a smooth residual for them
+          let residual: Vec<i16> = if w.max(h) == 64 {
+            let (a, b): (i32, i32) =
+              (ra.gen_range(-8, 8), ra.gen_range(-8, 8));
+            (0..w * h)
+              .map(|i| {
+                ((i % w) as i32 * a / 4 + (i / w) as i32 * b / 4) as i16
+              })
+              .collect()
+          } else {
+            (0..w * h).map(|_| ra.gen_range(-255, 256)).collect()
+          };
+          let mut coeffs = vec![0; w * h];
+          forward_transform(&residual, &mut coeffs, w, tx_size, tx_type);
+          let mut output = vec![512; w * h];
+          inverse_transform_add(&coeffs, &mut output, w, tx_size, tx_type, 10);
+          for (&r, &o) in residual.iter().zip(output.iter()) {
+            assert!((o as i32 - 512 - r as i32).abs() <= 2);
+          }
+        }
+      }
+    }
   }
 }
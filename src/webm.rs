@@ -0,0 +1,536 @@
+// Copyright (c) 2018, The rav1e contributors. All rights reserved
+//
+// This source code is subject to the terms of the BSD 2 Clause License and
+// the Alliance for Open Media Patent License 1.0. If the BSD 2 Clause License
+// was not distributed with this source code in the LICENSE file, you can
+// obtain it at www.aomedia.org/license/software. If the Alliance for Open
+// Media Patent License 1.0 was not distributed with this source code in the
+// PATENTS file, you can obtain it at www.aomedia.org/license/patent.
+
+//! Minimal Matroska/WebM muxer for a single AV1 video track.
+//!
+//! Clusters are written as soon as they are complete, followed by the Cues.
+//! The Segment size, the Duration and the SeekHead entry of the Cues are only
+//! known at the end: they are filled in by seeking back in files, and left
+//! as an unknown size and Void elements in other outputs.
+
+use super::*;
+use std;
+
+const EBML: u32 = 0x1A45DFA3;
+const EBML_VERSION: u32 = 0x4286;
+const EBML_READ_VERSION: u32 = 0x42F7;
+const EBML_MAX_ID_LENGTH: u32 = 0x42F2;
+const EBML_MAX_SIZE_LENGTH: u32 = 0x42F3;
+const DOC_TYPE: u32 = 0x4282;
+const DOC_TYPE_VERSION: u32 = 0x4287;
+const DOC_TYPE_READ_VERSION: u32 = 0x4285;
+
+const SEGMENT: u32 = 0x18538067;
+const SEEK_HEAD: u32 = 0x114D9B74;
+const SEEK: u32 = 0x4DBB;
+const SEEK_ID: u32 = 0x53AB;
+const SEEK_POSITION: u32 = 0x53AC;
+
+const INFO: u32 = 0x1549A966;
+const TIMECODE_SCALE: u32 = 0x2AD7B1;
+const DURATION: u32 = 0x4489;
+const MUXING_APP: u32 = 0x4D80;
+const WRITING_APP: u32 = 0x5741;
+
+const TRACKS: u32 = 0x1654AE6B;
+const TRACK_ENTRY: u32 = 0xAE;
+const TRACK_NUMBER: u32 = 0xD7;
+const TRACK_UID: u32 = 0x73C5;
+const TRACK_TYPE: u32 = 0x83;
+const FLAG_LACING: u32 = 0x9C;
+const CODEC_ID: u32 = 0x86;
+const CODEC_PRIVATE: u32 = 0x63A2;
+const DEFAULT_DURATION: u32 = 0x23E383;
+const VIDEO: u32 = 0xE0;
+const PIXEL_WIDTH: u32 = 0xB0;
+const PIXEL_HEIGHT: u32 = 0xBA;
+
+const CLUSTER: u32 = 0x1F43B675;
+const TIMECODE: u32 = 0xE7;
+const SIMPLE_BLOCK: u32 = 0xA3;
+
+const CUES: u32 = 0x1C53BB6B;
+const CUE_POINT: u32 = 0xBB;
+const CUE_TIME: u32 = 0xB3;
+const CUE_TRACK_POSITIONS: u32 = 0xB7;
+const CUE_TRACK: u32 = 0xF7;
+const CUE_CLUSTER_POSITION: u32 = 0xF1;
+
+const VOID: u32 = 0xEC;
+
+/// Timestamps are in milliseconds
+const TIMECODE_SCALE_NS: u64 = 1_000_000;
+const TRACK_TYPE_VIDEO: u64 = 1;
+const VIDEO_TRACK: u64 = 1;
+/// Size with all value bits set, on 8 bytes
+const UNKNOWN_SIZE: u64 = (1 << 56) - 1;
+
+fn write_id(buf: &mut Vec<u8>, id: u32) {
+  // The length of an element ID is encoded in its leading bits
+  let len = 4 - (id.leading_zeros() / 8) as usize;
+  for i in (0..len).rev() {
+    buf.push((id >> (8 * i)) as u8);
+  }
+}
+
+fn write_size(buf: &mut Vec<u8>, size: u64) {
+  // A size with all value bits set means unknown, so use a longer encoding
+  let mut len = 1;
+  while len < 8 && size >= (1 << (7 * len)) - 1 {
+    len += 1;
+  }
+  write_size_sized(buf, size, len);
+}
+
+/// Size encoded on `len` bytes, for sizes patched once known.
+fn write_size_sized(buf: &mut Vec<u8>, size: u64, len: usize) {
+  let size = size | (1 << (7 * len));
+  for i in (0..len).rev() {
+    buf.push((size >> (8 * i)) as u8);
+  }
+}
+
+fn write_element(buf: &mut Vec<u8>, id: u32, payload: &[u8]) {
+  write_id(buf, id);
+  write_size(buf, payload.len() as u64);
+  buf.extend_from_slice(payload);
+}
+
+fn write_uint(buf: &mut Vec<u8>, id: u32, value: u64) {
+  let len = std::cmp::max(1, 8 - (value.leading_zeros() / 8) as usize);
+  write_uint_sized(buf, id, value, len);
+}
+
+/// Unsigned integer element with a fixed width, for values whose size must
+/// be known before the value itself.
+fn write_uint_sized(buf: &mut Vec<u8>, id: u32, value: u64, len: usize) {
+  let mut payload = Vec::new();
+  for i in (0..len).rev() {
+    payload.push((value >> (8 * i)) as u8);
+  }
+  write_element(buf, id, &payload);
+}
+
+fn write_float(buf: &mut Vec<u8>, id: u32, value: f64) {
+  write_uint_sized(buf, id, value.to_bits(), 8);
+}
+
+fn write_string(buf: &mut Vec<u8>, id: u32, value: &str) {
+  write_element(buf, id, value.as_bytes());
+}
+
+/// Void element of `len` bytes, reserving room for an element written later
+fn write_void(buf: &mut Vec<u8>, len: usize) {
+  // One byte of ID and one of size
+  assert!(len >= 2);
+  assert!(len < 0x80);
+  write_element(buf, VOID, &vec![0; len - 2]);
+}
+
+fn overwrite(
+  file: &mut File, position: u64, data: &[u8]
+) -> Result<(), EncoderError> {
+  file.seek(SeekFrom::Start(position))?;
+  file.write_all(data)?;
+  Ok(())
+}
+
+fn seek_entry(id: u32, position: u64) -> Vec<u8> {
+  let mut seek_id = Vec::new();
+  write_id(&mut seek_id, id);
+  let mut seek = Vec::new();
+  write_element(&mut seek, SEEK_ID, &seek_id);
+  // Fixed width so that the SeekHead size does not depend on positions
+  write_uint_sized(&mut seek, SEEK_POSITION, position, 8);
+  let mut buf = Vec::new();
+  write_element(&mut buf, SEEK, &seek);
+  buf
+}
+
+struct CuePoint {
+  time: u64,
+  cluster_position: u64
+}
+
+pub struct WebMMuxer {
+  output: Output,
+  width: usize,
+  height: usize,
+  num: usize,
+  den: usize,
+  codec_private: Vec<u8>,
+  cluster: Vec<u8>,
+  cluster_time: u64,
+  cues: Vec<CuePoint>,
+  duration: u64,
+  /// Offset of the Segment payload in the output
+  segment_offset: u64,
+  /// Size of the Segment payload written so far
+  position: u64,
+  /// Positions in the Segment of the placeholders for the SeekHead entry of
+  /// the Cues and for the Duration
+  cues_seek_position: u64,
+  duration_position: u64
+}
+
+impl WebMMuxer {
+  pub fn new(
+    output: Output, sequence: &Sequence, num: usize, den: usize
+  ) -> Result<WebMMuxer, EncoderError> {
+    let mut muxer = WebMMuxer {
+      output,
+      width: sequence.max_frame_width as usize,
+      height: sequence.max_frame_height as usize,
+      num,
+      den,
+      codec_private: av1c_record(sequence)?,
+      cluster: Vec::new(),
+      cluster_time: 0,
+      cues: Vec::new(),
+      duration: 0,
+      segment_offset: 0,
+      position: 0,
+      cues_seek_position: 0,
+      duration_position: 0
+    };
+
+    // Positions are relative to the start of the Segment payload, and the
+    // placeholders end their parent elements
+    let info = muxer.info();
+    let tracks = muxer.tracks();
+    let seek_head_size = muxer.seek_head(&[(INFO, 0), (TRACKS, 0)]).len();
+    let info_offset = seek_head_size as u64;
+    let tracks_offset = info_offset + info.len() as u64;
+    let seek_head = muxer.seek_head(&[
+      (INFO, info_offset),
+      (TRACKS, tracks_offset)
+    ]);
+    muxer.cues_seek_position =
+      info_offset - seek_entry(CUES, 0).len() as u64;
+    muxer.duration_position =
+      tracks_offset - muxer.duration_element().len() as u64;
+
+    let mut header = Vec::new();
+    muxer.write_ebml_header(&mut header);
+    write_id(&mut header, SEGMENT);
+    write_size_sized(&mut header, UNKNOWN_SIZE, 8);
+    muxer.segment_offset = header.len() as u64;
+    header.extend_from_slice(&seek_head);
+    header.extend_from_slice(&info);
+    header.extend_from_slice(&tracks);
+    muxer.output.write_all(&header)?;
+    muxer.position = tracks_offset + tracks.len() as u64;
+    Ok(muxer)
+  }
+
+  /// Presentation time of frame `pts` in milliseconds
+  fn timecode(&self, pts: u64) -> u64 {
+    pts * self.den as u64 * 1000 / self.num as u64
+  }
+
+  fn flush_cluster(&mut self) -> Result<(), EncoderError> {
+    if !self.cluster.is_empty() {
+      let mut cluster = Vec::with_capacity(self.cluster.len() + 12);
+      write_element(&mut cluster, CLUSTER, &self.cluster);
+      self.output.write_all(&cluster)?;
+      self.position += cluster.len() as u64;
+      self.cluster.clear();
+    }
+    Ok(())
+  }
+
+  fn write_ebml_header(&self, buf: &mut Vec<u8>) {
+    let mut header = Vec::new();
+    write_uint(&mut header, EBML_VERSION, 1);
+    write_uint(&mut header, EBML_READ_VERSION, 1);
+    write_uint(&mut header, EBML_MAX_ID_LENGTH, 4);
+    write_uint(&mut header, EBML_MAX_SIZE_LENGTH, 8);
+    write_string(&mut header, DOC_TYPE, "webm");
+    write_uint(&mut header, DOC_TYPE_VERSION, 4);
+    write_uint(&mut header, DOC_TYPE_READ_VERSION, 2);
+    write_element(buf, EBML, &header);
+  }
+
+  fn duration_element(&self) -> Vec<u8> {
+    let mut buf = Vec::new();
+    write_float(&mut buf, DURATION, self.duration as f64);
+    buf
+  }
+
+  fn info(&self) -> Vec<u8> {
+    let mut info = Vec::new();
+    write_uint(&mut info, TIMECODE_SCALE, TIMECODE_SCALE_NS);
+    write_string(&mut info, MUXING_APP, "rav1e");
+    write_string(&mut info, WRITING_APP, "rav1e");
+    write_void(&mut info, self.duration_element().len());
+    let mut buf = Vec::new();
+    write_element(&mut buf, INFO, &info);
+    buf
+  }
+
+  fn tracks(&self) -> Vec<u8> {
+    let mut video = Vec::new();
+    write_uint(&mut video, PIXEL_WIDTH, self.width as u64);
+    write_uint(&mut video, PIXEL_HEIGHT, self.height as u64);
+
+    let mut entry = Vec::new();
+    write_uint(&mut entry, TRACK_NUMBER, VIDEO_TRACK);
+    write_uint(&mut entry, TRACK_UID, VIDEO_TRACK);
+    write_uint(&mut entry, TRACK_TYPE, TRACK_TYPE_VIDEO);
+    write_uint(&mut entry, FLAG_LACING, 0);
+    write_string(&mut entry, CODEC_ID, "V_AV1");
+    write_element(&mut entry, CODEC_PRIVATE, &self.codec_private);
+    write_uint(
+      &mut entry,
+      DEFAULT_DURATION,
+      self.den as u64 * 1_000_000_000 / self.num as u64
+    );
+    write_element(&mut entry, VIDEO, &video);
+
+    let mut tracks = Vec::new();
+    write_element(&mut tracks, TRACK_ENTRY, &entry);
+    let mut buf = Vec::new();
+    write_element(&mut buf, TRACKS, &tracks);
+    buf
+  }
+
+  fn cues(&self) -> Vec<u8> {
+    let mut cues = Vec::new();
+    for cue in &self.cues {
+      let mut positions = Vec::new();
+      write_uint(&mut positions, CUE_TRACK, VIDEO_TRACK);
+      write_uint(&mut positions, CUE_CLUSTER_POSITION, cue.cluster_position);
+      let mut point = Vec::new();
+      write_uint(&mut point, CUE_TIME, cue.time);
+      write_element(&mut point, CUE_TRACK_POSITIONS, &positions);
+      write_element(&mut cues, CUE_POINT, &point);
+    }
+    let mut buf = Vec::new();
+    write_element(&mut buf, CUES, &cues);
+    buf
+  }
+
+  /// SeekHead with `entries`, ending with a placeholder for the Cues
+  fn seek_head(&self, entries: &[(u32, u64)]) -> Vec<u8> {
+    let mut seek_head = Vec::new();
+    for &(id, position) in entries {
+      seek_head.extend_from_slice(&seek_entry(id, position));
+    }
+    write_void(&mut seek_head, seek_entry(CUES, 0).len());
+    let mut buf = Vec::new();
+    write_element(&mut buf, SEEK_HEAD, &seek_head);
+    buf
+  }
+}
+
+impl Muxer for WebMMuxer {
+  fn write_frame(
+    &mut self, pts: u64, data: &[u8], frame_type: FrameType
+  ) -> Result<(), EncoderError> {
+    let time = self.timecode(pts);
+    let keyframe = frame_type == FrameType::KEY;
+
+    // Clusters start on keyframes so that they are all seek points, and
+    // whenever the relative block timecode would overflow or be negative,
+    // as when the caller sends decreasing timestamps.
+    let in_cluster = match time.checked_sub(self.cluster_time) {
+      Some(relative_time) => relative_time <= i16::max_value() as u64,
+      None => false
+    };
+    if self.cluster.is_empty() || keyframe || !in_cluster {
+      self.flush_cluster()?;
+      self.cluster_time = time;
+      write_uint(&mut self.cluster, TIMECODE, time);
+      if keyframe {
+        self.cues.push(CuePoint { time, cluster_position: self.position });
+      }
+    }
+
+    let data = strip_temporal_delimiter(data);
+    let mut block = Vec::with_capacity(data.len() + 4);
+    write_size(&mut block, VIDEO_TRACK);
+    let relative_time = (time - self.cluster_time) as i16;
+    block.push((relative_time >> 8) as u8);
+    block.push(relative_time as u8);
+    block.push(if keyframe { 0x80 } else { 0 });
+    block.extend_from_slice(data);
+    write_element(&mut self.cluster, SIMPLE_BLOCK, &block);
+
+    self.duration = std::cmp::max(self.duration, self.timecode(pts + 1));
+    Ok(())
+  }
+
+  fn finish(&mut self) -> Result<(), EncoderError> {
+    self.flush_cluster()?;
+    let cues_position = self.position;
+    let cues = self.cues();
+    self.output.write_all(&cues)?;
+    self.position += cues.len() as u64;
+
+    // The placeholders can only be filled in seekable outputs
+    let duration = self.duration_element();
+    if let Output::File(ref mut f) = self.output {
+      let end = f.seek(SeekFrom::Current(0))?;
+      let mut segment_size = Vec::new();
+      write_size_sized(&mut segment_size, self.position, 8);
+      overwrite(f, self.segment_offset - 8, &segment_size)?;
+      overwrite(
+        f,
+        self.segment_offset + self.cues_seek_position,
+        &seek_entry(CUES, cues_position)
+      )?;
+      overwrite(f, self.segment_offset + self.duration_position, &duration)?;
+      f.seek(SeekFrom::Start(end))?;
+    }
+    self.output.flush()?;
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  // Element IDs keep their length marker, sizes lose it
+  fn read_vint(data: &[u8], keep_marker: bool) -> (u64, usize) {
+    let len = data[0].leading_zeros() as usize + 1;
+    let mut value = data[..len].iter().fold(0, |v, &b| v << 8 | b as u64);
+    if !keep_marker {
+      value &= !(1 << (7 * len));
+    }
+    (value, len)
+  }
+
+  fn elements(mut data: &[u8]) -> Vec<(u32, &[u8])> {
+    let mut elements = Vec::new();
+    while !data.is_empty() {
+      let (id, id_len) = read_vint(data, true);
+      let (size, size_len) = read_vint(&data[id_len..], false);
+      let start = id_len + size_len;
+      let end = if size == UNKNOWN_SIZE {
+        data.len()
+      } else {
+        start + size as usize
+      };
+      elements.push((id as u32, &data[start..end]));
+      data = &data[end..];
+    }
+    elements
+  }
+
+  fn find(data: &[u8], id: u32) -> &[u8] {
+    elements(data).into_iter().find(|e| e.0 == id).unwrap().1
+  }
+
+  fn uint(data: &[u8]) -> u64 {
+    data.iter().fold(0, |v, &b| v << 8 | b as u64)
+  }
+
+  fn mux(name: &str, frames: &[(u64, FrameType)]) -> Vec<u8> {
+    ::test::muxed(name, |output| {
+      let mut muxer =
+        WebMMuxer::new(output, &Sequence::new(64, 64), 1000, 1)?;
+      for &(pts, frame_type) in frames {
+        muxer.write_frame(pts, &[0xAA], frame_type)?;
+      }
+      muxer.finish()
+    })
+  }
+
+  #[test]
+  fn ebml_sizes() {
+    let size = |size| {
+      let mut buf = Vec::new();
+      write_size(&mut buf, size);
+      buf
+    };
+    assert_eq!(size(0), [0x80]);
+    assert_eq!(size(126), [0xFE]);
+    // All value bits set would mean an unknown size
+    assert_eq!(size(127), [0x40, 0x7F]);
+    assert_eq!(size(16382), [0x7F, 0xFE]);
+    assert_eq!(size(16383), [0x20, 0x3F, 0xFF]);
+    let mut unknown = Vec::new();
+    write_size_sized(&mut unknown, UNKNOWN_SIZE, 8);
+    assert_eq!(unknown, [0x01, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]);
+  }
+
+  #[test]
+  fn cluster_on_relative_timecode_overflow() {
+    // One frame per millisecond
+    let data = mux(
+      "cluster_on_relative_timecode_overflow.webm",
+      &[
+        (0, FrameType::KEY),
+        (1, FrameType::INTER),
+        (32767, FrameType::INTER),
+        (32768, FrameType::INTER),
+        (32000, FrameType::INTER)
+      ]
+    );
+    let clusters: Vec<(u64, Vec<u64>)> = elements(find(&data, SEGMENT))
+      .into_iter()
+      .filter(|e| e.0 == CLUSTER)
+      .map(|(_, cluster)| {
+        let elements = elements(cluster);
+        let time = uint(elements[0].1);
+        let blocks = elements[1..]
+          .iter()
+          .map(|block| uint(&block.1[1..3]))
+          .collect();
+        (time, blocks)
+      }).collect();
+    assert_eq!(
+      clusters,
+      [(0, vec![0, 1, 32767]), (32768, vec![0]), (32000, vec![0])]
+    );
+  }
+
+  #[test]
+  fn cues_positions() {
+    let data = mux(
+      "cues_positions.webm",
+      &[(0, FrameType::KEY), (1, FrameType::INTER), (2, FrameType::KEY)]
+    );
+    let top = elements(&data);
+    assert_eq!(top.len(), 2);
+    assert_eq!(top[1].0, SEGMENT);
+    let segment = top[1].1;
+    // The Segment size was filled in, on 8 bytes
+    let size_offset = data.len() - segment.len() - 8;
+    assert_eq!(read_vint(&data[size_offset..], false).0, segment.len() as u64);
+
+    // The SeekHead points to each element, Cues included
+    let seeks = elements(find(segment, SEEK_HEAD));
+    assert!(seeks.iter().all(|seek| seek.0 == SEEK));
+    let mut targets: Vec<u32> = seeks.iter().map(|&(_, seek)| {
+      let id = uint(find(seek, SEEK_ID)) as u32;
+      let position = uint(find(seek, SEEK_POSITION)) as usize;
+      assert_eq!(elements(&segment[position..])[0].0, id);
+      id
+    }).collect();
+    targets.sort();
+    assert_eq!(targets, [INFO, TRACKS, CUES]);
+
+    // Each keyframe starts a cluster, referenced by its cue
+    let cue_points = elements(find(segment, CUES));
+    assert_eq!(cue_points.len(), 2);
+    for (&(_, point), &time) in cue_points.iter().zip(&[0, 2]) {
+      assert_eq!(uint(find(point, CUE_TIME)), time);
+      let positions = find(point, CUE_TRACK_POSITIONS);
+      let position = uint(find(positions, CUE_CLUSTER_POSITION)) as usize;
+      let (id, cluster) = elements(&segment[position..])[0];
+      assert_eq!(id, CLUSTER);
+      assert_eq!(uint(find(cluster, TIMECODE)), time);
+    }
+
+    let duration = find(find(segment, INFO), DURATION);
+    assert_eq!(f64::from_bits(uint(duration)), 3.0);
+  }
+}